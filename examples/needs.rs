@@ -0,0 +1,82 @@
+use bevy::{ecs::component::ComponentId, prelude::*};
+use bevy_observed_utility::prelude::*;
+
+/// The same actor as in the `thirst` example, but built on the [`Needs`] module instead of hand-rolled components.
+pub fn spawn_entities(mut commands: Commands, actions: Res<ActionIds>) {
+    let thirsty = commands.spawn((NeedScore::new("thirst"), Score::default())).id();
+    let hungry = commands
+        .spawn((
+            NeedScore::new("hunger").with_curve(PowerEvaluator::from_power(2.)),
+            Score::default(),
+        ))
+        .id();
+
+    commands
+        .spawn((
+            Name::new("Actor"),
+            Picker::new(actions.idle)
+                .with(thirsty, actions.drink)
+                .with(hungry, actions.eat),
+            Needs::default()
+                .with("thirst", Need::new(4.))
+                .with("hunger", Need::new(2.)),
+            FirstToScore::new(0.5),
+        ))
+        .add_children(&[thirsty, hungry]);
+}
+
+pub fn print_needs(actors: Query<(&Needs, Option<&CurrentAction>)>, actions: Res<ActionIds>) {
+    for (needs, current_action) in actors.iter() {
+        let action = match current_action.map(|ca| ca.0) {
+            Some(action) if action == actions.drink => "DRINKING!",
+            Some(action) if action == actions.eat => "EATING!",
+            _ => "Idle",
+        };
+        let thirst = needs.get("thirst").map_or(0., |need| need.value);
+        let hunger = needs.get("hunger").map_or(0., |need| need.value);
+        println!("Thirst: {thirst:.1}, Hunger: {hunger:.1} - {action}");
+    }
+}
+
+#[derive(Component)]
+pub struct Drinking;
+
+#[derive(Component)]
+pub struct Eating;
+
+#[derive(Component)]
+pub struct Idle;
+
+#[derive(Resource)]
+pub struct ActionIds {
+    drink: ComponentId,
+    eat: ComponentId,
+    idle: ComponentId,
+}
+
+impl FromWorld for ActionIds {
+    fn from_world(world: &mut World) -> Self {
+        let actions = Self {
+            drink: world.register_component::<Drinking>(),
+            eat: world.register_component::<Eating>(),
+            idle: world.register_component::<Idle>(),
+        };
+
+        // Declare how the actions satisfy needs, instead of writing a system for each.
+        let mut satisfactions = world.resource_mut::<NeedSatisfactions>();
+        satisfactions.insert(actions.drink, Satisfaction::new("thirst", 8.).until(10.));
+        satisfactions.insert(actions.eat, Satisfaction::new("hunger", 5.).until(20.));
+
+        actions
+    }
+}
+
+fn main() {
+    App::new()
+        .add_plugins(MinimalPlugins)
+        .add_plugins((ObservedUtilityPlugins::RealTime, NeedsPlugin::default()))
+        .init_resource::<ActionIds>()
+        .add_systems(Startup, spawn_entities)
+        .add_systems(FixedUpdate, print_needs.after(NeedsPlugin::satisfy_needs))
+        .run();
+}
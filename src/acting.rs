@@ -5,8 +5,13 @@
 //! However, the library does provide these types:
 //! - [`RequestAction`] event to request a specific action or the picked action to be initiated for the target actor entity.
 //! - [`OnActionInitiated`] event to indicate that an action has been initiated. This should be listened to by action observers.
+//! - [`RejectAction`] event to fall back to the next-best choice when an initiated action can't actually start.
 //! - [`OnActionEnded`] event to indicate that an action has completed or been cancelled. This should be listened to by action observers.
+//! - [`Interrupt`] event to make an actor entity rescore, re-pick, and request an action immediately.
+//! - [`AiPaused`] component to pause the AI of an actor entity, see the [pause](crate::pause) module.
+//! - [`ActionOverrides`] component to force actions on an actor entity, see the [control](crate::control) module.
 //! - [`CurrentAction`] component to store the current action being performed by an actor entity, for easy access.
+//! - [`ActionHistory`](crate::history::ActionHistory) component to record the actions an actor entity has performed, see the [history](crate::history) module.
 //!
 //! And, these observers:
 //! - [`on_action_initiated_insert_default`] to insert a default instance of an action component when it is initiated.
@@ -18,7 +23,10 @@
 use bevy::{ecs::component::ComponentId, prelude::*};
 
 use crate::{
-    event::{ActionEndReason, OnActionEnded, OnActionInitiated, RequestAction},
+    control::ActionOverrides,
+    ecs::score_and_pick,
+    event::{ActionEndReason, Interrupt, OnActionEnded, OnActionInitiated, OnPicked, RejectAction, RequestAction},
+    pause::{AiPaused, GlobalAiPause},
     picking::Picker,
 };
 
@@ -28,11 +36,15 @@ pub struct ActionPlugin;
 impl Plugin for ActionPlugin {
     fn build(&self, app: &mut App) {
         app.add_observer(Self::on_request_cancel_and_initiate)
-            .add_observer(Self::on_ended_request_again);
+            .add_observer(Self::on_ended_request_again)
+            .add_observer(Self::on_interrupt_rescore_and_request)
+            .add_observer(Self::on_reject_initiate_next);
 
         app.register_type::<CurrentAction>();
 
         app.register_type::<RequestAction>()
+            .register_type::<Interrupt>()
+            .register_type::<RejectAction>()
             .register_type::<OnActionInitiated>()
             .register_type::<OnActionEnded>();
     }
@@ -41,16 +53,25 @@ impl Plugin for ActionPlugin {
 impl ActionPlugin {
     /// [`System`] that listens for [`RequestAction`] events and cancels the current action
     /// and initiates the picked action for the target actor entity.
+    ///
+    /// If the actor has an active [`ActionOverrides`] layer, its overriding action is initiated instead.
+    /// Requests are ignored while the actor's AI is paused.
     pub fn on_request_cancel_and_initiate(
         trigger: On<RequestAction>,
         mut commands: Commands,
-        mut actors: Query<(&Picker, Option<&CurrentAction>)>,
+        mut actors: Query<(&Picker, Option<&CurrentAction>, Option<&ActionOverrides>)>,
+        paused: Query<(), With<AiPaused>>,
+        global: Option<Res<GlobalAiPause>>,
     ) {
         let actor = trigger.event().entity;
+        if global.is_some_and(|global| global.is_paused()) || paused.contains(actor) {
+            return;
+        }
         let requested = trigger.event().action;
-        if let Ok((picker, current_action)) = actors.get_mut(actor) {
+        if let Ok((picker, current_action, overrides)) = actors.get_mut(actor) {
             let current_action = current_action.map(|ca| ca.0);
             let next_action = requested.unwrap_or(picker.picked);
+            let next_action = overrides.map_or(next_action, |overrides| overrides.resolve(next_action));
 
             if let Some(current_action) = current_action {
                 if next_action == current_action {
@@ -86,6 +107,70 @@ impl ActionPlugin {
             }
         }
     }
+
+    /// [`Observer`] that listens for [`RejectAction`] events, cancels the rejected action,
+    /// and initiates the next-ranked choice of the actor's last pick, triggering [`OnPicked`] for it.
+    ///
+    /// If the rejected action is forced by the active [`ActionOverrides`] layer, the layer is popped instead,
+    /// as falling back to the pick would resolve to the same override again.
+    pub fn on_reject_initiate_next(
+        trigger: On<RejectAction>,
+        mut commands: Commands,
+        mut actors: Query<(&mut Picker, Option<&CurrentAction>, Option<&mut ActionOverrides>)>,
+    ) {
+        let RejectAction { entity: actor, action } = *trigger.event();
+        let Ok((mut picker, current_action, overrides)) = actors.get_mut(actor) else {
+            return;
+        };
+        if current_action.is_none_or(|ca| ca.0 != action) {
+            // The rejected action isn't running anymore.
+            return;
+        }
+        if let Some(mut overrides) = overrides
+            && let Some((layer, overriding)) = overrides.active()
+        {
+            if overriding == action {
+                overrides.pop(layer);
+                commands.trigger(OnActionEnded::cancelled(actor, action));
+                commands.entity(actor).remove::<CurrentAction>();
+                commands.trigger(RequestAction::picked(actor));
+            }
+            // Otherwise the override doesn't allow falling back to another choice.
+            return;
+        }
+        if picker.is_default(action) {
+            // There's nothing left to fall back to.
+            return;
+        }
+
+        let next_action = picker.reject(action);
+        // Let pick observers such as `PickResult` see the fallback pick
+        commands.trigger(OnPicked {
+            entity: actor,
+            action: next_action,
+        });
+        // Cancel the rejected action, so the next action is initiated even if it's the same action for another choice
+        commands.trigger(OnActionEnded::cancelled(actor, action));
+        commands.entity(actor).remove::<CurrentAction>();
+        commands.trigger(RequestAction::specific(actor, next_action));
+    }
+
+    /// [`Observer`] that listens for [`Interrupt`] events and rescores the score trees of the target actor entity,
+    /// then re-picks and requests the picked action.
+    pub fn on_interrupt_rescore_and_request(
+        trigger: On<Interrupt>,
+        mut commands: Commands,
+        actors: Query<(), With<Picker>>,
+    ) {
+        let actor = trigger.event().entity;
+        if !actors.contains(actor) {
+            // The entity is not an actor.
+            return;
+        }
+
+        commands.queue(score_and_pick(actor, true));
+        commands.trigger(RequestAction::picked(actor));
+    }
 }
 
 /// [`Component`] for the current action picked by a [`Picker`].
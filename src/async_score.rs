@@ -0,0 +1,477 @@
+//! Asynchronous considerations score entities from work that runs on the [`AsyncComputeTaskPool`], such as pathfinding.
+//!
+//! An [`AsyncScore`] entity starts a task when it's scored. Once the task completes, the [`AsyncScorePlugin`]
+//! writes the real score, which parents pick up the next time they're scored.
+//!
+//! Rescoring the entity cancels the pending task, as its inputs may be stale, and starts a new one.
+//! Tasks that take longer than the scoring interval never complete this way, so either rescore such entities
+//! less often, such as with a [`ScoreCache`](crate::scoring::ScoreCache),
+//! or [keep pending tasks](AsyncScore::with_keep_pending) running to completion while the entity keeps being scored.
+//!
+//! Until the first task completes, the entity holds a configurable placeholder [`Score`], or keeps its last one.
+//! After that, it keeps the last result while refreshing it in the background.
+//! Trigger [`InvalidateScoreCache`] when the inputs of the task change, to cancel the pending task
+//! and fall back to the placeholder until a task started from the new inputs completes.
+//! Tasks are also cancelled when the entity is despawned or its [`AsyncScore`] is removed.
+//!
+//! # Example
+//!
+//! ```rust
+//! use bevy::{prelude::*, tasks::{AsyncComputeTaskPool, TaskPool}};
+//! use bevy_observed_utility::prelude::*;
+//!
+//! #[derive(Component)]
+//! struct Target(Vec2);
+//!
+//! # AsyncComputeTaskPool::get_or_init(TaskPool::default);
+//! # let mut app = App::new();
+//! # app.add_plugins(ObservedUtilityPlugins::RealTime);
+//! app.add_plugins(AsyncScorePlugin::default());
+//!
+//! # let world = app.world_mut();
+//! let path_cost = world
+//!     .spawn((
+//!         Target(Vec2::new(30., 40.)),
+//!         // Score 0 until the path cost is known, and don't pick the choice before then.
+//!         AsyncScore::new(|entity, world| {
+//!             let target = world.get::<Target>(entity).map_or(Vec2::ZERO, |target| target.0);
+//!             // Stand-in for an expensive pathfinding query.
+//!             async move { 1. - target.length() / 100. }
+//!         })
+//!         .with_placeholder(Score::MIN)
+//!         .with_ineligible_while_pending(),
+//!         Score::default(),
+//!     ))
+//!     .id();
+//!
+//! world.trigger(RunScoring::entity(path_cost));
+//! # world.flush();
+//! assert!(world.get::<AsyncScore>(path_cost).unwrap().is_pending());
+//! ```
+
+use std::{future::Future, pin::Pin, sync::Arc};
+
+use bevy::{
+    ecs::{
+        component::StorageType,
+        lifecycle::{ComponentHook, HookContext},
+        schedule::{InternedScheduleLabel, ScheduleLabel},
+        world::DeferredWorld,
+    },
+    prelude::*,
+    tasks::{AsyncComputeTaskPool, Task, futures::check_ready},
+};
+
+use crate::{
+    ecs::DeferredWorldExt,
+    event::{InvalidateScoreCache, OnScore},
+    picking::PendingScore,
+    scoring::Score,
+};
+
+/// [`Plugin`] that writes the scores of completed [`AsyncScore`] tasks in the configured [`Schedule`].
+pub struct AsyncScorePlugin {
+    /// The [`ScheduleLabel`] to poll tasks in.
+    pub update_in: InternedScheduleLabel,
+}
+
+impl Default for AsyncScorePlugin {
+    fn default() -> Self {
+        Self {
+            update_in: FixedUpdate.intern(),
+        }
+    }
+}
+
+impl Plugin for AsyncScorePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(self.update_in, Self::poll_tasks);
+
+        // Note: AsyncScore cannot be reflected due to the boxed task and start function
+    }
+}
+
+impl AsyncScorePlugin {
+    /// [`System`] that writes the scores of completed [`AsyncScore`] tasks,
+    /// making their entities eligible again if needed.
+    pub fn poll_tasks(mut commands: Commands, mut scores: Query<(Entity, &mut AsyncScore, &mut Score)>) {
+        for (entity, mut async_score, mut score) in scores.iter_mut() {
+            let Some(task) = async_score.task.as_mut() else {
+                continue;
+            };
+            let Some(value) = check_ready(task) else {
+                continue;
+            };
+
+            async_score.task = None;
+            async_score.has_result = true;
+            *score = Score::new(value);
+            if async_score.ineligible_while_pending {
+                commands.entity(entity).remove::<PendingScore>();
+            }
+        }
+    }
+}
+
+/// The boxed function that starts an [`AsyncScore`] task.
+type StartFn = dyn Fn(Entity, &World) -> Pin<Box<dyn Future<Output = f32> + Send + Sync>> + Send + Sync;
+
+/// [`Score`] [`Component`] that scores the result of an asynchronous task, from `0` to `1`.
+///
+/// See the [module docs](crate::async_score) for more information.
+pub struct AsyncScore {
+    /// Starts a task for the score entity, reading its inputs from the [`World`].
+    start: Arc<StartFn>,
+    /// The score to hold until the first task completes, or [`None`] to keep the last score.
+    placeholder: Option<Score>,
+    /// Whether to mark the entity [`PendingScore`] until the first task completes.
+    ineligible_while_pending: bool,
+    /// Whether to let a pending task complete when the entity is rescored, instead of restarting it.
+    keep_pending: bool,
+    /// The pending task, if any.
+    task: Option<Task<f32>>,
+    /// Whether a task has completed since the entity started scoring or was last invalidated.
+    has_result: bool,
+}
+
+impl AsyncScore {
+    /// Creates a new [`AsyncScore`] that starts a task from the score entity and the [`World`] when it's scored,
+    /// cancelling the pending task, if any.
+    ///
+    /// The start function should only read the inputs of the task from the [`World`],
+    /// and do the expensive work in the returned [`Future`].
+    /// The future must be [`Sync`] to support the single-threaded [`AsyncComputeTaskPool`].
+    #[must_use]
+    pub fn new<F, Fut>(start: F) -> Self
+    where
+        F: Fn(Entity, &World) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = f32> + Send + Sync + 'static,
+    {
+        Self {
+            start: Arc::new(move |entity, world| Box::pin(start(entity, world))),
+            placeholder: None,
+            ineligible_while_pending: false,
+            keep_pending: false,
+            task: None,
+            has_result: false,
+        }
+    }
+
+    /// Holds the given score until the first task completes, instead of keeping the last score.
+    #[must_use]
+    pub fn with_placeholder(mut self, placeholder: impl Into<Score>) -> Self {
+        self.placeholder = Some(placeholder.into());
+        self
+    }
+
+    /// Marks the entity [`PendingScore`] until the first task completes, so pickers skip its choice.
+    ///
+    /// This should be used on the [`Score`] entity of a choice, i.e. a direct child of the actor.
+    #[must_use]
+    pub fn with_ineligible_while_pending(mut self) -> Self {
+        self.ineligible_while_pending = true;
+        self
+    }
+
+    /// Lets a pending task run to completion when the entity is rescored, instead of restarting it.
+    ///
+    /// This suits slow tasks whose inputs rarely change. Trigger [`InvalidateScoreCache`] when they do.
+    #[must_use]
+    pub fn with_keep_pending(mut self) -> Self {
+        self.keep_pending = true;
+        self
+    }
+
+    /// Returns the score held until the first task completes, or [`None`] if the last score is kept.
+    #[must_use]
+    pub fn placeholder(&self) -> Option<Score> {
+        self.placeholder
+    }
+
+    /// Returns `true` if the entity is marked [`PendingScore`] until the first task completes.
+    #[must_use]
+    pub fn ineligible_while_pending(&self) -> bool {
+        self.ineligible_while_pending
+    }
+
+    /// Returns `true` if a pending task runs to completion when the entity is rescored.
+    #[must_use]
+    pub fn keep_pending(&self) -> bool {
+        self.keep_pending
+    }
+
+    /// Returns `true` if a task is pending.
+    #[must_use]
+    pub fn is_pending(&self) -> bool {
+        self.task.is_some()
+    }
+
+    /// Returns `true` if a task has completed since the entity started scoring or was last invalidated.
+    #[must_use]
+    pub fn has_result(&self) -> bool {
+        self.has_result
+    }
+
+    /// Cancels the pending task, if any.
+    pub fn cancel(&mut self) {
+        self.task = None;
+    }
+
+    /// Cancels the pending task, if any, and forgets the last result,
+    /// so the next scoring starts a new task and holds the placeholder until it completes.
+    pub fn invalidate(&mut self) {
+        self.task = None;
+        self.has_result = false;
+    }
+
+    /// [`Observer`] for [`AsyncScore`] [`Score`] entities that starts a new task,
+    /// replacing the pending one unless it's [kept](AsyncScore::with_keep_pending).
+    fn observer(trigger: On<OnScore>, world: &World, mut commands: Commands) {
+        let entity = trigger.event().entity;
+        let Some(settings) = world.get::<AsyncScore>(entity) else {
+            // The entity is not scoring asynchronously.
+            return;
+        };
+        if settings.keep_pending && settings.is_pending() {
+            // Let the pending task complete.
+            return;
+        }
+
+        let task = AsyncComputeTaskPool::get().spawn((settings.start)(entity, world));
+        commands.queue(move |world: &mut World| {
+            let Some(mut settings) = world.get_mut::<AsyncScore>(entity) else {
+                // The entity was despawned in the meantime, dropping the task cancels it.
+                return;
+            };
+            if settings.keep_pending && settings.is_pending() {
+                // The entity was scored twice before starting, dropping the task cancels it.
+                return;
+            }
+            // Replacing the pending task, if any, cancels it.
+            settings.task = Some(task);
+            if settings.has_result {
+                // Keep the last result until the new one is ready.
+                return;
+            }
+            let placeholder = settings.placeholder;
+            let ineligible = settings.ineligible_while_pending;
+
+            if let Some(placeholder) = placeholder
+                && let Some(mut score) = world.get_mut::<Score>(entity)
+            {
+                *score = placeholder;
+            }
+            if ineligible {
+                world.entity_mut(entity).insert(PendingScore);
+            }
+        });
+    }
+
+    /// [`Observer`] that invalidates [`AsyncScore`]s for [`InvalidateScoreCache`] events.
+    fn on_invalidate(trigger: On<InvalidateScoreCache>, mut scores: Query<&mut AsyncScore>) {
+        let entity = trigger.event().entity;
+        let Ok(mut settings) = scores.get_mut(entity) else {
+            // The entity is not scoring asynchronously.
+            return;
+        };
+
+        settings.invalidate();
+    }
+}
+
+impl Component for AsyncScore {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+    type Mutability = bevy::ecs::component::Mutable;
+
+    fn on_add() -> Option<ComponentHook> {
+        Some(|mut world: DeferredWorld, _context: HookContext| {
+            #[derive(Resource, Default)]
+            struct AsyncScoreObserverSpawned;
+
+            #[derive(Resource, Default)]
+            struct AsyncScoreInvalidateObserverSpawned;
+
+            world.once::<AsyncScoreObserverSpawned>().observe(Self::observer);
+            world
+                .once::<AsyncScoreInvalidateObserverSpawned>()
+                .observe(Self::on_invalidate);
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        },
+        time::{Duration, Instant},
+    };
+
+    use bevy::{
+        prelude::*,
+        tasks::{AsyncComputeTaskPool, TaskPool},
+    };
+
+    use crate::{
+        async_score::{AsyncScore, AsyncScorePlugin},
+        event::{InvalidateScoreCache, RunPicking, RunScoring},
+        picking::{FirstToScore, Ineligible, PendingScore, Picker},
+        scoring::Score,
+    };
+
+    #[test]
+    fn pending_then_ready() {
+        AsyncComputeTaskPool::get_or_init(TaskPool::default);
+
+        let mut app = App::new();
+        app.add_plugins(crate::ObservedUtilityPlugins::TurnBased);
+        let world = app.world_mut();
+
+        let entity = world
+            .spawn((
+                AsyncScore::new(|_, _| async { 0.6 })
+                    .with_placeholder(0.1)
+                    .with_ineligible_while_pending(),
+                Score::default(),
+            ))
+            .id();
+
+        world.trigger(RunScoring::entity(entity));
+        world.flush();
+        assert!(world.get::<AsyncScore>(entity).unwrap().is_pending());
+        assert!(world.get::<PendingScore>(entity).is_some());
+        assert_eq!(world.get::<Score>(entity).unwrap().get(), 0.1);
+
+        // Other sources of ineligibility are left alone.
+        world.entity_mut(entity).insert(Ineligible);
+
+        let start = Instant::now();
+        while world.get::<AsyncScore>(entity).unwrap().is_pending() {
+            assert!(start.elapsed() < Duration::from_secs(5), "the task never completed");
+            world.run_system_cached(AsyncScorePlugin::poll_tasks).unwrap();
+            std::thread::yield_now();
+        }
+        assert_eq!(world.get::<Score>(entity).unwrap().get(), 0.6);
+        assert!(world.get::<PendingScore>(entity).is_none());
+        assert!(world.get::<Ineligible>(entity).is_some());
+    }
+
+    #[test]
+    fn rescoring_restarts_pending() {
+        #[derive(Component)]
+        struct Input(f32);
+
+        AsyncComputeTaskPool::get_or_init(TaskPool::default);
+
+        let mut app = App::new();
+        app.add_plugins(crate::ObservedUtilityPlugins::TurnBased);
+        let world = app.world_mut();
+
+        let starts = Arc::new(AtomicUsize::new(0));
+        let counter = starts.clone();
+        let entity = world
+            .spawn((
+                Input(0.2),
+                AsyncScore::new(move |entity, world| {
+                    counter.fetch_add(1, Ordering::Relaxed);
+                    let input = world.get::<Input>(entity).map_or(0., |input| input.0);
+                    async move { input }
+                }),
+                Score::default(),
+            ))
+            .id();
+
+        world.trigger(RunScoring::entity(entity));
+        world.flush();
+        assert!(world.get::<AsyncScore>(entity).unwrap().is_pending());
+
+        // Rescoring replaces the pending task, so the stale input is never written.
+        world.entity_mut(entity).insert(Input(0.7));
+        world.trigger(RunScoring::entity(entity));
+        world.flush();
+        assert_eq!(starts.load(Ordering::Relaxed), 2);
+
+        let start = Instant::now();
+        while world.get::<AsyncScore>(entity).unwrap().is_pending() {
+            assert!(start.elapsed() < Duration::from_secs(5), "the task never completed");
+            world.run_system_cached(AsyncScorePlugin::poll_tasks).unwrap();
+            std::thread::yield_now();
+        }
+        assert_eq!(world.get::<Score>(entity).unwrap().get(), 0.7);
+
+        // Kept tasks aren't restarted.
+        let counter = starts.clone();
+        world.entity_mut(entity).insert(
+            AsyncScore::new(move |_, _| {
+                counter.fetch_add(1, Ordering::Relaxed);
+                async { 0.3 }
+            })
+            .with_keep_pending(),
+        );
+        world.trigger(RunScoring::entity(entity));
+        world.flush();
+        world.trigger(RunScoring::entity(entity));
+        world.flush();
+        assert!(world.get::<AsyncScore>(entity).unwrap().is_pending());
+        assert_eq!(starts.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn rescoring_keeps_results() {
+        #[derive(Component)]
+        struct Idle;
+
+        #[derive(Component)]
+        struct Travel;
+
+        AsyncComputeTaskPool::get_or_init(TaskPool::default);
+
+        let mut app = App::new();
+        app.add_plugins(crate::ObservedUtilityPlugins::TurnBased);
+        let world = app.world_mut();
+
+        let idle = world.register_component::<Idle>();
+        let travel = world.register_component::<Travel>();
+
+        let scorer = world
+            .spawn((
+                AsyncScore::new(|_, _| async { 0.8 })
+                    .with_placeholder(0.)
+                    .with_ineligible_while_pending()
+                    .with_keep_pending(),
+                Score::default(),
+            ))
+            .id();
+        let actor = world
+            .spawn((Picker::new(idle).with(scorer, travel), FirstToScore::new(0.5)))
+            .add_child(scorer)
+            .id();
+
+        // Scoring every tick lets the task complete, and the result stick.
+        let tick = |world: &mut World| {
+            world.trigger(RunScoring::all());
+            world.trigger(RunPicking::all());
+            world.flush();
+            world.run_system_cached(AsyncScorePlugin::poll_tasks).unwrap();
+        };
+        let start = Instant::now();
+        while world.get::<Picker>(actor).unwrap().picked != travel {
+            assert!(start.elapsed() < Duration::from_secs(5), "the choice was never picked");
+            tick(world);
+            std::thread::yield_now();
+        }
+        tick(world);
+        assert_eq!(world.get::<Picker>(actor).unwrap().picked, travel);
+        assert_eq!(world.get::<Score>(scorer).unwrap().get(), 0.8);
+
+        // Invalidating falls back to the placeholder.
+        world.trigger(InvalidateScoreCache { entity: scorer });
+        world.trigger(RunScoring::entity(scorer));
+        world.flush();
+        assert!(!world.get::<AsyncScore>(scorer).unwrap().has_result());
+        assert_eq!(world.get::<Score>(scorer).unwrap().get(), 0.);
+        assert!(world.get::<PendingScore>(scorer).is_some());
+    }
+}
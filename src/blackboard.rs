@@ -0,0 +1,680 @@
+//! Blackboards hold named, reflectable values for actors and score trees,
+//! so that new inputs can be wired up from data files without new component types.
+//!
+//! A [`Blackboard`] is a [`Component`] mapping key names to [`PartialReflect`] values.
+//! Values can be accessed with typed [`BlackboardKey`]s from Rust, or by name with reflection from data files.
+//!
+//! Scorers read the closest ancestor [`Blackboard`], so a blackboard can be placed on the actor
+//! or on any entity of its score tree:
+//! - [`BlackboardScore`] scores a numeric value, normalized from a range.
+//! - [`BlackboardChanged`] scores whether a value has changed.
+//!
+//! Actions write values with [`BlackboardWrites`] when they're initiated or ended.
+//!
+//! Changed keys are tracked until they are cleared, which the [`BlackboardPlugin`] does in the configured [`Schedule`],
+//! right before scoring. A key is only cleared once it has stayed changed through a whole tick,
+//! so that writes made after scoring, such as by actions, are seen by the next scoring pass.
+//!
+//! # Example
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_observed_utility::prelude::*;
+//!
+//! const HUNGER: BlackboardKey<f32> = BlackboardKey::new("hunger");
+//!
+//! # let mut app = App::new();
+//! # app.add_plugins(ObservedUtilityPlugins::RealTime);
+//! app.add_plugins(BlackboardPlugin::default());
+//!
+//! # let world = app.world_mut();
+//! let scorer = world
+//!     .spawn((BlackboardScore::new("hunger", 0., 100.), Score::default()))
+//!     .id();
+//!
+//! let actor = world
+//!     .spawn(Blackboard::default().with(&HUNGER, 25.))
+//!     .add_child(scorer)
+//!     .id();
+//!
+//! assert_eq!(world.get::<Blackboard>(actor).unwrap().get(&HUNGER), Some(&25.));
+//! # world.trigger(RunScoring::entity(scorer));
+//! # world.flush();
+//! # assert_eq!(world.get::<Score>(scorer).unwrap().get(), 0.25);
+//! ```
+
+use std::{borrow::Cow, marker::PhantomData};
+
+use bevy::{
+    ecs::{
+        component::{ComponentId, StorageType},
+        lifecycle::{ComponentHook, HookContext},
+        schedule::{InternedScheduleLabel, ScheduleLabel},
+        world::DeferredWorld,
+    },
+    platform::collections::{HashMap, HashSet},
+    prelude::*,
+    reflect::PartialReflect,
+};
+
+use crate::{
+    RealtimeLifecyclePlugin,
+    ecs::{AncestorQuery, DeferredWorldExt},
+    event::{ActionEndReason, OnActionEnded, OnActionInitiated, OnScore},
+    scoring::{Evaluator, LinearEvaluator, Score},
+};
+
+/// [`Plugin`] that applies [`BlackboardWrites`] and clears changed keys of all [`Blackboard`]s in the configured [`Schedule`].
+pub struct BlackboardPlugin {
+    /// The [`ScheduleLabel`] to clear changed keys in, before the [`RealtimeLifecyclePlugin`] scores.
+    pub clear_changed_in: InternedScheduleLabel,
+}
+
+impl Default for BlackboardPlugin {
+    fn default() -> Self {
+        Self {
+            clear_changed_in: FixedPostUpdate.intern(),
+        }
+    }
+}
+
+impl Plugin for BlackboardPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            self.clear_changed_in,
+            Self::clear_changed
+                .before(RealtimeLifecyclePlugin::score_and_pick)
+                .before(RealtimeLifecyclePlugin::score_and_pick_budgeted),
+        );
+
+        app.add_observer(Self::on_action_initiated_write)
+            .add_observer(Self::on_action_ended_write);
+
+        app.register_type::<BlackboardScore>()
+            .register_type::<BlackboardChanged>()
+            .register_type::<BlackboardWriteWhen>();
+
+        // Note: Blackboard and BlackboardWrites cannot be reflected due to the boxed values
+    }
+}
+
+impl BlackboardPlugin {
+    /// [`System`] that [ages](Blackboard::age_changed) the changed keys of all [`Blackboard`]s,
+    /// clearing the keys that were already changed the last time it ran.
+    pub fn clear_changed(mut blackboards: Query<&mut Blackboard>) {
+        for mut blackboard in blackboards.iter_mut() {
+            if !blackboard.changed.is_empty() {
+                blackboard.age_changed();
+            }
+        }
+    }
+
+    /// [`Observer`] that applies the [`BlackboardWrites`] of an actor when an action is initiated.
+    pub fn on_action_initiated_write(
+        trigger: On<OnActionInitiated>,
+        mut actors: Query<(&mut Blackboard, &BlackboardWrites)>,
+    ) {
+        let event = trigger.event();
+        if let Ok((mut blackboard, writes)) = actors.get_mut(event.entity) {
+            writes.apply(&mut blackboard, event.action, |when| {
+                when == BlackboardWriteWhen::Initiated
+            });
+        }
+    }
+
+    /// [`Observer`] that applies the [`BlackboardWrites`] of an actor when an action is ended.
+    pub fn on_action_ended_write(trigger: On<OnActionEnded>, mut actors: Query<(&mut Blackboard, &BlackboardWrites)>) {
+        let event = trigger.event();
+        if let Ok((mut blackboard, writes)) = actors.get_mut(event.entity) {
+            writes.apply(&mut blackboard, event.action, |when| match when {
+                BlackboardWriteWhen::Initiated => false,
+                BlackboardWriteWhen::Ended => true,
+                BlackboardWriteWhen::Completed => event.reason == ActionEndReason::Completed,
+            });
+        }
+    }
+}
+
+/// A typed key into a [`Blackboard`].
+///
+/// Keys are compared by name only, so the same name should always be used with the same type.
+pub struct BlackboardKey<T> {
+    /// The name of the key.
+    name: Cow<'static, str>,
+    _type: PhantomData<fn() -> T>,
+}
+
+impl<T> BlackboardKey<T> {
+    /// Creates a new [`BlackboardKey`] with a static name, usable in `const` contexts.
+    #[must_use]
+    pub const fn new(name: &'static str) -> Self {
+        Self {
+            name: Cow::Borrowed(name),
+            _type: PhantomData,
+        }
+    }
+
+    /// Creates a new [`BlackboardKey`] with the given name, such as one read from a data file.
+    #[must_use]
+    pub fn named(name: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            name: name.into(),
+            _type: PhantomData,
+        }
+    }
+
+    /// Returns the name of the key.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl<T> Clone for BlackboardKey<T> {
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            _type: PhantomData,
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for BlackboardKey<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("BlackboardKey").field(&self.name).finish()
+    }
+}
+
+/// [`Component`] holding named, reflectable values for an actor or score tree.
+///
+/// See the [module docs](crate::blackboard) for more information.
+#[derive(Component, Default, Debug)]
+pub struct Blackboard {
+    /// Map of key names to values.
+    values: HashMap<Cow<'static, str>, Box<dyn PartialReflect>>,
+    /// The names of the keys that changed since they were last cleared.
+    changed: HashSet<Cow<'static, str>>,
+    /// The names of the changed keys that were already changed the last time changes were aged.
+    aged: HashSet<Cow<'static, str>>,
+}
+
+impl Blackboard {
+    /// Sets the value of a typed key.
+    #[must_use]
+    pub fn with<T: Reflect>(mut self, key: &BlackboardKey<T>, value: T) -> Self {
+        self.set(key, value);
+        self
+    }
+
+    /// Returns the value of a typed key, if it is set and of type `T`.
+    #[must_use]
+    pub fn get<T: Reflect>(&self, key: &BlackboardKey<T>) -> Option<&T> {
+        self.values.get(key.name())?.try_downcast_ref::<T>()
+    }
+
+    /// Returns a mutable reference to the value of a typed key, if it is set and of type `T`.
+    ///
+    /// The key is marked as changed.
+    pub fn get_mut<T: Reflect>(&mut self, key: &BlackboardKey<T>) -> Option<&mut T> {
+        let value = self.values.get_mut(key.name())?.try_downcast_mut::<T>()?;
+        Self::mark_changed(&mut self.changed, &mut self.aged, key.name.clone());
+        Some(value)
+    }
+
+    /// Sets the value of a typed key, marking it as changed.
+    pub fn set<T: Reflect>(&mut self, key: &BlackboardKey<T>, value: T) {
+        self.set_reflect(key.name.clone(), Box::new(value));
+    }
+
+    /// Returns the value of a key by name.
+    #[must_use]
+    pub fn get_reflect(&self, name: &str) -> Option<&dyn PartialReflect> {
+        self.values.get(name).map(AsRef::as_ref)
+    }
+
+    /// Sets the value of a key by name, marking it as changed.
+    pub fn set_reflect(&mut self, name: impl Into<Cow<'static, str>>, value: Box<dyn PartialReflect>) {
+        let name = name.into();
+        Self::mark_changed(&mut self.changed, &mut self.aged, name.clone());
+        self.values.insert(name, value);
+    }
+
+    /// Returns the value of a key by name as an [`f32`], if it is set and numeric.
+    ///
+    /// Booleans are converted to `0` or `1`.
+    #[must_use]
+    pub fn get_f32(&self, name: &str) -> Option<f32> {
+        let value = self.get_reflect(name)?;
+        #[expect(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+        let number = if let Some(&v) = value.try_downcast_ref::<f32>() {
+            v
+        } else if let Some(&v) = value.try_downcast_ref::<f64>() {
+            v as f32
+        } else if let Some(&v) = value.try_downcast_ref::<i32>() {
+            v as f32
+        } else if let Some(&v) = value.try_downcast_ref::<i64>() {
+            v as f32
+        } else if let Some(&v) = value.try_downcast_ref::<u32>() {
+            v as f32
+        } else if let Some(&v) = value.try_downcast_ref::<u64>() {
+            v as f32
+        } else if let Some(&v) = value.try_downcast_ref::<usize>() {
+            v as f32
+        } else if let Some(&v) = value.try_downcast_ref::<bool>() {
+            f32::from(u8::from(v))
+        } else {
+            return None;
+        };
+        Some(number)
+    }
+
+    /// Removes the value of a key by name, marking it as changed.
+    pub fn remove(&mut self, name: &str) -> Option<Box<dyn PartialReflect>> {
+        let (name, value) = self.values.remove_entry(name)?;
+        Self::mark_changed(&mut self.changed, &mut self.aged, name);
+        Some(value)
+    }
+
+    /// Returns `true` if a key is set.
+    #[must_use]
+    pub fn contains(&self, name: &str) -> bool {
+        self.values.contains_key(name)
+    }
+
+    /// Returns `true` if a key changed since changes were last cleared.
+    #[must_use]
+    pub fn is_changed(&self, name: &str) -> bool {
+        self.changed.contains(name)
+    }
+
+    /// Returns the names of the keys that changed since changes were last cleared.
+    pub fn changed(&self) -> impl Iterator<Item = &str> {
+        self.changed.iter().map(AsRef::as_ref)
+    }
+
+    /// Clears the changed keys.
+    pub fn clear_changed(&mut self) {
+        self.changed.clear();
+        self.aged.clear();
+    }
+
+    /// Clears the keys that were already changed the last time changes were aged, and keeps the rest for one more time,
+    /// so that every change is kept for at least a whole interval between calls.
+    pub fn age_changed(&mut self) {
+        let aged = &self.aged;
+        self.changed.retain(|name| !aged.contains(name));
+        self.aged.clone_from(&self.changed);
+    }
+
+    /// Marks a key as changed, keeping it changed through the next [`Blackboard::age_changed`].
+    fn mark_changed(
+        changed: &mut HashSet<Cow<'static, str>>,
+        aged: &mut HashSet<Cow<'static, str>>,
+        name: Cow<'static, str>,
+    ) {
+        aged.remove(&name);
+        changed.insert(name);
+    }
+}
+
+/// When a [`BlackboardWrites`] entry is applied.
+#[derive(Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[reflect(PartialEq, Debug)]
+pub enum BlackboardWriteWhen {
+    /// When the action is initiated.
+    Initiated,
+    /// When the action ends for any reason.
+    Ended,
+    /// When the action ends by completing.
+    Completed,
+}
+
+/// [`Component`] for actor entities that write values to their [`Blackboard`] when actions are initiated or ended.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_observed_utility::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Eat;
+///
+/// const HUNGER: BlackboardKey<f32> = BlackboardKey::new("hunger");
+///
+/// # let mut app = App::new();
+/// # app.add_plugins((ObservedUtilityPlugins::TurnBased, BlackboardPlugin::default()));
+/// # let world = app.world_mut();
+/// let eat = world.register_component::<Eat>();
+/// let actor = world
+///     .spawn((
+///         Blackboard::default().with(&HUNGER, 80.),
+///         BlackboardWrites::default().with(eat, BlackboardWriteWhen::Completed, &HUNGER, 0.),
+///     ))
+///     .id();
+///
+/// world.trigger(OnActionEnded::completed(actor, eat));
+/// assert_eq!(world.get::<Blackboard>(actor).unwrap().get(&HUNGER), Some(&0.));
+/// ```
+#[derive(Component, Default, Debug)]
+pub struct BlackboardWrites {
+    /// The writes to apply, in order.
+    writes: Vec<BlackboardWrite>,
+}
+
+/// A single entry of [`BlackboardWrites`].
+#[derive(Debug)]
+struct BlackboardWrite {
+    action: ComponentId,
+    when: BlackboardWriteWhen,
+    name: Cow<'static, str>,
+    value: Box<dyn Reflect>,
+}
+
+impl BlackboardWrites {
+    /// Adds a write of a typed key for the given action.
+    #[must_use]
+    pub fn with<T: Reflect>(
+        self,
+        action: ComponentId,
+        when: BlackboardWriteWhen,
+        key: &BlackboardKey<T>,
+        value: T,
+    ) -> Self {
+        self.with_reflect(action, when, key.name.clone(), Box::new(value))
+    }
+
+    /// Adds a write of a key by name for the given action.
+    #[must_use]
+    pub fn with_reflect(
+        mut self,
+        action: ComponentId,
+        when: BlackboardWriteWhen,
+        name: impl Into<Cow<'static, str>>,
+        value: Box<dyn Reflect>,
+    ) -> Self {
+        self.writes.push(BlackboardWrite {
+            action,
+            when,
+            name: name.into(),
+            value,
+        });
+        self
+    }
+
+    /// Applies the writes for the given action whose [`BlackboardWriteWhen`] matches.
+    fn apply(&self, blackboard: &mut Blackboard, action: ComponentId, matches: impl Fn(BlackboardWriteWhen) -> bool) {
+        for write in self.writes.iter().filter(|w| w.action == action && matches(w.when)) {
+            if let Ok(value) = write.value.reflect_clone() {
+                blackboard.set_reflect(write.name.clone(), value.into_partial_reflect());
+            }
+        }
+    }
+}
+
+/// [`Score`] [`Component`] that scores a numeric [`Blackboard`] value, normalized from the `[min, max]` range.
+///
+/// The value is read from the [`Blackboard`] of the closest ancestor entity that has one.
+/// If the key is not set or not numeric, the score is the minimum.
+#[derive(Reflect)]
+#[derive(Clone, PartialEq, Debug)]
+#[reflect(Component, PartialEq, Debug)]
+pub struct BlackboardScore {
+    /// The name of the key to read.
+    key: Cow<'static, str>,
+    /// The value that scores the minimum.
+    min: f32,
+    /// The value that scores the maximum.
+    max: f32,
+}
+
+impl BlackboardScore {
+    /// Creates a new [`BlackboardScore`] that normalizes the key's value from the `[min, max]` range.
+    #[must_use]
+    pub fn new(key: impl Into<Cow<'static, str>>, min: f32, max: f32) -> Self {
+        Self {
+            key: key.into(),
+            min,
+            max,
+        }
+    }
+
+    /// Returns the name of the key to read.
+    #[must_use]
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// [`Observer`] for [`BlackboardScore`] [`Score`] entities that scores the key's value.
+    fn observer(
+        trigger: On<OnScore>,
+        mut target: Query<(&mut Score, &BlackboardScore)>,
+        mut blackboards: AncestorQuery<&'static Blackboard>,
+    ) {
+        let entity = trigger.event().entity;
+        let Ok((mut actor_score, settings)) = target.get_mut(entity) else {
+            // The entity is not scoring for a blackboard value.
+            return;
+        };
+
+        let value = blackboards
+            .get(entity)
+            .ok()
+            .and_then(|blackboard| blackboard.get_f32(&settings.key));
+
+        *actor_score = match value {
+            Some(value) => Score::new(LinearEvaluator::from_range(settings.min, settings.max).evaluate(value)),
+            // If there is no value, set the score to the minimum.
+            None => Score::MIN,
+        };
+    }
+}
+
+impl Component for BlackboardScore {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+    type Mutability = bevy::ecs::component::Immutable;
+
+    fn on_add() -> Option<ComponentHook> {
+        Some(|mut world: DeferredWorld, _context: HookContext| {
+            #[derive(Resource, Default)]
+            struct BlackboardScoreObserverSpawned;
+
+            world.once::<BlackboardScoreObserverSpawned>().observe(Self::observer);
+        })
+    }
+}
+
+/// [`Score`] [`Component`] that scores the maximum if a [`Blackboard`] key changed since changes were last cleared,
+/// and the minimum otherwise.
+///
+/// The key is read from the [`Blackboard`] of the closest ancestor entity that has one.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_observed_utility::prelude::*;
+///
+/// # let mut app = App::new();
+/// # app.add_plugins(ObservedUtilityPlugins::RealTime);
+/// # let world = app.world_mut();
+/// let scorer = world
+///     .spawn((BlackboardChanged::new("alarm"), Score::default()))
+///     .id();
+///
+/// let mut blackboard = Blackboard::default();
+/// blackboard.set(&BlackboardKey::new("alarm"), true);
+/// world.spawn(blackboard).add_child(scorer);
+/// # world.trigger(RunScoring::entity(scorer));
+/// # world.flush();
+/// # assert_eq!(world.get::<Score>(scorer).unwrap().get(), 1.);
+/// ```
+#[derive(Reflect)]
+#[derive(Clone, PartialEq, Debug)]
+#[reflect(Component, PartialEq, Debug)]
+pub struct BlackboardChanged {
+    /// The name of the key to check.
+    key: Cow<'static, str>,
+}
+
+impl BlackboardChanged {
+    /// Creates a new [`BlackboardChanged`] for the given key.
+    #[must_use]
+    pub fn new(key: impl Into<Cow<'static, str>>) -> Self {
+        Self { key: key.into() }
+    }
+
+    /// Returns the name of the key to check.
+    #[must_use]
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// [`Observer`] for [`BlackboardChanged`] [`Score`] entities that scores whether the key changed.
+    fn observer(
+        trigger: On<OnScore>,
+        mut target: Query<(&mut Score, &BlackboardChanged)>,
+        mut blackboards: AncestorQuery<&'static Blackboard>,
+    ) {
+        let entity = trigger.event().entity;
+        let Ok((mut actor_score, settings)) = target.get_mut(entity) else {
+            // The entity is not scoring for a blackboard change.
+            return;
+        };
+
+        let changed = blackboards
+            .get(entity)
+            .is_ok_and(|blackboard| blackboard.is_changed(&settings.key));
+
+        *actor_score = if changed { Score::MAX } else { Score::MIN };
+    }
+}
+
+impl Component for BlackboardChanged {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+    type Mutability = bevy::ecs::component::Immutable;
+
+    fn on_add() -> Option<ComponentHook> {
+        Some(|mut world: DeferredWorld, _context: HookContext| {
+            #[derive(Resource, Default)]
+            struct BlackboardChangedObserverSpawned;
+
+            world.once::<BlackboardChangedObserverSpawned>().observe(Self::observer);
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::component::Component, prelude::*};
+
+    use crate::{
+        blackboard::{
+            Blackboard, BlackboardChanged, BlackboardKey, BlackboardPlugin, BlackboardWriteWhen, BlackboardWrites,
+        },
+        event::{OnActionEnded, OnActionInitiated, RunScoring},
+        scoring::Score,
+    };
+
+    #[derive(Component)]
+    struct Patrol;
+
+    const ALERT: BlackboardKey<bool> = BlackboardKey::new("alert");
+    const WAYPOINT: BlackboardKey<u32> = BlackboardKey::new("waypoint");
+
+    #[test]
+    fn typed_and_reflected_access() {
+        let mut blackboard = Blackboard::default().with(&WAYPOINT, 3);
+
+        assert_eq!(blackboard.get(&WAYPOINT), Some(&3));
+        // The same name with the wrong type reads nothing.
+        assert_eq!(blackboard.get(&BlackboardKey::<f32>::named("waypoint")), None);
+        assert_eq!(blackboard.get_f32("waypoint"), Some(3.));
+        assert!(blackboard.is_changed("waypoint"));
+
+        blackboard.clear_changed();
+        *blackboard.get_mut(&WAYPOINT).unwrap() += 1;
+        assert_eq!(blackboard.get_f32("waypoint"), Some(4.));
+        assert!(blackboard.is_changed("waypoint"));
+
+        blackboard.clear_changed();
+        blackboard.set_reflect("speed", Box::new(2.5_f64));
+        assert_eq!(blackboard.get_f32("speed"), Some(2.5));
+        assert_eq!(blackboard.changed().collect::<Vec<_>>(), vec!["speed"]);
+
+        assert!(blackboard.remove("speed").is_some());
+        assert!(!blackboard.contains("speed"));
+    }
+
+    #[test]
+    fn writes_on_action() {
+        let mut app = App::new();
+        app.add_plugins((crate::ObservedUtilityPlugins::TurnBased, BlackboardPlugin::default()));
+        let world = app.world_mut();
+
+        let patrol = world.register_component::<Patrol>();
+        let actor = world
+            .spawn((
+                Blackboard::default(),
+                BlackboardWrites::default()
+                    .with(patrol, BlackboardWriteWhen::Initiated, &ALERT, true)
+                    .with(patrol, BlackboardWriteWhen::Ended, &ALERT, false)
+                    .with(patrol, BlackboardWriteWhen::Completed, &WAYPOINT, 1),
+            ))
+            .id();
+
+        world.trigger(OnActionInitiated {
+            entity: actor,
+            action: patrol,
+        });
+        assert_eq!(world.get::<Blackboard>(actor).unwrap().get(&ALERT), Some(&true));
+
+        world.trigger(OnActionEnded::cancelled(actor, patrol));
+        let blackboard = world.get::<Blackboard>(actor).unwrap();
+        assert_eq!(blackboard.get(&ALERT), Some(&false));
+        assert_eq!(blackboard.get(&WAYPOINT), None);
+
+        world.trigger(OnActionEnded::completed(actor, patrol));
+        assert_eq!(world.get::<Blackboard>(actor).unwrap().get(&WAYPOINT), Some(&1));
+
+        // Changes are kept through the first clear, so that the next scoring pass sees them.
+        world.run_system_cached(BlackboardPlugin::clear_changed).unwrap();
+        assert_eq!(world.get::<Blackboard>(actor).unwrap().changed().count(), 2);
+        world.run_system_cached(BlackboardPlugin::clear_changed).unwrap();
+        assert_eq!(world.get::<Blackboard>(actor).unwrap().changed().count(), 0);
+    }
+
+    #[test]
+    fn action_writes_seen_by_next_scoring() {
+        let mut app = App::new();
+        app.add_plugins((crate::ObservedUtilityPlugins::TurnBased, BlackboardPlugin::default()));
+        let world = app.world_mut();
+
+        let patrol = world.register_component::<Patrol>();
+        let scorer = world.spawn((BlackboardChanged::new("alert"), Score::default())).id();
+        let actor = world
+            .spawn((
+                Blackboard::default(),
+                BlackboardWrites::default().with(patrol, BlackboardWriteWhen::Initiated, &ALERT, true),
+            ))
+            .add_child(scorer)
+            .id();
+        let tick = |world: &mut World| {
+            world.run_system_cached(BlackboardPlugin::clear_changed).unwrap();
+            world.trigger(RunScoring::all());
+            world.flush();
+            world.get::<Score>(scorer).unwrap().get()
+        };
+        tick(world);
+
+        // An action is initiated after scoring, at the end of the tick.
+        world.trigger(OnActionInitiated {
+            entity: actor,
+            action: patrol,
+        });
+
+        assert_eq!(tick(world), 1.);
+        assert_eq!(tick(world), 0.);
+    }
+}
@@ -0,0 +1,417 @@
+//! Brains are complete decision setups that can be switched per actor at runtime, such as peaceful, combat, and fleeing.
+//!
+//! An actor's [`Brains`] component holds named [`Picker`]s, each with its own set of choice [`Score`] entities.
+//! The active brain's [`Picker`] is inserted on the actor, and the choice entities of inactive brains
+//! are [`Disabled`], so they aren't scored by [`RunScoring::all()`](crate::event::RunScoring::all) or seen by queries.
+//! All brains share the actor's picking component, such as [`Highest`](crate::picking::Highest).
+//!
+//! Trigger [`SwitchBrain`] to switch brains. Depending on the [`BrainSwitchPolicy`], the current action is either
+//! re-picked immediately with an [`Interrupt`], cancelling it if the new brain picks something else,
+//! or kept until it ends.
+//!
+//! Brains can also follow the value of a [`Resource`] with [`follow_resource`].
+//! Since Bevy's [`State`] and `SubState` values are stored in the `State<S>` resource,
+//! this binds brains to game states when the `bevy_state` feature of Bevy is enabled.
+//!
+//! # Example
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_observed_utility::prelude::*;
+//!
+//! #[derive(Component)]
+//! struct Idle;
+//! #[derive(Component)]
+//! struct Attack;
+//!
+//! # let mut app = App::new();
+//! # app.add_plugins(ObservedUtilityPlugins::RealTime);
+//! # let world = app.world_mut();
+//! let idle = world.register_component::<Idle>();
+//! let attack = world.register_component::<Attack>();
+//!
+//! let aggression = world.spawn((FixedScore::new(0.8), Score::default())).id();
+//! let actor = world
+//!     .spawn((
+//!         Brains::new("peaceful", Picker::new(idle))
+//!             .with("combat", Picker::new(idle).with(aggression, attack)),
+//!         Highest,
+//!     ))
+//!     .add_child(aggression)
+//!     .id();
+//! # world.flush();
+//!
+//! // Aggression isn't scored while peaceful.
+//! world.trigger(SwitchBrain::new(actor, "combat"));
+//! # world.flush();
+//! assert_eq!(world.get::<CurrentAction>(actor).unwrap().0, attack);
+//! ```
+//!
+//! [`State`]: https://docs.rs/bevy/latest/bevy/state/state/struct.State.html
+//! [`Score`]: crate::scoring::Score
+
+use std::borrow::Cow;
+
+use bevy::{
+    ecs::{
+        component::StorageType,
+        entity::EntityHashSet,
+        entity_disabling::Disabled,
+        lifecycle::{ComponentHook, HookContext},
+        world::DeferredWorld,
+    },
+    platform::collections::HashMap,
+    prelude::*,
+};
+
+use crate::{ecs::DeferredWorldExt, event::Interrupt, picking::Picker};
+
+/// [`Plugin`] that registers the brain types.
+///
+/// This plugin is included in [`ObservedUtilityPlugins`](crate::ObservedUtilityPlugins).
+#[derive(Default)]
+pub struct BrainPlugin;
+
+impl Plugin for BrainPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Brains>().register_type::<BrainSwitchPolicy>();
+
+        app.register_type::<SwitchBrain>();
+    }
+}
+
+/// What happens to the current action when switching [`Brains`].
+#[derive(Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[reflect(PartialEq, Debug, Default)]
+pub enum BrainSwitchPolicy {
+    /// Re-pick immediately, cancelling the current action if the new brain picks something else.
+    #[default]
+    Repick,
+    /// Keep the current action until it ends, then pick with the new brain.
+    KeepCurrent,
+}
+
+/// [`Component`] for actor entities with several switchable decision setups.
+///
+/// See the [module docs](crate::brain) for more information.
+#[derive(Reflect)]
+#[derive(Clone, PartialEq, Debug)]
+#[reflect(Component, PartialEq, Debug)]
+pub struct Brains {
+    /// Map of brain names to their [`Picker`]s.
+    ///
+    /// The active brain's [`Picker`] lives on the actor, so its entry is stale until switched away from.
+    brains: HashMap<Cow<'static, str>, Picker>,
+    /// The name of the active brain.
+    active: Cow<'static, str>,
+    /// What happens to the current action when switching brains.
+    policy: BrainSwitchPolicy,
+}
+
+impl Brains {
+    /// Creates a new [`Brains`] with the given active brain.
+    #[must_use]
+    pub fn new(active: impl Into<Cow<'static, str>>, picker: Picker) -> Self {
+        let active = active.into();
+        Self {
+            brains: HashMap::from_iter([(active.clone(), picker)]),
+            active,
+            policy: BrainSwitchPolicy::default(),
+        }
+    }
+
+    /// Adds an inactive brain.
+    #[must_use]
+    pub fn with(mut self, name: impl Into<Cow<'static, str>>, picker: Picker) -> Self {
+        self.brains.insert(name.into(), picker);
+        self
+    }
+
+    /// Sets what happens to the current action when switching brains.
+    #[must_use]
+    pub fn with_policy(mut self, policy: BrainSwitchPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Sets what happens to the current action when switching brains.
+    pub fn set_policy(&mut self, policy: BrainSwitchPolicy) {
+        self.policy = policy;
+    }
+
+    /// Returns the name of the active brain.
+    #[must_use]
+    pub fn active(&self) -> &str {
+        &self.active
+    }
+
+    /// Returns what happens to the current action when switching brains.
+    #[must_use]
+    pub fn policy(&self) -> BrainSwitchPolicy {
+        self.policy
+    }
+
+    /// Returns `true` if a brain with the given name exists.
+    #[must_use]
+    pub fn contains(&self, name: &str) -> bool {
+        self.brains.contains_key(name)
+    }
+
+    /// Returns an iterator over the names of all brains.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.brains.keys().map(AsRef::as_ref)
+    }
+
+    /// Enables the choice entities of the active brain and disables those of inactive brains.
+    fn toggle_choices(&self, active: &Picker, commands: &mut Commands) {
+        let enabled: EntityHashSet = active.choices.keys().copied().collect();
+        for (_, picker) in self.brains.iter().filter(|(name, _)| **name != self.active) {
+            for &choice in picker.choices.keys().filter(|choice| !enabled.contains(*choice)) {
+                commands.entity(choice).insert(Disabled);
+            }
+        }
+        for &choice in &enabled {
+            commands.entity(choice).remove::<Disabled>();
+        }
+    }
+
+    /// [`Observer`] that switches the active brain for [`SwitchBrain`] events.
+    fn observer(trigger: On<SwitchBrain>, mut commands: Commands, mut actors: Query<(&mut Brains, &mut Picker)>) {
+        let SwitchBrain { entity, ref brain } = *trigger.event();
+        let Ok((mut brains, mut picker)) = actors.get_mut(entity) else {
+            // The entity has no brains.
+            return;
+        };
+        if brains.active == *brain {
+            return;
+        }
+        let Some(next) = brains.brains.get(brain).cloned() else {
+            // The brain doesn't exist.
+            return;
+        };
+
+        // Store the active brain's picker, so it resumes where it left off when switched back to.
+        let previous = std::mem::replace(&mut *picker, next);
+        let active = std::mem::replace(&mut brains.active, brain.clone());
+        brains.brains.insert(active, previous);
+        brains.toggle_choices(&picker, &mut commands);
+
+        match brains.policy {
+            BrainSwitchPolicy::Repick => commands.trigger(Interrupt { entity }),
+            BrainSwitchPolicy::KeepCurrent => {}
+        }
+    }
+}
+
+impl Component for Brains {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+    type Mutability = bevy::ecs::component::Mutable;
+
+    fn on_add() -> Option<ComponentHook> {
+        Some(|mut world: DeferredWorld, _context: HookContext| {
+            #[derive(Resource, Default)]
+            struct BrainsObserverSpawned;
+
+            world.once::<BrainsObserverSpawned>().observe(Self::observer);
+        })
+    }
+
+    fn on_insert() -> Option<ComponentHook> {
+        Some(|mut world: DeferredWorld, context: HookContext| {
+            let Some(brains) = world.get::<Brains>(context.entity).cloned() else {
+                return;
+            };
+            let Some(active) = brains.brains.get(&brains.active).cloned() else {
+                return;
+            };
+
+            let mut commands = world.commands();
+            brains.toggle_choices(&active, &mut commands);
+            commands.entity(context.entity).insert(active);
+        })
+    }
+
+    fn on_remove() -> Option<ComponentHook> {
+        Some(|mut world: DeferredWorld, context: HookContext| {
+            let Some(brains) = world.get::<Brains>(context.entity).cloned() else {
+                return;
+            };
+
+            // Re-enable the choices of inactive brains, they'd be disabled forever otherwise.
+            let mut commands = world.commands();
+            for picker in brains.brains.values() {
+                for &choice in picker.choices.keys() {
+                    commands.entity(choice).try_remove::<Disabled>();
+                }
+            }
+        })
+    }
+}
+
+/// Trigger this [`Event`] to switch the active brain of the target actor entity with [`Brains`].
+///
+/// Switching to the active brain or to a brain that doesn't exist does nothing.
+#[derive(Event, Reflect)]
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[reflect(PartialEq, Debug)]
+pub struct SwitchBrain {
+    /// The actor entity to switch brains for.
+    pub entity: Entity,
+    /// The name of the brain to switch to.
+    pub brain: Cow<'static, str>,
+}
+
+impl SwitchBrain {
+    /// Creates a new [`SwitchBrain`] event.
+    #[must_use]
+    pub fn new(entity: Entity, brain: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            entity,
+            brain: brain.into(),
+        }
+    }
+}
+
+/// Returns a [`System`] that switches the [`Brains`] of all actors to the brain named after the value of the [`Resource`] `R`.
+///
+/// The brains are only switched when the resource is added or changed.
+/// If `brain` returns [`None`], or an actor has no brain with the returned name, the actor's brain is left as-is.
+///
+/// This can be used to bind brains to Bevy's [`State`] or `SubState` values:
+///
+/// ```rust,ignore
+/// app.add_systems(
+///     Update,
+///     follow_resource(|mode: &State<GameMode>| match mode.get() {
+///         GameMode::Exploring => Some("peaceful".into()),
+///         GameMode::Fighting => Some("combat".into()),
+///         _ => None,
+///     }),
+/// );
+/// ```
+///
+/// [`State`]: https://docs.rs/bevy/latest/bevy/state/state/struct.State.html
+pub fn follow_resource<R: Resource>(
+    brain: impl Fn(&R) -> Option<Cow<'static, str>> + Send + Sync + 'static,
+) -> impl System<In = (), Out = ()> {
+    let system = move |mut commands: Commands, resource: Option<Res<R>>, actors: Query<(Entity, &Brains)>| {
+        let Some(name) = resource
+            .filter(|resource| resource.is_changed())
+            .and_then(|resource| brain(&resource))
+        else {
+            return;
+        };
+        for (actor, brains) in actors.iter() {
+            if brains.active != name && brains.contains(&name) {
+                commands.trigger(SwitchBrain::new(actor, name.clone()));
+            }
+        }
+    };
+    IntoSystem::into_system(system)
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::entity_disabling::Disabled, prelude::*};
+
+    use crate::{
+        acting::CurrentAction,
+        brain::{BrainSwitchPolicy, Brains, SwitchBrain, follow_resource},
+        event::{OnActionEnded, RunPicking, RunScoring},
+        picking::{Highest, Picker},
+        scoring::{FixedScore, Score},
+    };
+
+    #[derive(Component)]
+    struct Idle;
+
+    #[derive(Component)]
+    struct Wander;
+
+    #[derive(Component)]
+    struct Attack;
+
+    #[derive(Resource, Clone, Copy, PartialEq, Eq)]
+    enum Mode {
+        Exploring,
+        Fighting,
+    }
+
+    #[test]
+    fn switch_brains() {
+        let mut app = App::new();
+        app.add_plugins(crate::ObservedUtilityPlugins::TurnBased);
+        app.insert_resource(Mode::Exploring);
+        app.add_systems(
+            Update,
+            follow_resource(|mode: &Mode| match mode {
+                Mode::Exploring => Some("peaceful".into()),
+                Mode::Fighting => Some("combat".into()),
+            }),
+        );
+        let world = app.world_mut();
+
+        let idle = world.register_component::<Idle>();
+        let wander = world.register_component::<Wander>();
+        let attack = world.register_component::<Attack>();
+
+        let curiosity = world.spawn((FixedScore::new(0.6), Score::default())).id();
+        let aggression = world.spawn((FixedScore::new(0.8), Score::default())).id();
+        let actor = world
+            .spawn((
+                Brains::new("peaceful", Picker::new(idle).with(curiosity, wander))
+                    .with("combat", Picker::new(idle).with(aggression, attack))
+                    .with_policy(BrainSwitchPolicy::KeepCurrent),
+                Highest,
+                CurrentAction(wander),
+            ))
+            .add_children(&[curiosity, aggression])
+            .id();
+        world.flush();
+
+        // The inactive brain isn't scored.
+        world.trigger(RunScoring::all());
+        world.flush();
+        assert_eq!(world.entity(curiosity).get::<Score>().unwrap().get(), 0.6);
+        assert_eq!(world.entity(aggression).get::<Score>().unwrap().get(), 0.);
+
+        // The current action is kept until it ends.
+        world.insert_resource(Mode::Fighting);
+        app.update();
+        let world = app.world_mut();
+        assert_eq!(world.get::<Brains>(actor).unwrap().active(), "combat");
+        assert_eq!(world.get::<CurrentAction>(actor).unwrap().0, wander);
+        assert!(world.entity(curiosity).contains::<Disabled>());
+
+        world.trigger(RunScoring::all());
+        world.flush();
+        world.trigger(RunPicking::all());
+        world.flush();
+        world.trigger(OnActionEnded::completed(actor, wander));
+        world.flush();
+        assert_eq!(world.get::<CurrentAction>(actor).unwrap().0, attack);
+
+        // Re-picking switches immediately.
+        world
+            .get_mut::<Brains>(actor)
+            .unwrap()
+            .set_policy(BrainSwitchPolicy::Repick);
+        world.trigger(SwitchBrain::new(actor, "peaceful"));
+        world.flush();
+        assert_eq!(world.get::<CurrentAction>(actor).unwrap().0, wander);
+        assert_eq!(world.get::<Picker>(actor).unwrap().picked, wander);
+
+        // Following the resource only switches when it changes.
+        app.update();
+        let world = app.world_mut();
+        assert_eq!(world.get::<Brains>(actor).unwrap().active(), "peaceful");
+        assert!(world.entity(aggression).contains::<Disabled>());
+
+        // Removing the brains re-enables all choices.
+        world.entity_mut(actor).remove::<Brains>();
+        world.flush();
+        assert!(!world.entity(curiosity).contains::<Disabled>());
+        assert!(!world.entity(aggression).contains::<Disabled>());
+    }
+}
@@ -0,0 +1,344 @@
+//! Budgets cap the AI work done per tick, so that large crowds of actors can't blow the frame time.
+//!
+//! In [`LifecycleMode::Budgeted`], the [`RealtimeLifecyclePlugin`] scores and picks at most `N` actors,
+//! or for at most `M` microseconds, per tick, as configured by the [`AiBudget`] resource.
+//! Actors are queued round-robin: each tick continues where the last one stopped,
+//! so every actor thinks once before any actor thinks twice.
+//! The [`AiBudgetMetrics`] resource reports how much work was done and how long actors are waiting.
+//!
+//! At least one actor is processed per tick, even if the time budget is already spent,
+//! so that the queue always makes progress. [`SelfScheduled`] pickers aren't queued.
+//!
+//! Only the [`Score`] children of queued actors are scored, along with their subtrees.
+//! Score trees that aren't parented to a [`Picker`] actor, such as standalone scores read by several actors,
+//! aren't scored in this mode. Trigger [`RunScoring`] for them from your own systems, at whatever rate suits them.
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::time::Duration;
+//!
+//! use bevy::prelude::*;
+//! use bevy_observed_utility::{RealtimeLifecyclePlugin, prelude::*};
+//!
+//! # let mut app = App::new();
+//! app.add_plugins(ObservedUtilityPlugins::TurnBased).add_plugins(RealtimeLifecyclePlugin {
+//!     // At most 50 actors or 500 microseconds of scoring and picking per tick.
+//!     mode: LifecycleMode::Budgeted(
+//!         AiBudget::default()
+//!             .with_max_actors(50)
+//!             .with_max_time(Duration::from_micros(500)),
+//!     ),
+//!     ..default()
+//! });
+//! ```
+//!
+//! [`RealtimeLifecyclePlugin`]: crate::RealtimeLifecyclePlugin
+//! [`Score`]: crate::scoring::Score
+//! [`RunScoring`]: crate::event::RunScoring
+
+use std::{collections::VecDeque, time::Duration};
+
+use bevy::{ecs::entity::EntityHashMap, prelude::*};
+
+use crate::picking::{Picker, SelfScheduled};
+
+/// How the [`RealtimeLifecyclePlugin`](crate::RealtimeLifecyclePlugin) schedules scoring and picking.
+#[derive(Clone, Debug, Default)]
+pub enum LifecycleMode {
+    /// Score and pick all actors every tick.
+    #[default]
+    EveryTick,
+    /// Score and pick a rotating subset of actors every tick, within the given [`AiBudget`].
+    Budgeted(AiBudget),
+}
+
+/// [`Resource`] for the AI work budget per tick and the round-robin queue of actors waiting to think.
+///
+/// See the [module docs](crate::budget) for more information.
+#[derive(Resource, Reflect)]
+#[derive(Clone, PartialEq, Debug, Default)]
+#[reflect(Resource, PartialEq, Debug, Default)]
+pub struct AiBudget {
+    /// The maximum number of actors to process per tick, or [`None`] for no limit.
+    max_actors: Option<usize>,
+    /// The maximum time to spend per tick, or [`None`] for no limit.
+    max_time: Option<Duration>,
+    /// The actors waiting to think, next first.
+    queue: VecDeque<Entity>,
+    /// Map of queued actors to the number of ticks they've been waiting since they last thought.
+    waiting: EntityHashMap<u32>,
+}
+
+impl AiBudget {
+    /// Limits the number of actors to process per tick, at least 1.
+    #[must_use]
+    pub fn with_max_actors(mut self, max_actors: usize) -> Self {
+        self.max_actors = Some(max_actors.max(1));
+        self
+    }
+
+    /// Limits the time to spend per tick.
+    #[must_use]
+    pub fn with_max_time(mut self, max_time: Duration) -> Self {
+        self.max_time = Some(max_time);
+        self
+    }
+
+    /// Returns the maximum number of actors to process per tick, or [`None`] for no limit.
+    #[must_use]
+    pub fn max_actors(&self) -> Option<usize> {
+        self.max_actors
+    }
+
+    /// Sets the maximum number of actors to process per tick, at least 1, or [`None`] for no limit.
+    pub fn set_max_actors(&mut self, max_actors: Option<usize>) {
+        self.max_actors = max_actors.map(|max| max.max(1));
+    }
+
+    /// Returns the maximum time to spend per tick, or [`None`] for no limit.
+    #[must_use]
+    pub fn max_time(&self) -> Option<Duration> {
+        self.max_time
+    }
+
+    /// Sets the maximum time to spend per tick, or [`None`] for no limit.
+    pub fn set_max_time(&mut self, max_time: Option<Duration>) {
+        self.max_time = max_time;
+    }
+
+    /// Returns the number of ticks the actor has been waiting since it last thought, if it's queued.
+    #[must_use]
+    pub fn waiting(&self, actor: Entity) -> Option<u32> {
+        self.waiting.get(&actor).copied()
+    }
+
+    /// Returns the number of queued actors.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Returns `true` if no actors are queued.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Queues the actor at the back, if it isn't queued yet.
+    pub fn enqueue(&mut self, actor: Entity) {
+        if self.waiting.insert(actor, 0).is_none() {
+            self.queue.push_back(actor);
+        }
+    }
+
+    /// Drops the actor from the queue.
+    pub fn dequeue(&mut self, actor: Entity) {
+        if self.waiting.remove(&actor).is_some() {
+            self.queue.retain(|&queued| queued != actor);
+        }
+    }
+
+    /// [`Observer`] that queues actors when they get a [`Picker`], unless they're [`SelfScheduled`].
+    pub fn on_add_picker(
+        trigger: On<Add, Picker>,
+        mut budget: ResMut<AiBudget>,
+        self_scheduled: Query<(), With<SelfScheduled>>,
+    ) {
+        let actor = trigger.event().entity;
+        if !self_scheduled.contains(actor) {
+            budget.enqueue(actor);
+        }
+    }
+
+    /// [`Observer`] that drops actors from the queue when their [`Picker`] is removed.
+    pub fn on_remove_picker(trigger: On<Remove, Picker>, mut budget: ResMut<AiBudget>) {
+        budget.dequeue(trigger.event().entity);
+    }
+
+    /// [`Observer`] that drops actors from the queue when they become [`SelfScheduled`].
+    pub fn on_add_self_scheduled(trigger: On<Add, SelfScheduled>, mut budget: ResMut<AiBudget>) {
+        budget.dequeue(trigger.event().entity);
+    }
+
+    /// [`Observer`] that queues actors again when they stop being [`SelfScheduled`].
+    pub fn on_remove_self_scheduled(
+        trigger: On<Remove, SelfScheduled>,
+        mut budget: ResMut<AiBudget>,
+        pickers: Query<(), With<Picker>>,
+    ) {
+        let actor = trigger.event().entity;
+        if pickers.contains(actor) {
+            budget.enqueue(actor);
+        }
+    }
+
+    /// Returns the next actor to think, if the budget allows another one after `processed` actors in `elapsed` time.
+    ///
+    /// The actor is moved to the back of the queue.
+    pub fn next(&mut self, processed: usize, elapsed: Duration) -> Option<Entity> {
+        if self.max_actors.is_some_and(|max| processed >= max)
+            || processed >= self.queue.len()
+            || (processed > 0 && self.max_time.is_some_and(|max| elapsed >= max))
+        {
+            return None;
+        }
+        let actor = self.queue.pop_front()?;
+        self.queue.push_back(actor);
+        self.waiting.insert(actor, 0);
+        Some(actor)
+    }
+
+    /// Ends the tick, returning the metrics of the tick.
+    ///
+    /// Every actor that didn't think waits one more tick.
+    pub fn finish(&mut self, processed: usize, elapsed: Duration) -> AiBudgetMetrics {
+        for waiting in self.waiting.values_mut() {
+            *waiting += 1;
+        }
+        // The actors that thought were moved to the back of the queue.
+        for actor in self.queue.iter().rev().take(processed) {
+            self.waiting.insert(*actor, 0);
+        }
+        AiBudgetMetrics {
+            processed,
+            pending: self.queue.len() - processed,
+            elapsed,
+            max_waiting: self.waiting.values().copied().max().unwrap_or_default(),
+        }
+    }
+}
+
+/// [`Resource`] for the metrics of the last budgeted tick.
+///
+/// See the [module docs](crate::budget) for more information.
+#[derive(Resource, Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[reflect(Resource, PartialEq, Debug, Default)]
+pub struct AiBudgetMetrics {
+    /// The number of actors that thought.
+    pub processed: usize,
+    /// The number of queued actors that didn't think.
+    pub pending: usize,
+    /// The time spent scoring and picking.
+    pub elapsed: Duration,
+    /// The most ticks any queued actor has been waiting since it last thought, a measure of starvation.
+    pub max_waiting: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::{ecs::schedule::ScheduleLabel, prelude::*};
+
+    use crate::{
+        RealtimeLifecyclePlugin,
+        budget::{AiBudget, AiBudgetMetrics, LifecycleMode},
+        event::OnPicked,
+        picking::{FirstToScore, Picker, SelfScheduled},
+        scoring::{FixedScore, Score},
+    };
+
+    #[derive(Component)]
+    struct Idle;
+
+    #[derive(Component)]
+    struct Work;
+
+    #[derive(Resource, Default)]
+    struct Picked(Vec<Entity>);
+
+    #[test]
+    fn round_robin() {
+        let mut app = App::new();
+        app.add_plugins(crate::ObservedUtilityPlugins::TurnBased);
+        app.add_plugins(RealtimeLifecyclePlugin {
+            score_pick_perform_in: Update.intern(),
+            mode: LifecycleMode::Budgeted(AiBudget::default().with_max_actors(2)),
+        });
+        app.init_resource::<Picked>();
+        app.add_observer(|trigger: On<OnPicked>, mut picked: ResMut<Picked>| picked.0.push(trigger.event().entity));
+        let world = app.world_mut();
+
+        let idle = world.register_component::<Idle>();
+        let work = world.register_component::<Work>();
+
+        let actors: Vec<Entity> = (0..5)
+            .map(|_| {
+                let scorer = world.spawn((FixedScore::new(0.8), Score::default())).id();
+                world
+                    .spawn((Picker::new(idle).with(scorer, work), FirstToScore::new(0.5)))
+                    .add_child(scorer)
+                    .id()
+            })
+            .collect();
+
+        app.update();
+        let metrics = *app.world().resource::<AiBudgetMetrics>();
+        assert_eq!(metrics.processed, 2);
+        assert_eq!(metrics.pending, 3);
+        assert_eq!(metrics.max_waiting, 1);
+
+        app.update();
+        app.update();
+        // Every actor thought once before any thought twice.
+        let picked = &app.world().resource::<Picked>().0;
+        assert_eq!(picked.len(), 6);
+        let mut first_round = picked[..5].to_vec();
+        first_round.sort();
+        let mut sorted_actors = actors.clone();
+        sorted_actors.sort();
+        assert_eq!(first_round, sorted_actors);
+        assert_eq!(picked[5], picked[0]);
+
+        let budget = app.world().resource::<AiBudget>();
+        assert_eq!(budget.waiting(picked[0]), Some(0));
+        assert_eq!(budget.waiting(picked[2]), Some(1));
+        assert_eq!(app.world().resource::<AiBudgetMetrics>().max_waiting, 2);
+    }
+
+    #[test]
+    fn time_budget_makes_progress() {
+        let mut budget = AiBudget::default().with_max_time(Duration::ZERO);
+        let [a, b] = [Entity::from_raw_u32(1).unwrap(), Entity::from_raw_u32(2).unwrap()];
+        budget.enqueue(a);
+        budget.enqueue(b);
+
+        assert_eq!(budget.next(0, Duration::from_millis(1)), Some(a));
+        assert_eq!(budget.next(1, Duration::from_millis(1)), None);
+        let metrics = budget.finish(1, Duration::from_millis(1));
+        assert_eq!((metrics.processed, metrics.pending, metrics.max_waiting), (1, 1, 1));
+
+        assert_eq!(budget.next(0, Duration::ZERO), Some(b));
+    }
+
+    #[test]
+    fn queue_follows_pickers() {
+        let mut app = App::new();
+        app.add_plugins(crate::ObservedUtilityPlugins::TurnBased);
+        app.add_plugins(RealtimeLifecyclePlugin {
+            score_pick_perform_in: Update.intern(),
+            mode: LifecycleMode::Budgeted(AiBudget::default().with_max_actors(0)),
+        });
+        let world = app.world_mut();
+
+        let idle = world.register_component::<Idle>();
+        let a = world.spawn(Picker::new(idle)).id();
+        let b = world.spawn(Picker::new(idle)).id();
+        world.spawn((Picker::new(idle), SelfScheduled));
+        assert_eq!(world.resource::<AiBudget>().len(), 2);
+
+        // A zero actor limit still lets one actor think per tick.
+        app.update();
+        assert_eq!(app.world().resource::<AiBudgetMetrics>().processed, 1);
+
+        let world = app.world_mut();
+        world.entity_mut(a).insert(SelfScheduled);
+        world.despawn(b);
+        assert!(world.resource::<AiBudget>().is_empty());
+
+        world.entity_mut(a).remove::<SelfScheduled>();
+        assert_eq!(world.resource::<AiBudget>().waiting(a), Some(0));
+    }
+}
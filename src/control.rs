@@ -0,0 +1,360 @@
+//! Manual control lets cutscenes and player possession force actions on actors, overriding their AI.
+//!
+//! An actor's [`ActionOverrides`] component is a stack of [`ControlLayer`]s,
+//! where the highest active layer's requested action wins:
+//! [`Script`](ControlLayer::Script) beats [`Player`](ControlLayer::Player),
+//! which beats the implicit [`Ai`](ControlLayer::Ai) layer, the action picked by the actor's [`Picker`].
+//!
+//! - Trigger [`PushOverride`] to force an action on a layer.
+//! - Trigger [`PopOverride`] to release a layer, resuming the next highest layer or the AI's own pick.
+//!
+//! Overrides are built on [`RequestAction`] and [`CurrentAction`]: while an override is active,
+//! every [`RequestAction`] for the actor resolves to the overriding action.
+//! When an overriding action completes, its layer is popped automatically.
+//! Scoring and picking keep running while the AI is suppressed, so the [`Picker`] can still be inspected for debugging.
+//!
+//! # Example
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_observed_utility::prelude::*;
+//!
+//! #[derive(Component)]
+//! struct Idle;
+//! #[derive(Component)]
+//! struct Bow;
+//!
+//! # let mut app = App::new();
+//! # app.add_plugins(ObservedUtilityPlugins::RealTime);
+//! # let world = app.world_mut();
+//! let idle = world.register_component::<Idle>();
+//! let bow = world.register_component::<Bow>();
+//!
+//! let actor = world.spawn((Picker::new(idle), ActionOverrides::default())).id();
+//!
+//! // The cutscene makes the actor bow, no matter what the AI picks.
+//! world.trigger(PushOverride::new(actor, ControlLayer::Script, bow));
+//! # world.flush();
+//! assert_eq!(world.get::<CurrentAction>(actor).unwrap().0, bow);
+//!
+//! // Once the cutscene ends, the AI resumes.
+//! world.trigger(PopOverride::new(actor, ControlLayer::Script));
+//! # world.flush();
+//! assert_eq!(world.get::<CurrentAction>(actor).unwrap().0, idle);
+//! ```
+//!
+//! [`Picker`]: crate::picking::Picker
+//! [`CurrentAction`]: crate::acting::CurrentAction
+
+use bevy::{ecs::component::ComponentId, prelude::*};
+
+use crate::event::{ActionEndReason, OnActionEnded, RequestAction};
+
+/// [`Plugin`] that pushes and pops [`ActionOverrides`] layers.
+///
+/// This plugin is included in [`ObservedUtilityPlugins`](crate::ObservedUtilityPlugins).
+#[derive(Default)]
+pub struct ControlPlugin;
+
+impl Plugin for ControlPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(on_push_override_request)
+            .add_observer(on_pop_override_request)
+            .add_observer(on_action_ended_pop_override);
+
+        app.register_type::<ActionOverrides>().register_type::<ControlLayer>();
+
+        app.register_type::<PushOverride>().register_type::<PopOverride>();
+    }
+}
+
+/// A layer of control over an actor, in increasing priority.
+#[derive(Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[reflect(PartialEq, Hash, Debug)]
+pub enum ControlLayer {
+    /// The actor's own AI, which is the action picked by its [`Picker`](crate::picking::Picker).
+    ///
+    /// This layer is implicit, so it can't be pushed or popped.
+    Ai,
+    /// The player possessing the actor.
+    Player,
+    /// Scripts, such as cutscenes.
+    Script,
+}
+
+/// [`Component`] for actor entities that can have their actions overridden by higher [`ControlLayer`]s.
+///
+/// See the [module docs](crate::control) for more information.
+#[derive(Component, Reflect)]
+#[derive(Clone, PartialEq, Debug, Default)]
+#[reflect(Component, PartialEq, Debug, Default)]
+pub struct ActionOverrides {
+    /// The overriding actions, sorted by increasing layer.
+    layers: Vec<(ControlLayer, ComponentId)>,
+}
+
+impl ActionOverrides {
+    /// Returns the highest active layer and its overriding action, or [`None`] if the AI is in control.
+    #[must_use]
+    pub fn active(&self) -> Option<(ControlLayer, ComponentId)> {
+        self.layers.last().copied()
+    }
+
+    /// Returns the highest active layer.
+    #[must_use]
+    pub fn active_layer(&self) -> ControlLayer {
+        self.active().map_or(ControlLayer::Ai, |(layer, _)| layer)
+    }
+
+    /// Returns the overriding action of the layer, if it's active.
+    #[must_use]
+    pub fn get(&self, layer: ControlLayer) -> Option<ComponentId> {
+        self.layers
+            .iter()
+            .find(|&&(active, _)| active == layer)
+            .map(|&(_, action)| action)
+    }
+
+    /// Returns an iterator over all active layers and their overriding actions, in increasing priority.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (ControlLayer, ComponentId)> {
+        self.layers.iter().copied()
+    }
+
+    /// Overrides the action of the layer, returning the action it previously overrode with, if any.
+    ///
+    /// Pushing onto the [`Ai`](ControlLayer::Ai) layer does nothing.
+    pub fn push(&mut self, layer: ControlLayer, action: ComponentId) -> Option<ComponentId> {
+        if layer == ControlLayer::Ai {
+            return None;
+        }
+        let previous = self.pop(layer);
+        let index = self.layers.partition_point(|&(active, _)| active < layer);
+        self.layers.insert(index, (layer, action));
+        previous
+    }
+
+    /// Releases the layer, returning the action it overrode with, if any.
+    pub fn pop(&mut self, layer: ControlLayer) -> Option<ComponentId> {
+        let index = self.layers.iter().position(|&(active, _)| active == layer)?;
+        Some(self.layers.remove(index).1)
+    }
+
+    /// Resolves the action to initiate for a request, given the AI's requested action.
+    #[must_use]
+    pub fn resolve(&self, requested: ComponentId) -> ComponentId {
+        self.active().map_or(requested, |(_, action)| action)
+    }
+}
+
+/// Trigger this [`Event`] to override the action of the target actor entity on a [`ControlLayer`].
+///
+/// The actor requests the overriding action, if its layer is the highest active one.
+#[derive(Event, Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[reflect(PartialEq, Debug)]
+pub struct PushOverride {
+    /// The actor entity to override.
+    pub entity: Entity,
+    /// The layer to override on.
+    pub layer: ControlLayer,
+    /// [`ComponentId`] of the overriding action.
+    pub action: ComponentId,
+}
+
+impl PushOverride {
+    /// Creates a new [`PushOverride`] event.
+    #[must_use]
+    pub fn new(entity: Entity, layer: ControlLayer, action: ComponentId) -> Self {
+        Self { entity, layer, action }
+    }
+}
+
+/// Trigger this [`Event`] to release a [`ControlLayer`] of the target actor entity.
+///
+/// The actor requests the action of the next highest layer, or the AI's own pick.
+#[derive(Event, Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[reflect(PartialEq, Debug)]
+pub struct PopOverride {
+    /// The actor entity to release.
+    pub entity: Entity,
+    /// The layer to release.
+    pub layer: ControlLayer,
+}
+
+impl PopOverride {
+    /// Creates a new [`PopOverride`] event.
+    #[must_use]
+    pub fn new(entity: Entity, layer: ControlLayer) -> Self {
+        Self { entity, layer }
+    }
+}
+
+/// [`Observer`] that pushes overrides for [`PushOverride`] events and requests the winning action.
+pub fn on_push_override_request(
+    trigger: On<PushOverride>,
+    mut commands: Commands,
+    mut actors: Query<&mut ActionOverrides>,
+) {
+    let PushOverride { entity, layer, action } = *trigger.event();
+    let Ok(mut overrides) = actors.get_mut(entity) else {
+        // The actor can't be overridden.
+        return;
+    };
+
+    overrides.push(layer, action);
+    if let Some((_, action)) = overrides.active() {
+        commands.trigger(RequestAction::specific(entity, action));
+    }
+}
+
+/// [`Observer`] that pops overrides for [`PopOverride`] events and requests the next winning action.
+pub fn on_pop_override_request(
+    trigger: On<PopOverride>,
+    mut commands: Commands,
+    mut actors: Query<&mut ActionOverrides>,
+) {
+    let PopOverride { entity, layer } = *trigger.event();
+    let Ok(mut overrides) = actors.get_mut(entity) else {
+        // The actor can't be overridden.
+        return;
+    };
+
+    if overrides.pop(layer).is_some() {
+        commands.trigger(RequestAction::picked(entity));
+    }
+}
+
+/// [`Observer`] that pops the active override when its action completes.
+pub fn on_action_ended_pop_override(trigger: On<OnActionEnded>, mut actors: Query<&mut ActionOverrides>) {
+    let OnActionEnded { entity, action, reason } = *trigger.event();
+    if reason != ActionEndReason::Completed {
+        return;
+    }
+    let Ok(mut overrides) = actors.get_mut(entity) else {
+        // The actor can't be overridden.
+        return;
+    };
+
+    if let Some((layer, active)) = overrides.active()
+        && active == action
+    {
+        overrides.pop(layer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use crate::{
+        acting::CurrentAction,
+        control::{ActionOverrides, ControlLayer, PopOverride, PushOverride},
+        event::{OnActionEnded, OnActionInitiated, RejectAction, RequestAction, RunPicking, RunScoring},
+        picking::{Highest, Picker},
+        scoring::{FixedScore, Score},
+    };
+
+    #[derive(Component)]
+    struct Idle;
+
+    #[derive(Component)]
+    struct Wander;
+
+    #[derive(Component)]
+    struct Walk;
+
+    #[derive(Component)]
+    struct Bow;
+
+    #[test]
+    fn layers() {
+        let mut app = App::new();
+        app.add_plugins(crate::ObservedUtilityPlugins::TurnBased);
+        let world = app.world_mut();
+
+        let idle = world.register_component::<Idle>();
+        let wander = world.register_component::<Wander>();
+        let walk = world.register_component::<Walk>();
+        let bow = world.register_component::<Bow>();
+
+        let scorer = world.spawn((FixedScore::new(0.8), Score::default())).id();
+        let actor = world
+            .spawn((
+                Picker::new(idle).with(scorer, wander),
+                Highest,
+                ActionOverrides::default(),
+            ))
+            .add_child(scorer)
+            .id();
+        let current = |world: &World| world.get::<CurrentAction>(actor).unwrap().0;
+
+        world.trigger(PushOverride::new(actor, ControlLayer::Player, walk));
+        world.flush();
+        assert_eq!(current(world), walk);
+
+        // The AI keeps picking, but its requests are suppressed.
+        world.trigger(RunScoring::all());
+        world.trigger(RunPicking::all());
+        world.flush();
+        world.trigger(RequestAction::picked(actor));
+        world.flush();
+        assert_eq!(world.get::<Picker>(actor).unwrap().picked, wander);
+        assert_eq!(current(world), walk);
+
+        // Scripts win over the player.
+        world.trigger(PushOverride::new(actor, ControlLayer::Script, bow));
+        world.flush();
+        assert_eq!(current(world), bow);
+        world.trigger(PopOverride::new(actor, ControlLayer::Script));
+        world.flush();
+        assert_eq!(current(world), walk);
+
+        // Completing the overriding action pops its layer, resuming the AI's pick.
+        world.trigger(OnActionEnded::completed(actor, walk));
+        world.flush();
+        let overrides = world.get::<ActionOverrides>(actor).unwrap();
+        assert_eq!(overrides.active_layer(), ControlLayer::Ai);
+        assert_eq!(current(world), wander);
+    }
+
+    #[test]
+    fn reject_override() {
+        let mut app = App::new();
+        app.add_plugins(crate::ObservedUtilityPlugins::TurnBased);
+        let world = app.world_mut();
+
+        let idle = world.register_component::<Idle>();
+        let wander = world.register_component::<Wander>();
+        let bow = world.register_component::<Bow>();
+
+        // Bowing can never start.
+        world.add_observer(move |trigger: On<OnActionInitiated>, mut commands: Commands| {
+            let OnActionInitiated { entity, action } = *trigger.event();
+            if action == bow {
+                commands.trigger(RejectAction { entity, action });
+            }
+        });
+
+        let scorer = world.spawn((FixedScore::new(0.8), Score::default())).id();
+        let actor = world
+            .spawn((
+                Picker::new(idle).with(scorer, wander),
+                Highest,
+                ActionOverrides::default(),
+            ))
+            .add_child(scorer)
+            .id();
+        world.trigger(RunScoring::all());
+        world.trigger(RunPicking::all());
+        world.flush();
+
+        // The rejected override is popped, handing control back to the AI.
+        world.trigger(PushOverride::new(actor, ControlLayer::Script, bow));
+        world.flush();
+        let overrides = world.get::<ActionOverrides>(actor).unwrap();
+        assert_eq!(overrides.active_layer(), ControlLayer::Ai);
+        assert_eq!(world.get::<CurrentAction>(actor).unwrap().0, wander);
+    }
+}
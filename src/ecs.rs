@@ -13,6 +13,11 @@ use bevy::{
     prelude::*,
 };
 
+use crate::{
+    event::{RunPicking, RunScoring},
+    scoring::Score,
+};
+
 /// A [`Query`] wrapper that finds the closest ancestor entity with a given component.
 /// Uses a cache to speed up subsequent queries.
 #[derive(SystemParam)]
@@ -52,6 +57,25 @@ impl<'w, T: ReferenceType> AncestorQuery<'w, '_, T> {
         }
     }
 
+    /// Returns the closest ancestor [`Entity`] with the component `T`, if any.
+    ///
+    /// # Errors
+    ///
+    /// If the entity does not exist or the component is not found.
+    pub fn entity(&mut self, start: Entity) -> Result<Entity, QueryEntityError> {
+        // Check the cache first
+        if let Some(&cached) = self.cache.get(&start) {
+            if self.fetch.contains(cached) {
+                // Cache hit
+                return Ok(cached);
+            }
+            // Cache miss - remove stale entry
+            self.cache.remove(&start);
+        }
+
+        self.find(start)
+    }
+
     /// Clears the cache to free up memory, if necessary.
     pub fn clear_cache(&mut self) {
         self.cache.clear();
@@ -154,7 +178,7 @@ impl<'w, 's, R: Resource + Default> OnceCommands<'w, 's, R> {
 
     /// Adds the specified [`Observer`] system if and only if the [`Resource`] `R` has not been inserted into the [`World`] yet.
     /// After running the command, the resource is inserted into the world.
-    pub fn observe<E: Event, B: Bundle, M>(mut self, observer: impl IntoObserverSystem<E, B, M> + Send + 'static) {
+    pub fn observe<E: Event, B: Bundle, M>(mut self, observer: impl IntoObserverSystem<E, B, M> + 'static) {
         self.commands.queue(Once::<R, _> {
             _type: PhantomData,
             command: Some(move |world: &mut World| {
@@ -177,6 +201,51 @@ impl CommandsExt for Commands<'_, '_> {
     }
 }
 
+/// Returns the elapsed [`Time`], in seconds, or `0` if there is no [`Time`] resource.
+pub(crate) fn elapsed_secs(time: Option<&Time>) -> f64 {
+    time.map_or(0., Time::elapsed_secs_f64)
+}
+
+/// Advances an interval timer by the given number of seconds, returning `true` if the interval has passed.
+///
+/// The time past the interval is carried over to the next interval, up to one interval.
+pub(crate) fn tick_interval(elapsed: &mut f32, interval: f32, seconds: f32) -> bool {
+    *elapsed += seconds;
+    if *elapsed < interval {
+        return false;
+    }
+    *elapsed = if interval > 0. {
+        (*elapsed - interval) % interval
+    } else {
+        0.
+    };
+    true
+}
+
+/// Returns a [`Command`] that scores every [`Score`] child of the picker entity, then picks for it.
+///
+/// If `bypass_caches` is `true`, the children are rescored even if their caches are fresh.
+pub(crate) fn score_and_pick(picker: Entity, bypass_caches: bool) -> impl Command {
+    move |world: &mut World| {
+        let children: Vec<Entity> = world
+            .get::<Children>(picker)
+            .into_iter()
+            .flatten()
+            .copied()
+            .filter(|&child| world.get::<Score>(child).is_some())
+            .collect();
+        for child in children {
+            let scoring = RunScoring::entity(child);
+            world.trigger(if bypass_caches {
+                scoring.bypassing_caches()
+            } else {
+                scoring
+            });
+        }
+        world.trigger(RunPicking::entity(picker));
+    }
+}
+
 /// Extension trait for [`DeferredWorld`] to get a once-commands wrapper.
 pub trait DeferredWorldExt {
     /// Returns a [`Commands`] wrapper that provides a way to run commands only once.
@@ -204,31 +273,46 @@ impl<'w, 's, F: QueryFilter + 'static> DFSPostTraversal<'w, 's, F> {
     /// The deepest children are visited first, followed by their parents.
     #[must_use = "iterators are lazy and do nothing unless consumed"]
     pub fn iter(&mut self, root: Entity) -> DFSPostTraversalIter<'_, 'w, 's, F> {
-        DFSPostTraversalIter::new(self, root)
+        DFSPostTraversalIter::new(self, root, |_| false)
+    }
+
+    /// Returns an iterator like [`DFSPostTraversal::iter`],
+    /// but skipping the whole subtree of each [`Entity`] for which `prune` returns `true`.
+    #[must_use = "iterators are lazy and do nothing unless consumed"]
+    pub fn iter_pruned<P: FnMut(Entity) -> bool>(
+        &mut self,
+        root: Entity,
+        prune: P,
+    ) -> DFSPostTraversalIter<'_, 'w, 's, F, P> {
+        DFSPostTraversalIter::new(self, root, prune)
     }
 }
 
-/// [`Iterator`] type returned by [`DFSPostTraversal::iter`].
-pub struct DFSPostTraversalIter<'a, 'w, 's, F: QueryFilter + 'static> {
+/// [`Iterator`] type returned by [`DFSPostTraversal::iter`] and [`DFSPostTraversal::iter_pruned`].
+pub struct DFSPostTraversalIter<'a, 'w, 's, F: QueryFilter + 'static, P = fn(Entity) -> bool> {
     param: &'a mut DFSPostTraversal<'w, 's, F>,
+    prune: P,
     visited: usize,
     current_depth: usize,
 }
 
-impl<'a, 'w, 's, F: QueryFilter + 'static> DFSPostTraversalIter<'a, 'w, 's, F> {
-    fn new(param: &'a mut DFSPostTraversal<'w, 's, F>, root: Entity) -> Self {
+impl<'a, 'w, 's, F: QueryFilter + 'static, P: FnMut(Entity) -> bool> DFSPostTraversalIter<'a, 'w, 's, F, P> {
+    fn new(param: &'a mut DFSPostTraversal<'w, 's, F>, root: Entity, mut prune: P) -> Self {
         param.queue.clear();
-        param.queue.push_back((0, root));
+        if !prune(root) {
+            param.queue.push_back((0, root));
+        }
 
         Self {
             param,
+            prune,
             visited: 0,
             current_depth: 0,
         }
     }
 }
 
-impl<F: QueryFilter + 'static> Iterator for DFSPostTraversalIter<'_, '_, '_, F> {
+impl<F: QueryFilter + 'static, P: FnMut(Entity) -> bool> Iterator for DFSPostTraversalIter<'_, '_, '_, F, P> {
     type Item = Entity;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -257,7 +341,11 @@ impl<F: QueryFilter + 'static> Iterator for DFSPostTraversalIter<'_, '_, '_, F>
             };
 
             // TODO: can we replace this with some kind of `extend_at`?
-            for (j, child) in entity_children.into_iter().copied().enumerate() {
+            let children = entity_children
+                .into_iter()
+                .copied()
+                .filter(|&child| !(self.prune)(child));
+            for (j, child) in children.enumerate() {
                 self.param.queue.insert(i + j + 1, (depth + 1, child));
             }
         }
@@ -271,4 +359,4 @@ impl<F: QueryFilter + 'static> Iterator for DFSPostTraversalIter<'_, '_, '_, F>
     }
 }
 
-impl<F: QueryFilter + 'static> FusedIterator for DFSPostTraversalIter<'_, '_, '_, F> {}
+impl<F: QueryFilter + 'static, P: FnMut(Entity) -> bool> FusedIterator for DFSPostTraversalIter<'_, '_, '_, F, P> {}
@@ -14,22 +14,31 @@
 //! [`Score`] entities with [`Score`] children will be scored after their children, to ensure correct scoring.
 //! This will trigger the [`OnScore`] event for the target entity, which should be listened to by scoring [`Observer`]s
 //! to calculate the [`Score`] for a given entity.
+//! For [`PostProcessed`] entities and their children it's followed by the [`OnScorePostProcess`] event,
+//! which can be listened to by [`Observer`]s that adjust the calculated [`Score`].
+//! [`InvalidateScoreCache`] can be triggered to rescore a cached entity before its interval elapses.
 //!
 //! # Picking events
 //!
 //! [`RunPicking`] can be triggered to make a specific entity or all entities with the [`Picker`] component pick an action.
 //! This will trigger the [`OnPick`] event for the target entity, which should be listened to by picking [`Observer`]s and
 //! which will trigger the [`OnPicked`] event with the picked action.
+//! Actors with a [`PickResult`] also trigger the [`OnPickChanged`] event when their pick changes.
 //!
 //! # Acting events
 //!
 //! [`RequestAction`] can be triggered to request an action to be initiated for a specific entity.
 //! This will trigger the [`OnActionInitiated`] event for the target entity, using the action picked by their [`Picker`].
+//! [`RejectAction`] can be triggered by action observers to fall back to the next-best choice if an action can't start.
 //! The [`OnActionEnded`] event is triggered by action lifecycle or actions themselves to indicate that they have completed or been cancelled.
 //! In between these two previous events, the action should be executed.
+//! [`OnActionSuspended`] and [`OnActionResumed`] are triggered when an action is suspended and resumed by pausing the AI.
+//! [`Interrupt`] can be triggered to make an actor rescore, re-pick, and request an action immediately.
 //!
 //! [`Score`]: crate::scoring::Score
+//! [`PostProcessed`]: crate::scoring::PostProcessed
 //! [`Picker`]: crate::picking::Picker
+//! [`PickResult`]: crate::picking::PickResult
 
 use bevy::{ecs::component::ComponentId, prelude::*};
 
@@ -42,26 +51,59 @@ use bevy::{ecs::component::ComponentId, prelude::*};
 ///
 /// Entities are scored in depth-first post-order traversal,
 /// ensuring that all children are scored before their parents.
+///
+/// Subtrees of fresh [`ScoreCache`]s are skipped, unless [bypassed](RunScoring::bypassing_caches).
+///
+/// [`ScoreCache`]: crate::scoring::ScoreCache
 #[derive(Event, Reflect)]
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
 #[reflect(PartialEq, Debug, Default)]
 pub struct RunScoring {
     /// The target entity to score, or [`None`] to score all entities.
     pub entity: Option<Entity>,
+    /// Whether to rescore entities with a fresh [`ScoreCache`](crate::scoring::ScoreCache) too.
+    pub bypass_caches: bool,
 }
 
 impl RunScoring {
     /// Creates a new [`RunScoring`] event for all entities.
     #[must_use]
     pub fn all() -> Self {
-        Self { entity: None }
+        Self {
+            entity: None,
+            bypass_caches: false,
+        }
     }
 
     /// Creates a new [`RunScoring`] event for a specific entity.
     #[must_use]
     pub fn entity(entity: Entity) -> Self {
-        Self { entity: Some(entity) }
+        Self {
+            entity: Some(entity),
+            bypass_caches: false,
+        }
     }
+
+    /// Rescores entities with a fresh [`ScoreCache`](crate::scoring::ScoreCache) too, refreshing their caches.
+    #[must_use]
+    pub fn bypassing_caches(mut self) -> Self {
+        self.bypass_caches = true;
+        self
+    }
+}
+
+/// Trigger this [`Event`] to make the targeted entity's [`ScoreCache`] rescore it on the next [`RunScoring`],
+/// even if its interval hasn't elapsed yet.
+/// It also makes the targeted entity's [`AsyncScore`] restart its task and hold its placeholder again.
+///
+/// [`ScoreCache`]: crate::scoring::ScoreCache
+/// [`AsyncScore`]: crate::async_score::AsyncScore
+#[derive(Event, Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[reflect(PartialEq, Debug)]
+pub struct InvalidateScoreCache {
+    /// The cached entity to rescore.
+    pub entity: Entity,
 }
 
 /// This [`Event`] is listened to by scoring systems to calculate the score(s) for a given entity.
@@ -74,6 +116,21 @@ pub struct OnScore {
     pub entity: Entity,
 }
 
+/// This [`Event`] is listened to by post-processing systems to adjust the [`Score`] of a given entity
+/// after all [`OnScore`] observers have calculated it, and before its parent is scored.
+/// Only triggered for entities that are [`PostProcessed`] or whose parent is.
+/// DO NOT TRIGGER MANUALLY, trigger [`RunScoring`] instead.
+///
+/// [`Score`]: crate::scoring::Score
+/// [`PostProcessed`]: crate::scoring::PostProcessed
+#[derive(Event, Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[reflect(PartialEq, Debug)]
+pub struct OnScorePostProcess {
+    /// The entity being post-processed.
+    pub entity: Entity,
+}
+
 ////////////////////////////////////////////////////////////
 // Picking events
 ////////////////////////////////////////////////////////////
@@ -125,6 +182,23 @@ pub struct OnPicked {
     pub action: ComponentId,
 }
 
+/// Listen to this [`Event`] to react when the pick of the target actor entity changes.
+/// This [`Event`] is triggered for actor entities with a [`PickResult`]
+/// when the picked action or choice differs from the previous pick.
+///
+/// [`PickResult`]: crate::picking::PickResult
+#[derive(Event, Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[reflect(PartialEq, Debug)]
+pub struct OnPickChanged {
+    /// The entity whose pick changed.
+    pub entity: Entity,
+    /// [`ComponentId`] of the previously picked action, or [`None`] if this is the first pick.
+    pub previous: Option<ComponentId>,
+    /// [`ComponentId`] of the newly picked action.
+    pub action: ComponentId,
+}
+
 ////////////////////////////////////////////////////////////
 // Action events
 ////////////////////////////////////////////////////////////
@@ -157,6 +231,22 @@ impl RequestAction {
     }
 }
 
+/// Trigger this [`Event`] to make the target actor entity reconsider its action immediately,
+/// such as when it takes damage, instead of waiting for the next scheduled lifecycle run.
+///
+/// This rescores every [`Score`] child of the actor, bypassing [`ScoreCache`]s, re-picks, and requests the picked action.
+/// If the pick changed, the current action is cancelled before the new one is initiated, like any [`RequestAction`].
+///
+/// [`Score`]: crate::scoring::Score
+/// [`ScoreCache`]: crate::scoring::ScoreCache
+#[derive(Event, Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[reflect(PartialEq, Debug)]
+pub struct Interrupt {
+    /// The actor entity to interrupt.
+    pub entity: Entity,
+}
+
 /// This [`Event`] is triggered by action lifecycle to indicate that they have been initiated.
 #[derive(Event, Reflect)]
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -168,6 +258,25 @@ pub struct OnActionInitiated {
     pub action: ComponentId,
 }
 
+/// Trigger this [`Event`] from an [`OnActionInitiated`] observer when the initiated action can't actually start,
+/// such as when its path is blocked or its target is gone.
+///
+/// The rejected action is cancelled, and the next-ranked choice of the actor's last pick is initiated immediately,
+/// down to the default action of its [`Picker`].
+/// If the rejected action was forced by the active [`ActionOverrides`] layer, that layer is popped instead.
+///
+/// [`Picker`]: crate::picking::Picker
+/// [`ActionOverrides`]: crate::control::ActionOverrides
+#[derive(Event, Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[reflect(PartialEq, Debug)]
+pub struct RejectAction {
+    /// The entity whose action was rejected.
+    pub entity: Entity,
+    /// [`ComponentId`] of the action that was rejected.
+    pub action: ComponentId,
+}
+
 /// This [`Event`] is triggered by action lifecycle or actions themselves to indicate
 /// that they have completed or been cancelled.
 ///
@@ -206,6 +315,31 @@ impl OnActionEnded {
     }
 }
 
+/// This [`Event`] is triggered when the current action of an actor is suspended by pausing its AI.
+///
+/// Action observers should stop performing the action until [`OnActionResumed`] is triggered,
+/// without cleaning it up as if it ended.
+#[derive(Event, Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[reflect(PartialEq, Debug)]
+pub struct OnActionSuspended {
+    /// The entity whose action was suspended.
+    pub entity: Entity,
+    /// [`ComponentId`] of the action that was suspended.
+    pub action: ComponentId,
+}
+
+/// This [`Event`] is triggered when a suspended action of an actor is resumed by resuming its AI.
+#[derive(Event, Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[reflect(PartialEq, Debug)]
+pub struct OnActionResumed {
+    /// The entity whose action was resumed.
+    pub entity: Entity,
+    /// [`ComponentId`] of the action that was resumed.
+    pub action: ComponentId,
+}
+
 /// The reason [`OnActionEnded`] was triggered.
 #[derive(Reflect)]
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
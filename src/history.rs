@@ -0,0 +1,704 @@
+//! Action histories record what actors have done, so that scores can favor novelty and avoid repetition.
+//!
+//! An actor's [`ActionHistory`] component is a ring buffer of [`ActionRecord`]s,
+//! populated from [`OnActionInitiated`] and [`OnActionEnded`] events with timestamps, durations and outcomes.
+//! Once full, the oldest records are dropped.
+//! Timestamps are the elapsed [`Time`] in seconds, or `0` if there is no [`Time`] resource.
+//!
+//! Scorers read from the [`ActionHistory`] of the closest ancestor entity that has one:
+//! - [`TimeSinceAction`] scores how long ago an action was last performed.
+//! - [`ActionFrequency`] scores how often an action was initiated in the last `N` seconds.
+//! - [`RepetitionPenalty`] lowers the score of its entity for each recent repetition of an action.
+//!
+//! # Example
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_observed_utility::prelude::*;
+//!
+//! #[derive(Component)]
+//! struct Wave;
+//!
+//! # let mut app = App::new();
+//! # app.add_plugins(ObservedUtilityPlugins::TurnBased);
+//! # app.init_resource::<Time>();
+//! # let world = app.world_mut();
+//! let wave = world.register_component::<Wave>();
+//!
+//! // Don't wave again within 10 seconds.
+//! let scorer = world
+//!     .spawn((TimeSinceAction::new(wave, 0., 10.), Score::default()))
+//!     .id();
+//!
+//! let actor = world.spawn(ActionHistory::default()).add_child(scorer).id();
+//!
+//! world.trigger(OnActionInitiated { entity: actor, action: wave });
+//! world.trigger(OnActionEnded::completed(actor, wave));
+//! # world.flush();
+//! assert_eq!(world.get::<ActionHistory>(actor).unwrap().len(), 1);
+//! # world.resource_mut::<Time>().advance_by(std::time::Duration::from_secs(5));
+//! # world.trigger(RunScoring::entity(scorer));
+//! # world.flush();
+//! # assert_eq!(world.get::<Score>(scorer).unwrap().get(), 0.5);
+//! ```
+
+use std::collections::VecDeque;
+
+use bevy::{
+    ecs::{
+        component::{ComponentId, StorageType},
+        lifecycle::{ComponentHook, HookContext},
+        world::DeferredWorld,
+    },
+    prelude::*,
+};
+
+use crate::{
+    ecs::{AncestorQuery, DeferredWorldExt, elapsed_secs},
+    event::{ActionEndReason, OnActionEnded, OnActionInitiated, OnScore, OnScorePostProcess},
+    scoring::{Evaluator, LinearEvaluator, PostProcessed, Score},
+};
+
+/// [`Plugin`] that registers the action history types.
+///
+/// This plugin is included in [`ObservedUtilityPlugins`](crate::ObservedUtilityPlugins).
+#[derive(Default)]
+pub struct HistoryPlugin;
+
+impl Plugin for HistoryPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<ActionHistory>()
+            .register_type::<ActionRecord>()
+            .register_type::<TimeSinceAction>()
+            .register_type::<ActionFrequency>()
+            .register_type::<RepetitionPenalty>();
+    }
+}
+
+/// A single action performed by an actor, as recorded in its [`ActionHistory`].
+#[derive(Reflect)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[reflect(PartialEq, Debug)]
+pub struct ActionRecord {
+    /// [`ComponentId`] of the action.
+    pub action: ComponentId,
+    /// The elapsed [`Time`], in seconds, the action was initiated at.
+    pub started: f64,
+    /// The elapsed [`Time`], in seconds, the action ended at, or [`None`] if it's still running.
+    pub ended: Option<f64>,
+    /// The reason the action ended, or [`None`] if it's still running.
+    pub outcome: Option<ActionEndReason>,
+}
+
+impl ActionRecord {
+    /// Returns `true` if the action hasn't ended yet.
+    #[must_use]
+    pub fn is_running(&self) -> bool {
+        self.ended.is_none()
+    }
+
+    /// Returns how long the action ran for, in seconds, or [`None`] if it's still running.
+    #[must_use]
+    pub fn duration(&self) -> Option<f32> {
+        self.ended.map(|ended| (ended - self.started) as f32)
+    }
+}
+
+/// [`Component`] for actor entities that records their most recent actions.
+///
+/// See the [module docs](crate::history) for more information.
+#[derive(Reflect)]
+#[derive(Clone, PartialEq, Debug)]
+#[reflect(Component, PartialEq, Debug)]
+pub struct ActionHistory {
+    /// The recorded actions, oldest first.
+    records: VecDeque<ActionRecord>,
+    /// The maximum number of records to keep.
+    capacity: usize,
+}
+
+impl Default for ActionHistory {
+    fn default() -> Self {
+        Self::new(32)
+    }
+}
+
+impl ActionHistory {
+    /// Creates a new [`ActionHistory`] that keeps up to `capacity` records.
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            records: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Returns the maximum number of records to keep.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of records.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Returns `true` if nothing has been recorded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Returns an iterator over all records, oldest first.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &ActionRecord> {
+        self.records.iter()
+    }
+
+    /// Returns the most recent record, if any.
+    #[must_use]
+    pub fn latest(&self) -> Option<&ActionRecord> {
+        self.records.back()
+    }
+
+    /// Returns the most recent record of the action, if any.
+    #[must_use]
+    pub fn last_of(&self, action: ComponentId) -> Option<&ActionRecord> {
+        self.records.iter().rev().find(|record| record.action == action)
+    }
+
+    /// Returns the number of times the action was initiated at or after the given time, in seconds.
+    #[must_use]
+    pub fn count_since(&self, action: ComponentId, since: f64) -> usize {
+        self.records
+            .iter()
+            .filter(|record| record.action == action && record.started >= since)
+            .count()
+    }
+
+    /// Returns the time, in seconds, since the action was last performed at the given time,
+    /// or [`None`] if it isn't recorded.
+    ///
+    /// Actions that are still running were last performed just now.
+    #[must_use]
+    pub fn time_since(&self, action: ComponentId, now: f64) -> Option<f32> {
+        self.last_of(action)
+            .map(|record| record.ended.map_or(0., |ended| (now - ended).max(0.) as f32))
+    }
+
+    /// Records that the action was initiated at the given time, dropping the oldest record if full.
+    pub fn initiated(&mut self, action: ComponentId, now: f64) {
+        if self.capacity == 0 {
+            return;
+        }
+        while self.records.len() >= self.capacity {
+            self.records.pop_front();
+        }
+        self.records.push_back(ActionRecord {
+            action,
+            started: now,
+            ended: None,
+            outcome: None,
+        });
+    }
+
+    /// Records that the most recent running record of the action ended at the given time, for the given reason.
+    pub fn ended(&mut self, action: ComponentId, now: f64, reason: ActionEndReason) {
+        let running = self
+            .records
+            .iter_mut()
+            .rev()
+            .find(|record| record.action == action && record.is_running());
+        if let Some(record) = running {
+            record.ended = Some(now);
+            record.outcome = Some(reason);
+        }
+    }
+
+    /// [`Observer`] that records initiated actions.
+    fn on_action_initiated(
+        trigger: On<OnActionInitiated>,
+        time: Option<Res<Time>>,
+        mut histories: Query<&mut ActionHistory>,
+    ) {
+        let OnActionInitiated { entity, action } = *trigger.event();
+        let Ok(mut history) = histories.get_mut(entity) else {
+            // The actor has no history.
+            return;
+        };
+
+        history.initiated(action, elapsed_secs(time.as_deref()));
+    }
+
+    /// [`Observer`] that records ended actions.
+    fn on_action_ended(trigger: On<OnActionEnded>, time: Option<Res<Time>>, mut histories: Query<&mut ActionHistory>) {
+        let OnActionEnded { entity, action, reason } = *trigger.event();
+        let Ok(mut history) = histories.get_mut(entity) else {
+            // The actor has no history.
+            return;
+        };
+
+        history.ended(action, elapsed_secs(time.as_deref()), reason);
+    }
+}
+
+impl Component for ActionHistory {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+    type Mutability = bevy::ecs::component::Mutable;
+
+    fn on_add() -> Option<ComponentHook> {
+        Some(|mut world: DeferredWorld, _context: HookContext| {
+            #[derive(Resource, Default)]
+            struct ActionHistoryInitiatedObserverSpawned;
+            #[derive(Resource, Default)]
+            struct ActionHistoryEndedObserverSpawned;
+
+            world
+                .once::<ActionHistoryInitiatedObserverSpawned>()
+                .observe(Self::on_action_initiated);
+            world
+                .once::<ActionHistoryEndedObserverSpawned>()
+                .observe(Self::on_action_ended);
+        })
+    }
+}
+
+/// [`Score`] [`Component`] that scores how long ago an action was last performed, in seconds.
+///
+/// The time is read from the [`ActionHistory`] of the closest ancestor entity that has one.
+/// The time is normalized from the `[min, max]` range, so recently performed actions score lower,
+/// and actions that are still running score the minimum.
+/// If the action isn't recorded, the score is the maximum.
+#[derive(Reflect)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[reflect(Component, PartialEq, Debug)]
+pub struct TimeSinceAction {
+    /// [`ComponentId`] of the action.
+    action: ComponentId,
+    /// The time that scores the minimum.
+    min: f32,
+    /// The time that scores the maximum.
+    max: f32,
+}
+
+impl TimeSinceAction {
+    /// Creates a new [`TimeSinceAction`] that normalizes times, in seconds, from the `[min, max]` range.
+    #[must_use]
+    pub fn new(action: ComponentId, min: f32, max: f32) -> Self {
+        Self { action, min, max }
+    }
+
+    /// Returns the [`ComponentId`] of the action.
+    #[must_use]
+    pub fn action(&self) -> ComponentId {
+        self.action
+    }
+
+    /// [`Observer`] for [`TimeSinceAction`] [`Score`] entities that scores the time since the action was performed.
+    fn observer(
+        trigger: On<OnScore>,
+        time: Option<Res<Time>>,
+        mut target: Query<(&mut Score, &TimeSinceAction)>,
+        mut histories: AncestorQuery<&'static ActionHistory>,
+    ) {
+        let entity = trigger.event().entity;
+        let Ok((mut actor_score, settings)) = target.get_mut(entity) else {
+            // The entity is not scoring for time since action.
+            return;
+        };
+
+        let elapsed = histories
+            .get(entity)
+            .ok()
+            .and_then(|history| history.time_since(settings.action, elapsed_secs(time.as_deref())))
+            .unwrap_or(f32::INFINITY);
+
+        *actor_score = Score::new(LinearEvaluator::from_range(settings.min, settings.max).evaluate(elapsed));
+    }
+}
+
+impl Component for TimeSinceAction {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+    type Mutability = bevy::ecs::component::Immutable;
+
+    fn on_add() -> Option<ComponentHook> {
+        Some(|mut world: DeferredWorld, _context: HookContext| {
+            #[derive(Resource, Default)]
+            struct TimeSinceActionObserverSpawned;
+
+            world.once::<TimeSinceActionObserverSpawned>().observe(Self::observer);
+        })
+    }
+}
+
+/// [`Score`] [`Component`] that scores how many times an action was initiated in the last `window` seconds.
+///
+/// The records are read from the [`ActionHistory`] of the closest ancestor entity that has one.
+/// The count is normalized from the `[min, max]` range.
+#[derive(Reflect)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[reflect(Component, PartialEq, Debug)]
+pub struct ActionFrequency {
+    /// [`ComponentId`] of the action.
+    action: ComponentId,
+    /// How many seconds to look back.
+    window: f32,
+    /// The count that scores the minimum.
+    min: f32,
+    /// The count that scores the maximum.
+    max: f32,
+}
+
+impl ActionFrequency {
+    /// Creates a new [`ActionFrequency`] that counts initiations in the last `window` seconds,
+    /// normalizing the count from the `[min, max]` range.
+    #[must_use]
+    pub fn new(action: ComponentId, window: f32, min: f32, max: f32) -> Self {
+        Self {
+            action,
+            window,
+            min,
+            max,
+        }
+    }
+
+    /// Returns the [`ComponentId`] of the action.
+    #[must_use]
+    pub fn action(&self) -> ComponentId {
+        self.action
+    }
+
+    /// [`Observer`] for [`ActionFrequency`] [`Score`] entities that scores how often the action was initiated.
+    fn observer(
+        trigger: On<OnScore>,
+        time: Option<Res<Time>>,
+        mut target: Query<(&mut Score, &ActionFrequency)>,
+        mut histories: AncestorQuery<&'static ActionHistory>,
+    ) {
+        let entity = trigger.event().entity;
+        let Ok((mut actor_score, settings)) = target.get_mut(entity) else {
+            // The entity is not scoring for action frequency.
+            return;
+        };
+
+        let since = elapsed_secs(time.as_deref()) - f64::from(settings.window);
+        let count = histories
+            .get(entity)
+            .map_or(0, |history| history.count_since(settings.action, since));
+
+        *actor_score = Score::new(LinearEvaluator::from_range(settings.min, settings.max).evaluate(count as f32));
+    }
+}
+
+impl Component for ActionFrequency {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+    type Mutability = bevy::ecs::component::Immutable;
+
+    fn on_add() -> Option<ComponentHook> {
+        Some(|mut world: DeferredWorld, _context: HookContext| {
+            #[derive(Resource, Default)]
+            struct ActionFrequencyObserverSpawned;
+
+            world.once::<ActionFrequencyObserverSpawned>().observe(Self::observer);
+        })
+    }
+}
+
+/// [`Component`] for [`Score`] entities whose score is lowered for each recent repetition of an action,
+/// to keep behavior varied.
+///
+/// After the entity is scored, each initiation of the action in the last `window` seconds
+/// subtracts up to `penalty` from a multiplier of `1`, fading out linearly as the initiation ages.
+/// The score is then multiplied by the remaining multiplier, clamped to `[0, 1]`.
+///
+/// The records are read from the [`ActionHistory`] of the closest ancestor entity that has one.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_observed_utility::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Joke;
+///
+/// # let mut app = App::new();
+/// # app.add_plugins(ObservedUtilityPlugins::TurnBased);
+/// # app.init_resource::<Time>();
+/// # let world = app.world_mut();
+/// let joke = world.register_component::<Joke>();
+///
+/// // Telling a joke is less appealing for a minute after telling one.
+/// let scorer = world
+///     .spawn((FixedScore::new(0.8), RepetitionPenalty::new(joke, 60., 0.5), Score::default()))
+///     .id();
+///
+/// let actor = world.spawn(ActionHistory::default()).add_child(scorer).id();
+/// world.trigger(OnActionInitiated { entity: actor, action: joke });
+/// # world.flush();
+/// # world.trigger(RunScoring::entity(scorer));
+/// # world.flush();
+/// # assert_eq!(world.get::<Score>(scorer).unwrap().get(), 0.4);
+/// ```
+#[derive(Reflect)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[reflect(Component, PartialEq, Debug)]
+pub struct RepetitionPenalty {
+    /// [`ComponentId`] of the action.
+    action: ComponentId,
+    /// How many seconds to look back.
+    window: f32,
+    /// The penalty for each fresh repetition.
+    penalty: f32,
+}
+
+impl RepetitionPenalty {
+    /// Creates a new [`RepetitionPenalty`] that penalizes each initiation of the action in the last `window` seconds
+    /// by up to `penalty`.
+    #[must_use]
+    pub fn new(action: ComponentId, window: f32, penalty: f32) -> Self {
+        Self {
+            action,
+            window,
+            penalty,
+        }
+    }
+
+    /// Returns the [`ComponentId`] of the action.
+    #[must_use]
+    pub fn action(&self) -> ComponentId {
+        self.action
+    }
+
+    /// Returns the multiplier for the given history at the given time, in seconds.
+    /// An empty or negative window never penalizes.
+    #[must_use]
+    pub fn multiplier(&self, history: &ActionHistory, now: f64) -> f32 {
+        if self.window <= 0. {
+            return 1.;
+        }
+        let fade = LinearEvaluator::from_range(self.window, 0.);
+        let total: f32 = history
+            .iter()
+            .filter(|record| record.action == self.action)
+            .map(|record| (now - record.started) as f32)
+            .filter(|&age| age <= self.window)
+            .map(|age| self.penalty * fade.evaluate(age))
+            .sum();
+        (1. - total).clamp(0., 1.)
+    }
+
+    /// [`Observer`] for [`RepetitionPenalty`] [`Score`] entities that applies the penalty to the calculated score.
+    fn observer(
+        trigger: On<OnScorePostProcess>,
+        time: Option<Res<Time>>,
+        mut target: Query<(&mut Score, &RepetitionPenalty)>,
+        mut histories: AncestorQuery<&'static ActionHistory>,
+    ) {
+        let entity = trigger.event().entity;
+        let Ok((mut score, settings)) = target.get_mut(entity) else {
+            // The entity is not penalized for repetition.
+            return;
+        };
+
+        let Ok(history) = histories.get(entity) else {
+            return;
+        };
+
+        let value = score.get() * settings.multiplier(history, elapsed_secs(time.as_deref()));
+        score.set(value);
+    }
+}
+
+impl Component for RepetitionPenalty {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+    type Mutability = bevy::ecs::component::Immutable;
+
+    fn on_add() -> Option<ComponentHook> {
+        Some(|mut world: DeferredWorld, context: HookContext| {
+            #[derive(Resource, Default)]
+            struct RepetitionPenaltyObserverSpawned;
+
+            world.once::<RepetitionPenaltyObserverSpawned>().observe(Self::observer);
+            PostProcessed::insert_for(&mut world, context);
+        })
+    }
+
+    fn on_remove() -> Option<ComponentHook> {
+        Some(|mut world: DeferredWorld, context: HookContext| {
+            PostProcessed::remove_for(&mut world, context);
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use approx::assert_relative_eq;
+    use bevy::prelude::*;
+
+    use crate::{
+        event::{ActionEndReason, OnActionEnded, OnActionInitiated, RequestAction, RunScoring},
+        history::{ActionFrequency, ActionHistory, RepetitionPenalty, TimeSinceAction},
+        picking::Picker,
+        scoring::{FixedScore, PostProcessed, Score},
+    };
+
+    #[derive(Component, Default)]
+    struct Idle;
+
+    #[derive(Component, Default)]
+    struct Dance;
+
+    #[test]
+    fn ring_buffer() {
+        let mut app = App::new();
+        app.add_plugins(crate::ObservedUtilityPlugins::TurnBased);
+        app.init_resource::<Time>();
+        let world = app.world_mut();
+
+        let idle = world.register_component::<Idle>();
+        let dance = world.register_component::<Dance>();
+        let actor = world.spawn((Picker::new(idle), ActionHistory::new(3))).id();
+        world.flush();
+
+        for _ in 0..2 {
+            world.trigger(RequestAction::specific(actor, dance));
+            world.flush();
+            world.resource_mut::<Time>().advance_by(Duration::from_secs(2));
+            world.trigger(RequestAction::specific(actor, idle));
+            world.flush();
+        }
+
+        let history = world.get::<ActionHistory>(actor).unwrap();
+        assert_eq!(history.len(), 3);
+        let records: Vec<_> = history.iter().map(|record| record.action).collect();
+        assert_eq!(records, [idle, dance, idle]);
+
+        let danced = history.last_of(dance).unwrap();
+        assert_eq!(danced.started, 2.);
+        assert_eq!(danced.duration(), Some(2.));
+        assert_eq!(danced.outcome, Some(ActionEndReason::Cancelled));
+        assert!(history.latest().unwrap().is_running());
+    }
+
+    #[test]
+    fn scorers() {
+        let mut app = App::new();
+        app.add_plugins(crate::ObservedUtilityPlugins::TurnBased);
+        app.init_resource::<Time>();
+        let world = app.world_mut();
+
+        let dance = world.register_component::<Dance>();
+        let since = world
+            .spawn((TimeSinceAction::new(dance, 0., 10.), Score::default()))
+            .id();
+        let frequency = world
+            .spawn((ActionFrequency::new(dance, 5., 0., 4.), Score::default()))
+            .id();
+        let penalized = world
+            .spawn((
+                FixedScore::new(1.),
+                RepetitionPenalty::new(dance, 10., 0.4),
+                Score::default(),
+            ))
+            .id();
+        let actor = world
+            .spawn(ActionHistory::default())
+            .add_children(&[since, frequency, penalized])
+            .id();
+
+        let score = |world: &mut World, entity| {
+            world.trigger(RunScoring::entity(entity));
+            world.flush();
+            world.get::<Score>(entity).unwrap().get()
+        };
+
+        // Never danced.
+        assert_eq!(score(world, since), 1.);
+        assert_eq!(score(world, frequency), 0.);
+        assert_eq!(score(world, penalized), 1.);
+
+        for _ in 0..2 {
+            world.trigger(OnActionInitiated {
+                entity: actor,
+                action: dance,
+            });
+            world.trigger(OnActionEnded::completed(actor, dance));
+            world.flush();
+            world.resource_mut::<Time>().advance_by(Duration::from_secs(5));
+        }
+
+        // Danced at 0 and 5 seconds, it's now 10 seconds.
+        assert_relative_eq!(score(world, since), 0.5);
+        assert_relative_eq!(score(world, frequency), 0.25);
+        assert_relative_eq!(score(world, penalized), 1. - 0.4 * 0.5);
+    }
+
+    #[test]
+    fn without_time() {
+        let mut app = App::new();
+        app.add_plugins(crate::ObservedUtilityPlugins::TurnBased);
+        let world = app.world_mut();
+
+        let dance = world.register_component::<Dance>();
+        let since = world
+            .spawn((TimeSinceAction::new(dance, 0., 10.), Score::default()))
+            .id();
+        let actor = world.spawn(ActionHistory::default()).add_child(since).id();
+
+        world.trigger(OnActionInitiated {
+            entity: actor,
+            action: dance,
+        });
+        world.trigger(OnActionEnded::completed(actor, dance));
+        world.trigger(RunScoring::entity(since));
+        world.flush();
+
+        let history = world.get::<ActionHistory>(actor).unwrap();
+        assert_eq!(history.latest().unwrap().duration(), Some(0.));
+        assert_eq!(world.get::<Score>(since).unwrap().get(), 0.);
+    }
+
+    #[test]
+    fn repetition_penalty_empty_window() {
+        let mut world = World::new();
+        let dance = world.register_component::<Dance>();
+
+        let mut history = ActionHistory::default();
+        history.initiated(dance, 5.);
+
+        for window in [0., -1.] {
+            let penalty = RepetitionPenalty::new(dance, window, 0.4);
+            assert_eq!(penalty.multiplier(&history, 5.), 1.);
+        }
+        assert_relative_eq!(
+            RepetitionPenalty::new(dance, 10., 0.4).multiplier(&history, 10.),
+            1. - 0.4 * 0.5
+        );
+    }
+
+    #[test]
+    fn remove_post_processed() {
+        let mut app = App::new();
+        app.add_plugins(crate::ObservedUtilityPlugins::TurnBased);
+        let world = app.world_mut();
+
+        let dance = world.register_component::<Dance>();
+        let entity = world
+            .spawn((
+                FixedScore::new(1.),
+                RepetitionPenalty::new(dance, 10., 0.4),
+                Score::default(),
+            ))
+            .id();
+        world.flush();
+        assert!(world.get::<PostProcessed>(entity).is_some());
+
+        world.entity_mut(entity).remove::<RepetitionPenalty>();
+        world.flush();
+        assert!(world.get::<PostProcessed>(entity).is_none());
+    }
+}
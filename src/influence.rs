@@ -0,0 +1,553 @@
+//! Influence maps are 2D grids that entities stamp influence onto, such as threat, ally presence, or resources.
+//!
+//! Influence spreads to neighboring cells and decays over time, giving scorers a cheap, smoothed view of the world
+//! that can be sampled at the actor's position with [`SampleInfluence`],
+//! or at candidate points of [`TacticalPoints`] with [`InfluenceAtPoint`].
+//!
+//! Each [`InfluenceMap`] is a [`Component`], so that a game can have as many layers as it needs.
+//! Add the [`InfluenceMapPlugin`] to update them.
+//!
+//! # Example
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_observed_utility::prelude::*;
+//! # use approx::assert_relative_eq;
+//!
+//! # let mut app = App::new();
+//! # app.add_plugins((ObservedUtilityPlugins::RealTime, TransformPlugin));
+//! app.add_plugins(InfluenceMapPlugin::default());
+//! # app.init_resource::<Time>();
+//!
+//! # let world = app.world_mut();
+//! // A 32x32 threat map with 1 unit cells, centered on the world origin.
+//! let threat = world
+//!     .spawn(InfluenceMap::new(32, 32, 1.).with_origin(Vec2::splat(-16.)))
+//!     .id();
+//!
+//! // An enemy that stamps threat within 4 units of itself.
+//! world.spawn((InfluenceSource::new(threat, 1., 4.), Transform::from_xyz(2.5, 0., 0.5)));
+//!
+//! // Score how threatened the actor feels.
+//! let scorer = world.spawn((SampleInfluence::new(threat, 0., 1.), Score::default())).id();
+//! world.spawn(Transform::from_xyz(0.5, 0., 0.5)).add_child(scorer);
+//!
+//! # world.flush();
+//! # app.update();
+//! # let world = app.world_mut();
+//! # world.run_system_cached(InfluenceMapPlugin::update_influence_maps).unwrap();
+//! # world.trigger(RunScoring::entity(scorer));
+//! # world.flush();
+//! # assert_relative_eq!(world.get::<Score>(scorer).unwrap().get(), 0.5);
+//! ```
+//!
+//! [`TacticalPoints`]: crate::scoring::TacticalPoints
+
+use bevy::{
+    ecs::{
+        component::StorageType,
+        lifecycle::{ComponentHook, HookContext},
+        schedule::{InternedScheduleLabel, ScheduleLabel},
+        world::DeferredWorld,
+    },
+    prelude::*,
+};
+
+use crate::{
+    ecs::{AncestorQuery, DeferredWorldExt},
+    event::OnScore,
+    scoring::{Evaluator, LinearEvaluator, PointConsideration, PointContext, ReflectPointConsideration, Score},
+};
+
+/// [`Plugin`] that decays, propagates, and stamps all [`InfluenceMap`]s in the configured [`Schedule`].
+pub struct InfluenceMapPlugin {
+    /// The [`ScheduleLabel`] to update influence maps in.
+    pub update_in: InternedScheduleLabel,
+}
+
+impl Default for InfluenceMapPlugin {
+    fn default() -> Self {
+        Self {
+            update_in: FixedUpdate.intern(),
+        }
+    }
+}
+
+impl Plugin for InfluenceMapPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(self.update_in, Self::update_influence_maps);
+
+        app.register_type::<InfluenceMap>()
+            .register_type::<InfluencePlane>()
+            .register_type::<InfluenceSource>()
+            .register_type::<SampleInfluence>()
+            .register_type::<InfluenceAtPoint>();
+    }
+}
+
+impl InfluenceMapPlugin {
+    /// [`System`] that decays and propagates all [`InfluenceMap`]s, then stamps all [`InfluenceSource`]s onto them.
+    pub fn update_influence_maps(
+        time: Res<Time>,
+        mut maps: Query<&mut InfluenceMap>,
+        sources: Query<(&InfluenceSource, &GlobalTransform)>,
+    ) {
+        let delta = time.delta_secs();
+        for mut map in maps.iter_mut() {
+            map.decay(delta);
+            map.propagate(delta);
+        }
+
+        for (source, transform) in sources.iter() {
+            if let Ok(mut map) = maps.get_mut(source.map) {
+                map.stamp(transform.translation(), source.strength, source.radius);
+            }
+        }
+    }
+}
+
+/// The plane of the world an [`InfluenceMap`] lies in.
+#[derive(Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[reflect(PartialEq, Debug, Default)]
+pub enum InfluencePlane {
+    /// The XY plane, for 2D games.
+    XY,
+    /// The XZ plane, for 3D games.
+    #[default]
+    XZ,
+}
+
+impl InfluencePlane {
+    /// Projects a world position onto the plane.
+    #[must_use]
+    pub fn project(self, position: Vec3) -> Vec2 {
+        match self {
+            InfluencePlane::XY => position.xy(),
+            InfluencePlane::XZ => position.xz(),
+        }
+    }
+}
+
+/// [`Component`] for a 2D grid of influence values.
+///
+/// See the [module docs](crate::influence) for more information.
+#[derive(Component, Reflect)]
+#[derive(Clone, PartialEq, Debug)]
+#[reflect(Component, PartialEq, Debug)]
+pub struct InfluenceMap {
+    /// The number of cells along the first axis of the plane.
+    width: u32,
+    /// The number of cells along the second axis of the plane.
+    height: u32,
+    /// The world-space size of a single cell.
+    cell_size: f32,
+    /// The world-space position of the corner of the first cell.
+    origin: Vec2,
+    /// The plane of the world the map lies in.
+    plane: InfluencePlane,
+    /// The fraction of influence lost per second.
+    decay: f32,
+    /// The fraction of the difference to its strongest neighbor that a cell closes per second.
+    propagation: f32,
+    /// The number of seconds per propagation step, in which influence spreads by one cell.
+    propagation_interval: f32,
+    /// The number of seconds accumulated towards the next propagation step.
+    propagation_elapsed: f32,
+    /// The influence values, row by row.
+    values: Vec<f32>,
+}
+
+impl InfluenceMap {
+    /// Creates a new empty [`InfluenceMap`] with the given dimensions, in the [`InfluencePlane::XZ`] plane.
+    ///
+    /// By default, influence decays by half every second,
+    /// and cells close half the difference to their strongest neighbor every second,
+    /// spreading by one cell every quarter second.
+    /// The cell size is clamped to be positive.
+    #[must_use]
+    pub fn new(width: u32, height: u32, cell_size: f32) -> Self {
+        Self {
+            width,
+            height,
+            cell_size: cell_size.max(f32::EPSILON),
+            origin: Vec2::ZERO,
+            plane: InfluencePlane::default(),
+            decay: 0.5,
+            propagation: 0.5,
+            propagation_interval: 0.25,
+            propagation_elapsed: 0.,
+            values: vec![0.; (width * height) as usize],
+        }
+    }
+
+    /// Sets the world-space position of the corner of the first cell.
+    #[must_use]
+    pub fn with_origin(mut self, origin: Vec2) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    /// Sets the plane of the world the map lies in.
+    #[must_use]
+    pub fn with_plane(mut self, plane: InfluencePlane) -> Self {
+        self.plane = plane;
+        self
+    }
+
+    /// Sets the fraction of influence lost per second, clamped to the range `[0, 1]`.
+    #[must_use]
+    pub fn with_decay(mut self, decay: f32) -> Self {
+        self.decay = decay.clamp(0., 1.);
+        self
+    }
+
+    /// Sets the fraction of the difference to its strongest neighbor that a cell closes per second,
+    /// clamped to the range `[0, 1]`.
+    #[must_use]
+    pub fn with_propagation(mut self, propagation: f32) -> Self {
+        self.propagation = propagation.clamp(0., 1.);
+        self
+    }
+
+    /// Sets the number of seconds per propagation step, in which influence spreads by one cell,
+    /// clamped to be positive.
+    #[must_use]
+    pub fn with_propagation_interval(mut self, interval: f32) -> Self {
+        self.propagation_interval = interval.max(f32::EPSILON);
+        self
+    }
+
+    /// Returns the number of cells along each axis of the plane.
+    #[must_use]
+    pub fn size(&self) -> UVec2 {
+        UVec2::new(self.width, self.height)
+    }
+
+    /// Returns the world-space size of a single cell.
+    #[must_use]
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    /// Returns the cell containing the given world position, if it is on the map.
+    #[must_use]
+    pub fn cell_at(&self, position: Vec3) -> Option<UVec2> {
+        let local = (self.plane.project(position) - self.origin) / self.cell_size;
+        if local.x < 0. || local.y < 0. {
+            return None;
+        }
+        let cell = local.floor().as_uvec2();
+        (cell.x < self.width && cell.y < self.height).then_some(cell)
+    }
+
+    /// Returns the world-space center of the given cell, projected onto the plane.
+    #[must_use]
+    pub fn cell_center(&self, cell: UVec2) -> Vec2 {
+        self.origin + (cell.as_vec2() + 0.5) * self.cell_size
+    }
+
+    /// Returns the influence of the given cell, or `0` if it is not on the map.
+    #[must_use]
+    pub fn get(&self, cell: UVec2) -> f32 {
+        self.index(cell).map_or(0., |i| self.values[i])
+    }
+
+    /// Sets the influence of the given cell, if it is on the map.
+    pub fn set(&mut self, cell: UVec2, value: f32) {
+        if let Some(i) = self.index(cell) {
+            self.values[i] = value.max(0.);
+        }
+    }
+
+    /// Returns the influence at the given world position, or `0` if it is not on the map.
+    #[must_use]
+    pub fn sample(&self, position: Vec3) -> f32 {
+        self.cell_at(position).map_or(0., |cell| self.get(cell))
+    }
+
+    /// Stamps influence around the given world position, falling off linearly to zero at the radius.
+    ///
+    /// Cells keep the stronger of their current influence and the stamped influence.
+    pub fn stamp(&mut self, position: Vec3, strength: f32, radius: f32) {
+        let center = self.plane.project(position);
+        let reach = (radius / self.cell_size).ceil() as i64 + 1;
+        let local = ((center - self.origin) / self.cell_size).floor().as_i64vec2();
+
+        for y in (local.y - reach).max(0)..(local.y + reach + 1).min(self.height as i64) {
+            for x in (local.x - reach).max(0)..(local.x + reach + 1).min(self.width as i64) {
+                let cell = UVec2::new(x as u32, y as u32);
+                let distance = self.cell_center(cell).distance(center);
+                if distance > radius {
+                    continue;
+                }
+                let falloff = if radius > 0. { 1. - distance / radius } else { 1. };
+                let i = self.index(cell).unwrap_or_default();
+                self.values[i] = self.values[i].max(strength * falloff);
+            }
+        }
+    }
+
+    /// Decays all influence by the configured fraction per second, over the given number of seconds.
+    pub fn decay(&mut self, seconds: f32) {
+        let factor = (1. - self.decay).powf(seconds);
+        for value in self.values.iter_mut() {
+            *value *= factor;
+        }
+    }
+
+    /// Spreads influence to neighboring cells by the configured fraction per second, over the given number of seconds.
+    ///
+    /// Time is accumulated into fixed propagation steps, each spreading influence by one cell,
+    /// and the remainder carries over to the next call, so the result doesn't depend on the frame rate.
+    pub fn propagate(&mut self, seconds: f32) {
+        if self.propagation <= 0. || seconds <= 0. {
+            return;
+        }
+
+        self.propagation_elapsed += seconds;
+        let rate = 1. - (1. - self.propagation).powf(self.propagation_interval);
+        let mut previous = Vec::new();
+        while self.propagation_elapsed >= self.propagation_interval {
+            self.propagation_elapsed -= self.propagation_interval;
+            previous.clone_from(&self.values);
+            self.propagate_step(&previous, rate);
+        }
+    }
+
+    /// Spreads influence by one cell, closing the given fraction of the difference to the strongest neighbor.
+    fn propagate_step(&mut self, previous: &[f32], rate: f32) {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let strongest = [(-1, 0), (1, 0), (0, -1), (0, 1)]
+                    .into_iter()
+                    .filter_map(|(dx, dy)| {
+                        let neighbor = UVec2::new(x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+                        self.index(neighbor).map(|i| previous[i])
+                    })
+                    .fold(0., f32::max);
+
+                let i = (y * self.width + x) as usize;
+                self.values[i] = previous[i].max(previous[i] + (strongest - previous[i]) * rate);
+            }
+        }
+    }
+
+    /// Clears all influence.
+    pub fn clear(&mut self) {
+        self.values.fill(0.);
+    }
+
+    fn index(&self, cell: UVec2) -> Option<usize> {
+        (cell.x < self.width && cell.y < self.height).then(|| (cell.y * self.width + cell.x) as usize)
+    }
+}
+
+/// [`Component`] for entities that stamp influence onto an [`InfluenceMap`] around their [`GlobalTransform`].
+#[derive(Component, Reflect)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[reflect(Component, PartialEq, Debug)]
+pub struct InfluenceSource {
+    /// The [`InfluenceMap`] entity to stamp onto.
+    pub map: Entity,
+    /// The influence at the source's position.
+    pub strength: f32,
+    /// The distance at which the influence falls off to zero.
+    pub radius: f32,
+}
+
+impl InfluenceSource {
+    /// Creates a new [`InfluenceSource`] for the given [`InfluenceMap`] entity.
+    #[must_use]
+    pub fn new(map: Entity, strength: f32, radius: f32) -> Self {
+        Self { map, strength, radius }
+    }
+}
+
+/// [`Score`] [`Component`] that scores the influence of an [`InfluenceMap`] at the actor's position.
+///
+/// The actor's position is read from the [`GlobalTransform`] of the closest ancestor entity that has one.
+/// The influence is normalized from the `[min, max]` range.
+///
+/// See the [module docs](crate::influence) for an example.
+#[derive(Reflect)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[reflect(Component, PartialEq, Debug)]
+pub struct SampleInfluence {
+    /// The [`InfluenceMap`] entity to sample.
+    map: Entity,
+    /// The influence that scores the minimum.
+    min: f32,
+    /// The influence that scores the maximum.
+    max: f32,
+}
+
+impl SampleInfluence {
+    /// Creates a new [`SampleInfluence`] that normalizes the influence from the `[min, max]` range.
+    #[must_use]
+    pub fn new(map: Entity, min: f32, max: f32) -> Self {
+        Self { map, min, max }
+    }
+
+    /// Returns the [`InfluenceMap`] entity to sample.
+    #[must_use]
+    pub fn map(&self) -> Entity {
+        self.map
+    }
+
+    /// [`Observer`] for [`SampleInfluence`] [`Score`] entities that scores the influence at the actor's position.
+    fn observer(
+        trigger: On<OnScore>,
+        mut target: Query<(&mut Score, &SampleInfluence)>,
+        mut actors: AncestorQuery<&'static GlobalTransform>,
+        maps: Query<&InfluenceMap>,
+    ) {
+        let entity = trigger.event().entity;
+        let Ok((mut actor_score, settings)) = target.get_mut(entity) else {
+            // The entity is not scoring for sampled influence.
+            return;
+        };
+
+        let (Ok(transform), Ok(map)) = (actors.get(entity), maps.get(settings.map)) else {
+            // If there is no actor or map, set the score to the minimum.
+            *actor_score = Score::MIN;
+            return;
+        };
+
+        let value = map.sample(transform.translation());
+        actor_score.set(LinearEvaluator::from_range(settings.min, settings.max).evaluate(value));
+    }
+}
+
+impl Component for SampleInfluence {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+    type Mutability = bevy::ecs::component::Immutable;
+
+    fn on_add() -> Option<ComponentHook> {
+        Some(|mut world: DeferredWorld, _context: HookContext| {
+            #[derive(Resource, Default)]
+            struct SampleInfluenceObserverSpawned;
+
+            world.once::<SampleInfluenceObserverSpawned>().observe(Self::observer);
+        })
+    }
+}
+
+/// [`PointConsideration`] that measures the influence of an [`InfluenceMap`] at the candidate point.
+#[derive(Reflect, Clone, Copy, PartialEq, Debug)]
+#[reflect(PointConsideration, PartialEq, Debug)]
+pub struct InfluenceAtPoint {
+    /// The [`InfluenceMap`] entity to sample.
+    pub map: Entity,
+}
+
+impl InfluenceAtPoint {
+    /// Creates a new [`InfluenceAtPoint`] for the given [`InfluenceMap`] entity.
+    #[must_use]
+    pub fn new(map: Entity) -> Self {
+        Self { map }
+    }
+}
+
+impl PointConsideration for InfluenceAtPoint {
+    fn consider(&self, point: Vec3, context: &PointContext) -> f32 {
+        context
+            .get::<InfluenceMap>(self.map)
+            .map_or(0., |map| map.sample(point))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+    use bevy::prelude::*;
+
+    use crate::{
+        event::RunScoring,
+        influence::{InfluenceAtPoint, InfluenceMap},
+        scoring::{LinearEvaluator, RingGenerator, Score, TacticalPoints, WeightedProduct},
+    };
+
+    #[test]
+    fn stamp_and_sample() {
+        let mut map = InfluenceMap::new(8, 8, 1.);
+        map.stamp(Vec3::new(4.5, 0., 4.5), 1., 2.);
+
+        assert_relative_eq!(1., map.sample(Vec3::new(4.5, 0., 4.5)));
+        assert_relative_eq!(0.5, map.sample(Vec3::new(5.5, 0., 4.5)));
+        assert_relative_eq!(0., map.sample(Vec3::new(7.5, 0., 4.5)));
+        assert_relative_eq!(0., map.sample(Vec3::new(-1., 0., 4.5)));
+    }
+
+    #[test]
+    fn decay_and_propagate() {
+        let mut map = InfluenceMap::new(4, 1, 1.)
+            .with_decay(0.5)
+            .with_propagation(0.5)
+            .with_propagation_interval(1.);
+        map.set(UVec2::new(0, 0), 1.);
+
+        map.decay(1.);
+        map.propagate(1.);
+        assert_relative_eq!(0.5, map.get(UVec2::new(0, 0)));
+        assert_relative_eq!(0.25, map.get(UVec2::new(1, 0)));
+        assert_relative_eq!(0., map.get(UVec2::new(2, 0)));
+
+        map.propagate(1.);
+        assert_relative_eq!(0.125, map.get(UVec2::new(2, 0)));
+    }
+
+    #[test]
+    fn propagate_independent_of_frame_rate() {
+        let mut once = InfluenceMap::new(6, 1, 1.).with_propagation(0.5);
+        once.set(UVec2::new(0, 0), 1.);
+        let mut often = once.clone();
+
+        once.propagate(1.);
+        for _ in 0..16 {
+            often.propagate(1. / 16.);
+        }
+        for x in 1..6 {
+            let cell = UVec2::new(x, 0);
+            assert_relative_eq!(once.get(cell), often.get(cell), epsilon = 1e-6);
+        }
+        // Influence spreads by one cell per quarter second.
+        assert!(once.get(UVec2::new(4, 0)) > 0.);
+        assert_eq!(once.get(UVec2::new(5, 0)), 0.);
+    }
+
+    #[test]
+    fn positive_cell_size() {
+        let map = InfluenceMap::new(4, 4, 0.);
+        assert!(map.cell_size() > 0.);
+        assert_eq!(map.cell_at(Vec3::ZERO), Some(UVec2::ZERO));
+    }
+
+    #[test]
+    fn tactical_points_sample_influence() {
+        let mut app = App::new();
+        app.add_plugins((crate::ObservedUtilityPlugins::TurnBased, TransformPlugin));
+        let world = app.world_mut();
+
+        let mut map = InfluenceMap::new(8, 8, 1.).with_origin(Vec2::splat(-4.));
+        map.stamp(Vec3::new(2.5, 0., 0.5), 1., 1.);
+        let map = world.spawn(map).id();
+
+        let cover = world
+            .spawn((
+                TacticalPoints::new(RingGenerator::new(2., 4), WeightedProduct)
+                    .with(InfluenceAtPoint::new(map), LinearEvaluator::from_range(0., 1.)),
+                Score::default(),
+            ))
+            .id();
+        world.spawn(Transform::from_xyz(0.5, 0., 0.5)).add_child(cover);
+        app.update();
+
+        let world = app.world_mut();
+        world.trigger(RunScoring::entity(cover));
+        world.flush();
+        let best = world.get::<TacticalPoints>(cover).unwrap().best().unwrap();
+        assert_relative_eq!(2.5, best.x, epsilon = 1e-4);
+        assert_relative_eq!(1., world.get::<Score>(cover).unwrap().get(), epsilon = 1e-4);
+    }
+}
@@ -0,0 +1,333 @@
+//! Decision layers re-decide at their own rates, so slow strategic decisions can feed fast tactical ones.
+//!
+//! A picker entity with a [`DecisionLayer`] component is scored and picked on its own schedule,
+//! every `interval` seconds, instead of by [`RunScoring::all`] and [`RunPicking::all`].
+//! Due layers are resolved in ascending `order`, scoring each layer's child [`Score`] entities right before it picks,
+//! so a strategic layer's pick is always up to date before the tactical layers that depend on it are scored.
+//! Pickers without a layer are scored and picked after all layers, by the usual lifecycle.
+//!
+//! The [`CurrentPick`] scorer reads another picker's current pick, turning a strategic goal into a tactical input.
+//!
+//! Add the [`DecisionLayerPlugin`] to run the layers,
+//! or run [`DecisionLayerPlugin::run_layers`] manually in turn-based games.
+//!
+//! # Example
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_observed_utility::prelude::*;
+//!
+//! #[derive(Component)]
+//! struct DefendBase;
+//! #[derive(Component)]
+//! struct Raid;
+//! #[derive(Component)]
+//! struct Idle;
+//! #[derive(Component)]
+//! struct ManTurret;
+//!
+//! # let mut app = App::new();
+//! # app.add_plugins(ObservedUtilityPlugins::RealTime);
+//! app.add_plugins(DecisionLayerPlugin::default());
+//! # app.init_resource::<Time>();
+//!
+//! # let world = app.world_mut();
+//! let defend = world.register_component::<DefendBase>();
+//! let raid = world.register_component::<Raid>();
+//! let idle = world.register_component::<Idle>();
+//! let man_turret = world.register_component::<ManTurret>();
+//!
+//! // The strategy re-decides every 5 seconds.
+//! let threat = world.spawn((FixedScore::new(0.8), Score::default())).id();
+//! let strategy = world
+//!     .spawn((Picker::new(raid).with(threat, defend), FirstToScore::new(0.5), DecisionLayer::new(0, 5.)))
+//!     .add_child(threat)
+//!     .id();
+//!
+//! // The tactics re-decide every tick, manning turrets while the strategy is to defend the base.
+//! let defending = world.spawn((CurrentPick::new(strategy, defend), Score::default())).id();
+//! let tactics = world
+//!     .spawn((Picker::new(idle).with(defending, man_turret), FirstToScore::new(0.5), DecisionLayer::new(1, 0.)))
+//!     .add_child(defending)
+//!     .id();
+//!
+//! world.run_system_cached(DecisionLayerPlugin::run_layers).unwrap();
+//! # world.flush();
+//! assert_eq!(world.get::<Picker>(tactics).unwrap().picked, man_turret);
+//! ```
+//!
+//! [`RunScoring::all`]: crate::event::RunScoring::all
+//! [`RunPicking::all`]: crate::event::RunPicking::all
+
+use bevy::{
+    ecs::{
+        component::{ComponentId, StorageType},
+        lifecycle::{ComponentHook, HookContext},
+        schedule::{InternedScheduleLabel, ScheduleLabel},
+        world::DeferredWorld,
+    },
+    prelude::*,
+};
+
+use crate::{
+    ecs::{DeferredWorldExt, score_and_pick, tick_interval},
+    event::OnScore,
+    picking::{Picker, SelfScheduled},
+    scoring::Score,
+};
+
+/// [`Plugin`] that scores and picks due [`DecisionLayer`]s in the configured [`Schedule`].
+pub struct DecisionLayerPlugin {
+    /// The [`ScheduleLabel`] to run the layers in.
+    ///
+    /// This should run before the [`RealtimeLifecyclePlugin`](crate::RealtimeLifecyclePlugin),
+    /// so that pickers without a layer see the latest picks of the layers.
+    pub update_in: InternedScheduleLabel,
+}
+
+impl Default for DecisionLayerPlugin {
+    fn default() -> Self {
+        Self {
+            update_in: FixedUpdate.intern(),
+        }
+    }
+}
+
+impl Plugin for DecisionLayerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(self.update_in, Self::run_layers);
+
+        app.register_type::<DecisionLayer>().register_type::<CurrentPick>();
+    }
+}
+
+impl DecisionLayerPlugin {
+    /// [`System`] that ticks all [`DecisionLayer`]s by the [`Time`] delta,
+    /// then scores and picks the due layers in ascending order.
+    pub fn run_layers(mut commands: Commands, time: Res<Time>, mut layers: Query<(Entity, &mut DecisionLayer)>) {
+        let mut due: Vec<(i32, Entity)> = layers
+            .iter_mut()
+            .filter_map(|(entity, mut layer)| layer.tick(time.delta_secs()).then_some((layer.order, entity)))
+            .collect();
+        // Stable, so layers of equal order keep their query order.
+        due.sort_by_key(|&(order, _)| order);
+
+        for (_, entity) in due {
+            commands.queue(score_and_pick(entity, false));
+        }
+    }
+}
+
+/// [`Component`] for picker entities that re-decide on their own schedule.
+///
+/// See the [module docs](crate::layer) for more information.
+#[derive(Component, Reflect)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[reflect(Component, PartialEq, Debug)]
+#[require(SelfScheduled)]
+pub struct DecisionLayer {
+    /// The order to resolve the layer in, lower layers first.
+    order: i32,
+    /// The number of seconds between decisions, or `0` to decide every tick.
+    interval: f32,
+    /// The number of seconds since the last decision.
+    elapsed: f32,
+}
+
+impl DecisionLayer {
+    /// Creates a new [`DecisionLayer`] of the given order, deciding every `interval` seconds, starting immediately.
+    #[must_use]
+    pub fn new(order: i32, interval: f32) -> Self {
+        let interval = interval.max(0.);
+        Self {
+            order,
+            interval,
+            elapsed: interval,
+        }
+    }
+
+    /// Returns the order to resolve the layer in, lower layers first.
+    #[must_use]
+    pub fn order(&self) -> i32 {
+        self.order
+    }
+
+    /// Returns the number of seconds between decisions.
+    #[must_use]
+    pub fn interval(&self) -> f32 {
+        self.interval
+    }
+
+    /// Advances the layer by the given number of seconds, returning `true` if it's due to decide.
+    ///
+    /// The time past the interval counts towards the next decision, so the layer keeps its cadence.
+    pub fn tick(&mut self, seconds: f32) -> bool {
+        tick_interval(&mut self.elapsed, self.interval, seconds)
+    }
+
+    /// Makes the layer decide on its next tick, regardless of its interval.
+    pub fn reset(&mut self) {
+        self.elapsed = self.interval;
+    }
+}
+
+/// [`Score`] [`Component`] that scores the maximum if another picker entity's current pick is the given action,
+/// and the minimum otherwise.
+///
+/// See the [module docs](crate::layer) for more information.
+#[derive(Reflect)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[reflect(Component, PartialEq, Debug)]
+pub struct CurrentPick {
+    /// The picker entity to read the pick of.
+    picker: Entity,
+    /// [`ComponentId`] of the action to score for.
+    action: ComponentId,
+}
+
+impl CurrentPick {
+    /// Creates a new [`CurrentPick`] that scores whether the picker entity currently picks the given action.
+    #[must_use]
+    pub fn new(picker: Entity, action: ComponentId) -> Self {
+        Self { picker, action }
+    }
+
+    /// Returns the picker entity to read the pick of.
+    #[must_use]
+    pub fn picker(&self) -> Entity {
+        self.picker
+    }
+
+    /// Returns the [`ComponentId`] of the action to score for.
+    #[must_use]
+    pub fn action(&self) -> ComponentId {
+        self.action
+    }
+
+    /// [`Observer`] for [`CurrentPick`] [`Score`] entities that scores another picker's pick.
+    fn observer(trigger: On<OnScore>, mut target: Query<(&mut Score, &CurrentPick)>, pickers: Query<&Picker>) {
+        let entity = trigger.event().entity;
+        let Ok((mut actor_score, settings)) = target.get_mut(entity) else {
+            // The entity is not scoring for a current pick.
+            return;
+        };
+
+        let picked = pickers
+            .get(settings.picker)
+            .is_ok_and(|picker| picker.picked == settings.action);
+        *actor_score = if picked { Score::MAX } else { Score::MIN };
+    }
+}
+
+impl Component for CurrentPick {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+    type Mutability = bevy::ecs::component::Immutable;
+
+    fn on_add() -> Option<ComponentHook> {
+        Some(|mut world: DeferredWorld, _context: HookContext| {
+            #[derive(Resource, Default)]
+            struct CurrentPickObserverSpawned;
+
+            world.once::<CurrentPickObserverSpawned>().observe(Self::observer);
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::prelude::*;
+
+    use crate::{
+        event::{RunPicking, RunScoring},
+        layer::{CurrentPick, DecisionLayer, DecisionLayerPlugin},
+        picking::{FirstToScore, Picker},
+        scoring::{FixedScore, Score},
+    };
+
+    #[derive(Component)]
+    struct Defend;
+
+    #[derive(Component)]
+    struct Raid;
+
+    #[derive(Component)]
+    struct Idle;
+
+    #[derive(Component)]
+    struct Guard;
+
+    #[test]
+    fn tick_keeps_cadence() {
+        let mut layer = DecisionLayer::new(0, 1.);
+        assert!(layer.tick(0.));
+        assert!(!layer.tick(0.75));
+        assert!(layer.tick(0.75));
+        // The half second past the interval counts towards the next decision.
+        assert!(layer.tick(0.5));
+    }
+
+    #[test]
+    fn strategy_feeds_tactics() {
+        let mut app = App::new();
+        app.add_plugins(crate::ObservedUtilityPlugins::TurnBased);
+        app.init_resource::<Time>();
+        let world = app.world_mut();
+
+        let defend = world.register_component::<Defend>();
+        let raid = world.register_component::<Raid>();
+        let idle = world.register_component::<Idle>();
+        let guard = world.register_component::<Guard>();
+
+        let strategy = world.spawn_empty().id();
+        let defending = world.spawn((CurrentPick::new(strategy, defend), Score::default())).id();
+        let tactics = world
+            .spawn((
+                Picker::new(idle).with(defending, guard),
+                FirstToScore::new(0.5),
+                DecisionLayer::new(1, 0.),
+            ))
+            .add_child(defending)
+            .id();
+
+        // The strategy is resolved before the tactics, even though the tactics come first in query order.
+        let threat = world.spawn((FixedScore::new(0.8), Score::default())).id();
+        world
+            .entity_mut(strategy)
+            .insert((
+                Picker::new(raid).with(threat, defend),
+                FirstToScore::new(0.5),
+                DecisionLayer::new(0, 5.),
+            ))
+            .add_child(threat);
+
+        let run = |world: &mut World, seconds: u64| {
+            world.resource_mut::<Time>().advance_by(Duration::from_secs(seconds));
+            world.run_system_cached(DecisionLayerPlugin::run_layers).unwrap();
+            world.flush();
+        };
+
+        run(world, 0);
+        assert_eq!(world.get::<Picker>(strategy).unwrap().picked, defend);
+        assert_eq!(world.get::<Picker>(tactics).unwrap().picked, guard);
+
+        // The threat is gone, but the strategy doesn't re-decide until its interval has passed.
+        world.entity_mut(threat).insert(FixedScore::new(0.));
+        run(world, 1);
+        assert_eq!(world.get::<Picker>(strategy).unwrap().picked, defend);
+        assert_eq!(world.get::<Picker>(tactics).unwrap().picked, guard);
+
+        run(world, 4);
+        assert_eq!(world.get::<Picker>(strategy).unwrap().picked, raid);
+        assert_eq!(world.get::<Picker>(tactics).unwrap().picked, idle);
+
+        // Layered pickers are skipped by global scoring and picking.
+        world.entity_mut(threat).insert(FixedScore::new(0.8));
+        world.trigger(RunScoring::all());
+        world.trigger(RunPicking::all());
+        world.flush();
+        assert_eq!(world.get::<Score>(threat).unwrap().get(), 0.);
+        assert_eq!(world.get::<Picker>(strategy).unwrap().picked, raid);
+    }
+}
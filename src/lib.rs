@@ -210,21 +210,48 @@
 use bevy::{
     app::PluginGroupBuilder,
     ecs::schedule::{InternedScheduleLabel, ScheduleLabel},
+    platform::time::Instant,
     prelude::*,
 };
 
 use crate::{
     acting::{ActionPlugin, CurrentAction},
+    brain::BrainPlugin,
+    budget::{AiBudget, AiBudgetMetrics, LifecycleMode},
+    control::ControlPlugin,
     event::{RequestAction, RunPicking, RunScoring},
+    history::HistoryPlugin,
+    pause::PausePlugin,
+    personality::PersonalityPlugin,
     picking::{Picker, PickingPlugin},
     scoring::ScoringPlugin,
+    stimulus::StimulusPlugin,
+    think::ThinkRate,
 };
 
 pub mod acting;
+pub mod async_score;
+pub mod blackboard;
+pub mod brain;
+pub mod budget;
+pub mod control;
 pub mod ecs;
 pub mod event;
+pub mod history;
+pub mod influence;
+pub mod layer;
+pub mod modifier;
+pub mod needs;
+pub mod pause;
+pub mod perception;
+pub mod personality;
 pub mod picking;
+pub mod reservation;
+pub mod routine;
 pub mod scoring;
+pub mod smart_object;
+pub mod stimulus;
+pub mod think;
 
 pub mod prelude {
     //! Re-exports important traits and types.
@@ -234,17 +261,51 @@ pub mod prelude {
             CurrentAction, on_action_ended_remove, on_action_initiated_insert_default,
             on_action_initiated_insert_from_resource,
         },
+        async_score::{AsyncScore, AsyncScorePlugin},
+        blackboard::{
+            Blackboard, BlackboardChanged, BlackboardKey, BlackboardPlugin, BlackboardScore, BlackboardWriteWhen,
+            BlackboardWrites,
+        },
+        brain::{BrainPlugin, BrainSwitchPolicy, Brains, SwitchBrain, follow_resource},
+        budget::{AiBudget, AiBudgetMetrics, LifecycleMode},
+        control::{ActionOverrides, ControlLayer, ControlPlugin, PopOverride, PushOverride},
         ecs::AncestorQuery,
         event::{
-            ActionEndReason, OnActionEnded, OnActionInitiated, OnPick, OnPicked, OnScore, RequestAction, RunPicking,
-            RunScoring,
+            ActionEndReason, Interrupt, InvalidateScoreCache, OnActionEnded, OnActionInitiated, OnActionResumed,
+            OnActionSuspended, OnPick, OnPickChanged, OnPicked, OnScore, OnScorePostProcess, RejectAction,
+            RequestAction, RunPicking, RunScoring,
+        },
+        history::{ActionFrequency, ActionHistory, ActionRecord, HistoryPlugin, RepetitionPenalty, TimeSinceAction},
+        influence::{
+            InfluenceAtPoint, InfluenceMap, InfluenceMapPlugin, InfluencePlane, InfluenceSource, SampleInfluence,
+        },
+        layer::{CurrentPick, DecisionLayer, DecisionLayerPlugin},
+        modifier::{ModifierOp, ScoreModifier, ScoreModifierPlugin, ScoreModifiers},
+        needs::{Need, NeedSatisfactions, NeedScore, Needs, NeedsPlugin, Satisfaction},
+        pause::{AiPaused, GlobalAiPause, PausePlugin},
+        perception::{
+            ConeSensor, Memory, Perceivable, PerceptionPlugin, RadiusSensor, Remembered, RememberedCount, Senses,
+            Sensor, TimeSinceSeen,
+        },
+        personality::{Personality, PersonalityPlugin, TraitModulated, TraitModulation},
+        picking::{
+            Blend, BlendWeights, Eligible, FirstToScore, Highest, Ineligible, PendingScore, PickEntry, PickResult,
+            Picker, Reserved, SelfScheduled,
         },
-        picking::{FirstToScore, Highest, Picker},
+        reservation::{ActionCapacity, Reservable, Reservation, ReservationPlugin, ReservesTarget},
+        routine::{GameClock, Routine, RoutinePlugin, RoutineWindow},
         scoring::{
-            AllOrNothing, Evaluated, Evaluator, FixedScore, LinearEvaluator, Measure, Measured, PowerEvaluator,
-            Product, Score, SigmoidEvaluator, Sum, Weighted, WeightedMax, WeightedProduct, WeightedRMS, WeightedSum,
-            Winning, score_ancestor,
+            AllOrNothing, CountInRadius, DistanceToNearest, DistanceToNearestThreat, DistanceToOrigin, Evaluated,
+            Evaluator, FacingTarget, FixedScore, GridGenerator, LinearEvaluator, Measure, Measured, PointConsideration,
+            PointContext, PointGenerator, PostProcessed, PowerEvaluator, Product, RingGenerator, Score, ScoreCache,
+            SigmoidEvaluator, Sum, TacticalPoints, TacticalTarget, TacticalThreat, Weighted, WeightedMax,
+            WeightedProduct, WeightedRMS, WeightedSum, Winning, score_ancestor,
         },
+        smart_object::{
+            AdvertisedChoice, Advertisement, SmartObject, SmartObjectPlugin, SmartObjectSeeker, UsingSmartObject,
+        },
+        stimulus::{ReceivedStimulus, Stimulate, Stimuli, Stimulus, StimulusPlugin, StimulusStacking},
+        think::{ThinkImportance, ThinkPlugin, ThinkRate, ThinkTiers},
     };
 
     #[cfg(feature = "rand")]
@@ -274,7 +335,13 @@ impl PluginGroup for ObservedUtilityPlugins {
         let builder = PluginGroupBuilder::start::<Self>()
             .add(ScoringPlugin)
             .add(PickingPlugin)
-            .add(ActionPlugin);
+            .add(ActionPlugin)
+            .add(PersonalityPlugin)
+            .add(StimulusPlugin)
+            .add(HistoryPlugin)
+            .add(ControlPlugin)
+            .add(PausePlugin::default())
+            .add(BrainPlugin);
         match self {
             ObservedUtilityPlugins::RealTime => builder.add(RealtimeLifecyclePlugin::default()),
             ObservedUtilityPlugins::TurnBased => builder,
@@ -289,14 +356,34 @@ impl PluginGroup for ObservedUtilityPlugins {
 pub struct RealtimeLifecyclePlugin {
     /// The [`ScheduleLabel`] to run scoring and picking, and action selection in.
     pub score_pick_perform_in: InternedScheduleLabel,
+    /// How to schedule scoring and picking, see the [`budget`] module.
+    pub mode: LifecycleMode,
 }
 
 impl Plugin for RealtimeLifecyclePlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            self.score_pick_perform_in,
-            (Self::score_and_pick, Self::request_action_if_none_or_default),
-        );
+        match &self.mode {
+            LifecycleMode::EveryTick => {
+                app.add_systems(
+                    self.score_pick_perform_in,
+                    (Self::score_and_pick, Self::request_action_if_none_or_default),
+                );
+            }
+            LifecycleMode::Budgeted(budget) => {
+                app.insert_resource(budget.clone())
+                    .init_resource::<AiBudgetMetrics>()
+                    .add_observer(AiBudget::on_add_picker)
+                    .add_observer(AiBudget::on_remove_picker)
+                    .add_observer(AiBudget::on_add_self_scheduled)
+                    .add_observer(AiBudget::on_remove_self_scheduled)
+                    .add_systems(
+                        self.score_pick_perform_in,
+                        (Self::score_and_pick_budgeted, Self::request_action_if_none_or_default).chain(),
+                    );
+            }
+        }
+
+        app.register_type::<AiBudget>().register_type::<AiBudgetMetrics>();
     }
 }
 
@@ -304,6 +391,7 @@ impl Default for RealtimeLifecyclePlugin {
     fn default() -> Self {
         Self {
             score_pick_perform_in: FixedPostUpdate.intern(),
+            mode: LifecycleMode::EveryTick,
         }
     }
 }
@@ -315,13 +403,36 @@ impl RealtimeLifecyclePlugin {
         commands.trigger(RunPicking::all());
     }
 
+    /// Exclusive [`System`] that scores and picks the next actors in the [`AiBudget`] queue, within the budget,
+    /// and records the [`AiBudgetMetrics`] of the tick.
+    ///
+    /// Only the [`Score`](crate::scoring::Score) children of queued actors are scored, see the [`budget`] module.
+    pub fn score_and_pick_budgeted(world: &mut World) {
+        let start = Instant::now();
+        let mut processed = 0;
+        while let Some(actor) = world.resource_mut::<AiBudget>().next(processed, start.elapsed()) {
+            ecs::score_and_pick(actor, false).apply(world);
+            world.flush();
+            processed += 1;
+        }
+
+        let metrics = world.resource_mut::<AiBudget>().finish(processed, start.elapsed());
+        world.insert_resource(metrics);
+    }
+
     /// [`System`] that requests a new action for an actor if they're currently "idling",
     /// i.e. performing their default action.
+    ///
+    /// Actors [frozen](crate::think) by their [`ThinkRate`] are skipped.
     pub fn request_action_if_none_or_default(
         mut commands: Commands,
         actors: Query<(Entity, &Picker, Option<&CurrentAction>)>,
+        rates: Query<&ThinkRate>,
     ) {
         for (actor, picker, current_action) in actors.iter() {
+            if rates.get(actor).is_ok_and(ThinkRate::is_frozen) {
+                continue;
+            }
             if current_action.is_some_and(|ca| picker.is_default(ca.0)) || current_action.is_none() {
                 commands.trigger(RequestAction::picked(actor));
             }
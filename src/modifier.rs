@@ -0,0 +1,409 @@
+//! Score modifiers are temporary buffs and debuffs applied to scores, without editing the score tree itself.
+//!
+//! A [`ScoreModifiers`] component holds labeled [`ScoreModifier`]s, each adding to or multiplying a score,
+//! optionally for a limited duration:
+//! - On a [`Score`] entity, every modifier applies to that entity.
+//! - On an actor entity with a [`Picker`], modifiers apply to its child choice [`Score`] entities,
+//!   either to all of them or only to those picking a specific action.
+//!
+//! Modifiers are applied as a post-step of scoring, when [`OnScorePostProcess`] is triggered.
+//! All additions are applied first, then all multiplications, so the order modifiers were added in doesn't matter.
+//! Add the [`ScoreModifierPlugin`] to expire timed modifiers automatically.
+//!
+//! # Example
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_observed_utility::prelude::*;
+//!
+//! #[derive(Component)]
+//! struct Idle;
+//! #[derive(Component)]
+//! struct Flee;
+//!
+//! # let mut app = App::new();
+//! # app.add_plugins(ObservedUtilityPlugins::RealTime);
+//! app.add_plugins(ScoreModifierPlugin::default());
+//!
+//! # let world = app.world_mut();
+//! let idle = world.register_component::<Idle>();
+//! let flee = world.register_component::<Flee>();
+//!
+//! let scorer = world.spawn((FixedScore::new(0.8), Score::default())).id();
+//! world.spawn((
+//!     Picker::new(idle).with(scorer, flee),
+//!     // Halve the urge to flee for 10 seconds while enraged.
+//!     ScoreModifiers::default().with(ScoreModifier::multiply("enraged", 0.5).for_action(flee).lasting(10.)),
+//! ))
+//! .add_child(scorer);
+//!
+//! # world.trigger(RunScoring::entity(scorer));
+//! # world.flush();
+//! # approx::assert_relative_eq!(world.get::<Score>(scorer).unwrap().get(), 0.4);
+//! ```
+
+use std::borrow::Cow;
+
+use bevy::{
+    ecs::{
+        component::{ComponentId, StorageType},
+        lifecycle::{ComponentHook, HookContext},
+        schedule::{InternedScheduleLabel, ScheduleLabel},
+        world::DeferredWorld,
+    },
+    prelude::*,
+};
+
+use crate::{
+    ecs::DeferredWorldExt,
+    event::OnScorePostProcess,
+    picking::Picker,
+    scoring::{PostProcessed, Score},
+};
+
+/// [`Plugin`] that expires timed [`ScoreModifier`]s in the configured [`Schedule`].
+pub struct ScoreModifierPlugin {
+    /// The [`ScheduleLabel`] to expire modifiers in.
+    pub update_in: InternedScheduleLabel,
+}
+
+impl Default for ScoreModifierPlugin {
+    fn default() -> Self {
+        Self {
+            update_in: FixedUpdate.intern(),
+        }
+    }
+}
+
+impl Plugin for ScoreModifierPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(self.update_in, Self::expire_modifiers);
+
+        app.register_type::<ScoreModifiers>()
+            .register_type::<ScoreModifier>()
+            .register_type::<ModifierOp>();
+    }
+}
+
+impl ScoreModifierPlugin {
+    /// [`System`] that counts down the remaining duration of all timed [`ScoreModifier`]s, removing expired ones.
+    pub fn expire_modifiers(time: Res<Time>, mut modifiers: Query<&mut ScoreModifiers>) {
+        let delta = time.delta_secs();
+        for mut modifiers in modifiers.iter_mut() {
+            if modifiers.modifiers.iter().any(|modifier| modifier.remaining.is_some()) {
+                modifiers.tick(delta);
+            }
+        }
+    }
+}
+
+/// How a [`ScoreModifier`] changes a score.
+#[derive(Reflect)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[reflect(PartialEq, Debug)]
+pub enum ModifierOp {
+    /// Adds the value to the score.
+    Add(f32),
+    /// Multiplies the score by the value.
+    Multiply(f32),
+}
+
+/// A labeled, optionally timed buff or debuff held in [`ScoreModifiers`].
+#[derive(Reflect)]
+#[derive(Clone, PartialEq, Debug)]
+#[reflect(PartialEq, Debug)]
+pub struct ScoreModifier {
+    /// A label describing the modifier's source, such as `"enraged"`.
+    pub label: Cow<'static, str>,
+    /// How the modifier changes the score.
+    pub op: ModifierOp,
+    /// The action whose choices the modifier applies to, when held by an actor,
+    /// or [`None`] to apply to all choices.
+    pub action: Option<ComponentId>,
+    /// The remaining duration in seconds, or [`None`] if the modifier doesn't expire.
+    pub remaining: Option<f32>,
+}
+
+impl ScoreModifier {
+    /// Creates a new permanent [`ScoreModifier`] that adds the value to the score.
+    #[must_use]
+    pub fn add(label: impl Into<Cow<'static, str>>, value: f32) -> Self {
+        Self::new(label, ModifierOp::Add(value))
+    }
+
+    /// Creates a new permanent [`ScoreModifier`] that multiplies the score by the value.
+    #[must_use]
+    pub fn multiply(label: impl Into<Cow<'static, str>>, value: f32) -> Self {
+        Self::new(label, ModifierOp::Multiply(value))
+    }
+
+    /// Creates a new permanent [`ScoreModifier`] with the given operation.
+    #[must_use]
+    pub fn new(label: impl Into<Cow<'static, str>>, op: ModifierOp) -> Self {
+        Self {
+            label: label.into(),
+            op,
+            action: None,
+            remaining: None,
+        }
+    }
+
+    /// Applies the modifier only to the choices picking the given action, when held by an actor.
+    #[must_use]
+    pub fn for_action(mut self, action: ComponentId) -> Self {
+        self.action = Some(action);
+        self
+    }
+
+    /// Expires the modifier after the given duration, in seconds.
+    #[must_use]
+    pub fn lasting(mut self, seconds: f32) -> Self {
+        self.remaining = Some(seconds);
+        self
+    }
+
+    /// Returns `true` if the modifier has expired.
+    #[must_use]
+    pub fn is_expired(&self) -> bool {
+        self.remaining.is_some_and(|remaining| remaining <= 0.)
+    }
+}
+
+/// [`Component`] for [`Score`] entities or actor entities holding temporary [`ScoreModifier`]s.
+///
+/// See the [module docs](crate::modifier) for more information.
+#[derive(Reflect)]
+#[derive(Clone, PartialEq, Debug, Default)]
+#[reflect(Component, PartialEq, Debug, Default)]
+pub struct ScoreModifiers {
+    /// The held modifiers, in the order they were added.
+    modifiers: Vec<ScoreModifier>,
+}
+
+impl ScoreModifiers {
+    /// Adds a [`ScoreModifier`].
+    #[must_use]
+    pub fn with(mut self, modifier: ScoreModifier) -> Self {
+        self.insert(modifier);
+        self
+    }
+
+    /// Adds a [`ScoreModifier`], replacing any modifier with the same label and action.
+    pub fn insert(&mut self, modifier: ScoreModifier) {
+        self.modifiers
+            .retain(|held| held.label != modifier.label || held.action != modifier.action);
+        self.modifiers.push(modifier);
+    }
+
+    /// Removes all modifiers with the given label, returning `true` if any were removed.
+    pub fn remove(&mut self, label: &str) -> bool {
+        let len = self.modifiers.len();
+        self.modifiers.retain(|held| held.label != label);
+        self.modifiers.len() != len
+    }
+
+    /// Returns an iterator over all held modifiers, in the order they were added.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &ScoreModifier> {
+        self.modifiers.iter()
+    }
+
+    /// Returns the number of held modifiers.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.modifiers.len()
+    }
+
+    /// Returns `true` if no modifiers are held.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.modifiers.is_empty()
+    }
+
+    /// Counts down the remaining duration of timed modifiers by the given number of seconds, removing expired ones.
+    pub fn tick(&mut self, seconds: f32) {
+        self.modifiers.retain_mut(|modifier| {
+            if let Some(remaining) = &mut modifier.remaining {
+                *remaining -= seconds;
+            }
+            !modifier.is_expired()
+        });
+    }
+
+    /// Applies the modifiers matching the action to the value, all additions first, then all multiplications.
+    ///
+    /// If `action` is [`None`], all modifiers are applied.
+    #[must_use]
+    pub fn apply(&self, value: f32, action: Option<ComponentId>) -> f32 {
+        let matching = || {
+            self.modifiers.iter().filter(move |modifier| {
+                !modifier.is_expired() && (action.is_none() || modifier.action.is_none() || modifier.action == action)
+            })
+        };
+        let added: f32 = matching()
+            .filter_map(|modifier| match modifier.op {
+                ModifierOp::Add(value) => Some(value),
+                ModifierOp::Multiply(_) => None,
+            })
+            .sum();
+        let multiplied: f32 = matching()
+            .filter_map(|modifier| match modifier.op {
+                ModifierOp::Multiply(value) => Some(value),
+                ModifierOp::Add(_) => None,
+            })
+            .product();
+        (value + added) * multiplied
+    }
+
+    /// [`Observer`] that applies the [`ScoreModifiers`] of a [`Score`] entity and of its parent actor to its calculated score.
+    fn observer(
+        trigger: On<OnScorePostProcess>,
+        mut scores: Query<(&mut Score, Option<&ScoreModifiers>, Option<&ChildOf>)>,
+        actors: Query<(&Picker, &ScoreModifiers)>,
+    ) {
+        let entity = trigger.event().entity;
+        let Ok((mut score, own, parent)) = scores.get_mut(entity) else {
+            return;
+        };
+
+        let actor = parent.and_then(|parent| actors.get(parent.parent()).ok());
+        if own.is_none() && actor.is_none() {
+            // The entity is not modified.
+            return;
+        }
+
+        let mut value = score.get();
+        if let Some(own) = own {
+            value = own.apply(value, None);
+        }
+        // Actor modifiers only apply to the actor's choices.
+        if let Some((picker, modifiers)) = actor
+            && let Some(&action) = picker.choices.get(&entity)
+        {
+            value = modifiers.apply(value, Some(action));
+        }
+        score.set(value);
+    }
+}
+
+impl Component for ScoreModifiers {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+    type Mutability = bevy::ecs::component::Mutable;
+
+    fn on_add() -> Option<ComponentHook> {
+        Some(|mut world: DeferredWorld, context: HookContext| {
+            #[derive(Resource, Default)]
+            struct ScoreModifiersObserverSpawned;
+
+            world.once::<ScoreModifiersObserverSpawned>().observe(Self::observer);
+            PostProcessed::insert_for(&mut world, context);
+        })
+    }
+
+    fn on_remove() -> Option<ComponentHook> {
+        Some(|mut world: DeferredWorld, context: HookContext| {
+            PostProcessed::remove_for(&mut world, context);
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use approx::assert_relative_eq;
+    use bevy::prelude::*;
+
+    use crate::{
+        event::RunScoring,
+        modifier::{ScoreModifier, ScoreModifierPlugin, ScoreModifiers},
+        personality::{TraitModulated, TraitModulation},
+        picking::Picker,
+        scoring::{FixedScore, PostProcessed, Score},
+    };
+
+    #[derive(Component)]
+    struct Idle;
+
+    #[derive(Component)]
+    struct Flee;
+
+    #[derive(Component)]
+    struct Heal;
+
+    #[test]
+    fn stack_and_expire() {
+        let mut app = App::new();
+        app.add_plugins(crate::ObservedUtilityPlugins::TurnBased);
+        app.init_resource::<Time>();
+        let world = app.world_mut();
+
+        let idle = world.register_component::<Idle>();
+        let flee = world.register_component::<Flee>();
+        let heal = world.register_component::<Heal>();
+
+        let flee_score = world.spawn((FixedScore::new(0.6), Score::default())).id();
+        let heal_score = world
+            .spawn((
+                FixedScore::new(0.3),
+                ScoreModifiers::default().with(ScoreModifier::add("potion nearby", 0.2)),
+                Score::default(),
+            ))
+            .id();
+        let actor = world
+            .spawn((
+                Picker::new(idle).with(flee_score, flee).with(heal_score, heal),
+                ScoreModifiers::default()
+                    .with(ScoreModifier::multiply("enraged", 0.5).for_action(flee).lasting(10.))
+                    .with(ScoreModifier::multiply("tired", 0.5).lasting(2.))
+                    .with(ScoreModifier::add("wounded", 0.2).for_action(heal)),
+            ))
+            .add_children(&[flee_score, heal_score])
+            .id();
+
+        let score = |world: &mut World, entity| {
+            world.trigger(RunScoring::entity(entity));
+            world.flush();
+            world.get::<Score>(entity).unwrap().get()
+        };
+
+        assert_relative_eq!(score(world, flee_score), 0.6 * 0.5 * 0.5);
+        // Own modifiers are applied before the actor's, additions before multiplications.
+        assert_relative_eq!(score(world, heal_score), (0.3 + 0.2 + 0.2) * 0.5);
+
+        world.resource_mut::<Time>().advance_by(Duration::from_secs(5));
+        world.run_system_cached(ScoreModifierPlugin::expire_modifiers).unwrap();
+        assert_eq!(world.get::<ScoreModifiers>(actor).unwrap().len(), 2);
+        assert_relative_eq!(score(world, flee_score), 0.3);
+        assert_relative_eq!(score(world, heal_score), 0.7);
+
+        world.resource_mut::<Time>().advance_by(Duration::from_secs(5));
+        world.run_system_cached(ScoreModifierPlugin::expire_modifiers).unwrap();
+        assert_relative_eq!(score(world, flee_score), 0.6);
+    }
+
+    #[test]
+    fn remove_post_processed() {
+        let mut app = App::new();
+        app.add_plugins(crate::ObservedUtilityPlugins::TurnBased);
+        let world = app.world_mut();
+
+        let entity = world
+            .spawn((
+                FixedScore::new(0.5),
+                ScoreModifiers::default(),
+                TraitModulated::new("bravery", TraitModulation::Scale { min: 1., max: 0. }),
+                Score::default(),
+            ))
+            .id();
+        world.flush();
+
+        // The marker stays while another post-processing component needs it.
+        world.entity_mut(entity).remove::<ScoreModifiers>();
+        world.flush();
+        assert!(world.get::<PostProcessed>(entity).is_some());
+
+        world.entity_mut(entity).remove::<TraitModulated>();
+        world.flush();
+        assert!(world.get::<PostProcessed>(entity).is_none());
+    }
+}
@@ -0,0 +1,476 @@
+//! Needs, or motives, are named values like hunger or thirst that grow over time and are satisfied by actions.
+//!
+//! An actor's [`Needs`] component maps need names to a [`Need`], each with its own value, growth rate, and cap.
+//! Needs are scored with [`NeedScore`], which maps the need's fullness through an optional curve.
+//!
+//! Actions declare how they satisfy needs over time in the [`NeedSatisfactions`] resource.
+//! While an actor performs such an action, its needs are lowered, and the action is completed
+//! once all needs with a target are satisfied.
+//! Actions advertised by a [`SmartObject`] also satisfy the needs listed in their [`Advertisement`].
+//!
+//! Add the [`NeedsPlugin`] to update needs.
+//!
+//! # Example
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_observed_utility::prelude::*;
+//!
+//! #[derive(Component)]
+//! struct Idle;
+//!
+//! #[derive(Component)]
+//! struct Drink;
+//!
+//! # let mut app = App::new();
+//! # app.add_plugins(ObservedUtilityPlugins::RealTime);
+//! app.add_plugins(NeedsPlugin::default());
+//!
+//! # let world = app.world_mut();
+//! let idle = world.register_component::<Idle>();
+//! let drink = world.register_component::<Drink>();
+//!
+//! // Drinking lowers thirst by 8 per second, until it's down to 10.
+//! world
+//!     .resource_mut::<NeedSatisfactions>()
+//!     .insert(drink, Satisfaction::new("thirst", 8.).until(10.));
+//!
+//! // Get thirstier the higher the need, squared.
+//! let thirsty = world
+//!     .spawn((NeedScore::new("thirst").with_curve(PowerEvaluator::from_power(2.)), Score::default()))
+//!     .id();
+//!
+//! let actor = world
+//!     .spawn((
+//!         Needs::default().with("thirst", Need::new(4.).with_value(50.)),
+//!         Picker::new(idle).with(thirsty, drink),
+//!         FirstToScore::new(0.2),
+//!     ))
+//!     .add_child(thirsty)
+//!     .id();
+//! # world.trigger(RunScoring::entity(thirsty));
+//! # world.flush();
+//! # assert_eq!(world.get::<Score>(thirsty).unwrap().get(), 0.25);
+//! ```
+//!
+//! [`SmartObject`]: crate::smart_object::SmartObject
+//! [`Advertisement`]: crate::smart_object::Advertisement
+
+use std::borrow::Cow;
+
+use bevy::{
+    ecs::{
+        component::{ComponentId, StorageType},
+        lifecycle::{ComponentHook, HookContext},
+        schedule::{InternedScheduleLabel, ScheduleLabel},
+        world::DeferredWorld,
+    },
+    platform::collections::HashMap,
+    prelude::*,
+};
+
+use crate::{
+    acting::CurrentAction,
+    ecs::{AncestorQuery, DeferredWorldExt},
+    event::{OnActionEnded, OnScore},
+    scoring::{Evaluator, Score},
+    smart_object::{SmartObject, UsingSmartObject},
+};
+
+/// [`Plugin`] that grows all [`Needs`] and satisfies them with the actions being performed in the configured [`Schedule`].
+pub struct NeedsPlugin {
+    /// The [`ScheduleLabel`] to update needs in.
+    pub update_in: InternedScheduleLabel,
+}
+
+impl Default for NeedsPlugin {
+    fn default() -> Self {
+        Self {
+            update_in: FixedUpdate.intern(),
+        }
+    }
+}
+
+impl Plugin for NeedsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<NeedSatisfactions>();
+
+        app.add_systems(self.update_in, (Self::grow_needs, Self::satisfy_needs).chain());
+
+        app.register_type::<Needs>()
+            .register_type::<Need>()
+            .register_type::<NeedSatisfactions>()
+            .register_type::<Satisfaction>();
+
+        // Note: NeedScore cannot be reflected due to the boxed Evaluator trait object
+    }
+}
+
+impl NeedsPlugin {
+    /// [`System`] that grows all [`Needs`] by their rates.
+    pub fn grow_needs(time: Res<Time>, mut actors: Query<&mut Needs>) {
+        let delta = time.delta_secs();
+        for mut needs in actors.iter_mut() {
+            for need in needs.needs.values_mut() {
+                need.add(need.rate * delta);
+            }
+        }
+    }
+
+    /// [`System`] that satisfies the [`Needs`] of actors by the [`NeedSatisfactions`] of their [`CurrentAction`],
+    /// and by the [`Advertisement`](crate::smart_object::Advertisement) of the [`SmartObject`] they're using.
+    ///
+    /// Completes the action once all of its [`Satisfaction`]s with a target are met,
+    /// removing the [`CurrentAction`] so that it's completed only once.
+    pub fn satisfy_needs(
+        mut commands: Commands,
+        time: Res<Time>,
+        satisfactions: Res<NeedSatisfactions>,
+        mut actors: Query<(Entity, &mut Needs, &CurrentAction, Option<&UsingSmartObject>)>,
+        objects: Query<&SmartObject>,
+    ) {
+        let delta = time.delta_secs();
+        for (actor, mut needs, &CurrentAction(action), using) in actors.iter_mut() {
+            let mut has_target = false;
+            let mut satisfied = true;
+            for satisfaction in satisfactions.get(action) {
+                let Some(need) = needs.get_mut(&satisfaction.need) else {
+                    continue;
+                };
+                need.add(-satisfaction.per_second * delta);
+                if let Some(until) = satisfaction.until {
+                    has_target = true;
+                    satisfied &= need.value <= until;
+                }
+            }
+
+            let advertised = using
+                .filter(|using| using.action == action)
+                .and_then(|using| objects.get(using.object).ok())
+                .into_iter()
+                .flat_map(|object| object.advertisements.iter())
+                .filter(|advertisement| advertisement.action == action);
+            for advertisement in advertised {
+                for (name, per_second) in &advertisement.satisfies {
+                    if let Some(need) = needs.get_mut(name) {
+                        need.add(-per_second * delta);
+                    }
+                }
+            }
+
+            if has_target && satisfied {
+                commands.entity(actor).remove::<CurrentAction>();
+                commands.trigger(OnActionEnded::completed(actor, action));
+            }
+        }
+    }
+}
+
+/// A single need, like hunger or thirst, that grows over time.
+#[derive(Reflect)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[reflect(PartialEq, Debug)]
+pub struct Need {
+    /// The current value of the need, from `0` to `max`.
+    pub value: f32,
+    /// How much the value grows per second. Negative rates decay the need instead.
+    pub rate: f32,
+    /// The cap of the value.
+    pub max: f32,
+}
+
+impl Need {
+    /// Creates a new [`Need`] that starts at `0`, grows by the given rate per second, and is capped at `100`.
+    #[must_use]
+    pub fn new(rate: f32) -> Self {
+        Self {
+            value: 0.,
+            rate,
+            max: 100.,
+        }
+    }
+
+    /// Sets the current value of the need.
+    #[must_use]
+    pub fn with_value(mut self, value: f32) -> Self {
+        self.value = value.clamp(0., self.max);
+        self
+    }
+
+    /// Sets the cap of the value.
+    #[must_use]
+    pub fn with_max(mut self, max: f32) -> Self {
+        self.max = max;
+        self.value = self.value.clamp(0., max);
+        self
+    }
+
+    /// Adds to the value of the need, keeping it within `[0, max]`.
+    pub fn add(&mut self, amount: f32) {
+        self.value = (self.value + amount).clamp(0., self.max);
+    }
+
+    /// Returns how full the need is, from `0` to `1`.
+    #[must_use]
+    pub fn fullness(&self) -> f32 {
+        if self.max > 0. { self.value / self.max } else { 0. }
+    }
+}
+
+/// [`Component`] for actor entities that have named [`Need`]s.
+///
+/// See the [module docs](crate::needs) for more information.
+#[derive(Component, Reflect)]
+#[derive(Clone, PartialEq, Debug, Default)]
+#[reflect(Component, PartialEq, Debug, Default)]
+pub struct Needs {
+    /// Map of need names to needs.
+    needs: HashMap<Cow<'static, str>, Need>,
+}
+
+impl Needs {
+    /// Adds a named [`Need`].
+    #[must_use]
+    pub fn with(mut self, name: impl Into<Cow<'static, str>>, need: Need) -> Self {
+        self.insert(name, need);
+        self
+    }
+
+    /// Inserts a named [`Need`], returning the previous need with that name, if any.
+    pub fn insert(&mut self, name: impl Into<Cow<'static, str>>, need: Need) -> Option<Need> {
+        self.needs.insert(name.into(), need)
+    }
+
+    /// Returns the named [`Need`], if any.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&Need> {
+        self.needs.get(name)
+    }
+
+    /// Returns the named [`Need`] mutably, if any.
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Need> {
+        self.needs.get_mut(name)
+    }
+
+    /// Returns an iterator over all named needs.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Need)> {
+        self.needs.iter().map(|(name, need)| (name.as_ref(), need))
+    }
+}
+
+/// How an action satisfies a [`Need`] over time.
+#[derive(Reflect)]
+#[derive(Clone, PartialEq, Debug)]
+#[reflect(PartialEq, Debug)]
+pub struct Satisfaction {
+    /// The name of the need to satisfy.
+    pub need: Cow<'static, str>,
+    /// How much the need is lowered per second.
+    pub per_second: f32,
+    /// The value at or below which the need is satisfied, completing the action, if any.
+    pub until: Option<f32>,
+}
+
+impl Satisfaction {
+    /// Creates a new [`Satisfaction`] that lowers the named need by the given amount per second.
+    #[must_use]
+    pub fn new(need: impl Into<Cow<'static, str>>, per_second: f32) -> Self {
+        Self {
+            need: need.into(),
+            per_second,
+            until: None,
+        }
+    }
+
+    /// Completes the action once the need is at or below the given value.
+    #[must_use]
+    pub fn until(mut self, value: f32) -> Self {
+        self.until = Some(value);
+        self
+    }
+}
+
+/// [`Resource`] declaring how actions satisfy [`Needs`] while they're performed.
+#[derive(Resource, Reflect)]
+#[derive(Clone, PartialEq, Debug, Default)]
+#[reflect(Resource, PartialEq, Debug, Default)]
+pub struct NeedSatisfactions {
+    /// Map of action [`ComponentId`]s to their satisfactions.
+    actions: HashMap<ComponentId, Vec<Satisfaction>>,
+}
+
+impl NeedSatisfactions {
+    /// Declares that the action satisfies a need while it's performed.
+    pub fn insert(&mut self, action: ComponentId, satisfaction: Satisfaction) {
+        self.actions.entry(action).or_default().push(satisfaction);
+    }
+
+    /// Returns the satisfactions of the action.
+    #[must_use]
+    pub fn get(&self, action: ComponentId) -> &[Satisfaction] {
+        self.actions.get(&action).map_or(&[], Vec::as_slice)
+    }
+}
+
+/// [`Score`] [`Component`] that scores how full a [`Need`] is, optionally mapped through an [`Evaluator`] curve.
+///
+/// The need is read from the [`Needs`] of the closest ancestor entity that has one.
+/// If the need doesn't exist, the score is the minimum.
+pub struct NeedScore {
+    /// The name of the need to score.
+    need: Cow<'static, str>,
+    /// The curve to map the need's fullness through, if any.
+    curve: Option<Box<dyn Evaluator>>,
+}
+
+impl NeedScore {
+    /// Creates a new [`NeedScore`] for the named need, scoring its fullness as-is.
+    #[must_use]
+    pub fn new(need: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            need: need.into(),
+            curve: None,
+        }
+    }
+
+    /// Maps the need's fullness through the given [`Evaluator`] curve.
+    #[must_use]
+    pub fn with_curve(mut self, curve: impl Evaluator) -> Self {
+        self.curve = Some(Box::new(curve));
+        self
+    }
+
+    /// Returns the name of the need to score.
+    #[must_use]
+    pub fn need(&self) -> &str {
+        &self.need
+    }
+
+    /// [`Observer`] for [`NeedScore`] [`Score`] entities that scores the need's fullness.
+    fn observer(
+        trigger: On<OnScore>,
+        mut target: Query<(&mut Score, &NeedScore)>,
+        mut actors: AncestorQuery<&'static Needs>,
+    ) {
+        let entity = trigger.event().entity;
+        let Ok((mut actor_score, settings)) = target.get_mut(entity) else {
+            // The entity is not scoring for a need.
+            return;
+        };
+
+        let Some(fullness) = actors
+            .get(entity)
+            .ok()
+            .and_then(|needs| needs.get(&settings.need))
+            .map(Need::fullness)
+        else {
+            // If there is no need, set the score to the minimum.
+            *actor_score = Score::MIN;
+            return;
+        };
+
+        let value = settings
+            .curve
+            .as_ref()
+            .map_or(fullness, |curve| curve.evaluate(fullness));
+        *actor_score = Score::new(value);
+    }
+}
+
+impl Component for NeedScore {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+    type Mutability = bevy::ecs::component::Immutable;
+
+    fn on_add() -> Option<ComponentHook> {
+        Some(|mut world: DeferredWorld, _context: HookContext| {
+            #[derive(Resource, Default)]
+            struct NeedScoreObserverSpawned;
+
+            world.once::<NeedScoreObserverSpawned>().observe(Self::observer);
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use approx::assert_relative_eq;
+    use bevy::prelude::*;
+
+    use crate::{
+        acting::CurrentAction,
+        event::OnActionEnded,
+        needs::{Need, NeedSatisfactions, Needs, NeedsPlugin, Satisfaction},
+        smart_object::{Advertisement, SmartObject, UsingSmartObject},
+    };
+
+    #[derive(Component)]
+    struct Eat;
+
+    #[derive(Resource, Default)]
+    struct Completed(Vec<Entity>);
+
+    #[test]
+    fn grow_and_satisfy() {
+        let mut app = App::new();
+        app.add_plugins(NeedsPlugin::default());
+        app.init_resource::<Time>().init_resource::<Completed>();
+        app.add_observer(|trigger: On<OnActionEnded>, mut completed: ResMut<Completed>| {
+            completed.0.push(trigger.event().entity);
+        });
+        let world = app.world_mut();
+
+        let eat = world.register_component::<Eat>();
+        world
+            .resource_mut::<NeedSatisfactions>()
+            .insert(eat, Satisfaction::new("hunger", 12.).until(20.));
+
+        let actor = world
+            .spawn(
+                Needs::default()
+                    .with("hunger", Need::new(2.).with_value(35.))
+                    .with("energy", Need::new(-5.).with_value(3.)),
+            )
+            .id();
+
+        let update = |world: &mut World| {
+            world.resource_mut::<Time>().advance_by(Duration::from_secs(1));
+            world.run_system_cached(NeedsPlugin::grow_needs).unwrap();
+            world.run_system_cached(NeedsPlugin::satisfy_needs).unwrap();
+            world.flush();
+        };
+
+        update(world);
+        let needs = world.get::<Needs>(actor).unwrap();
+        assert_relative_eq!(needs.get("hunger").unwrap().value, 37.);
+        // Needs never drop below zero.
+        assert_relative_eq!(needs.get("energy").unwrap().value, 0.);
+
+        world.entity_mut(actor).insert(CurrentAction(eat));
+        update(world);
+        assert_relative_eq!(world.get::<Needs>(actor).unwrap().get("hunger").unwrap().value, 27.);
+        assert!(world.resource::<Completed>().0.is_empty());
+
+        update(world);
+        assert_relative_eq!(world.get::<Needs>(actor).unwrap().get("hunger").unwrap().value, 17.);
+        assert_eq!(world.resource::<Completed>().0, vec![actor]);
+        assert!(world.get::<CurrentAction>(actor).is_none());
+
+        // The action is only completed once.
+        update(world);
+        assert_eq!(world.resource::<Completed>().0, vec![actor]);
+
+        // Smart objects satisfy the needs they advertise.
+        world.entity_mut(actor).insert(CurrentAction(eat));
+        let fridge = world
+            .spawn(SmartObject::new(1.).with(Advertisement::new(eat, 1.).satisfies("hunger", 7.)))
+            .id();
+        world.entity_mut(actor).insert(UsingSmartObject {
+            object: fridge,
+            action: eat,
+        });
+        update(world);
+        assert_relative_eq!(world.get::<Needs>(actor).unwrap().get("hunger").unwrap().value, 2.);
+    }
+}
@@ -0,0 +1,477 @@
+//! Pausing suspends the AI of an actor, or of all actors, without removing its [`Picker`].
+//!
+//! - Insert the [`AiPaused`] component on an actor entity to pause its AI, and remove it to resume.
+//! - Use the [`GlobalAiPause`] resource to pause the AI of all actors at once.
+//!
+//! While paused, the actor's score trees aren't scored, its [`Picker`] doesn't pick, and action requests are ignored.
+//!
+//! Pausing can optionally suspend the current action, triggering [`OnActionSuspended`] so action observers can stop it.
+//! Resuming restores the exact previous [`CurrentAction`] and triggers [`OnActionResumed`],
+//! without going through a fresh [`OnActionInitiated`].
+//!
+//! # Example
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_observed_utility::prelude::*;
+//!
+//! #[derive(Component)]
+//! struct Idle;
+//! #[derive(Component)]
+//! struct Patrol;
+//!
+//! # let mut app = App::new();
+//! # app.add_plugins(ObservedUtilityPlugins::RealTime);
+//! # let world = app.world_mut();
+//! let idle = world.register_component::<Idle>();
+//! let patrol = world.register_component::<Patrol>();
+//!
+//! let actor = world.spawn((Picker::new(idle), CurrentAction(patrol))).id();
+//!
+//! // Open a dialogue, freezing the patrol in place.
+//! world.entity_mut(actor).insert(AiPaused::suspending());
+//! # world.flush();
+//!
+//! // Requests are ignored while paused.
+//! world.trigger(RequestAction::specific(actor, idle));
+//! # world.flush();
+//!
+//! // Close the dialogue, resuming the patrol.
+//! world.entity_mut(actor).remove::<AiPaused>();
+//! # world.flush();
+//! assert_eq!(world.get::<CurrentAction>(actor).unwrap().0, patrol);
+//! ```
+//!
+//! [`Picker`]: crate::picking::Picker
+//! [`OnActionInitiated`]: crate::event::OnActionInitiated
+
+use bevy::{
+    ecs::{
+        component::{ComponentId, StorageType},
+        entity::{Entities, EntityHashMap},
+        lifecycle::{ComponentHook, HookContext},
+        schedule::{InternedScheduleLabel, ScheduleLabel},
+        world::DeferredWorld,
+    },
+    prelude::*,
+};
+
+use crate::{
+    acting::CurrentAction,
+    event::{OnActionResumed, OnActionSuspended},
+};
+
+/// [`Plugin`] that suspends and resumes current actions when the [`GlobalAiPause`] changes, in the configured [`Schedule`].
+///
+/// This plugin is included in [`ObservedUtilityPlugins`](crate::ObservedUtilityPlugins).
+pub struct PausePlugin {
+    /// The [`ScheduleLabel`] to apply the [`GlobalAiPause`] in.
+    pub update_in: InternedScheduleLabel,
+}
+
+impl Default for PausePlugin {
+    fn default() -> Self {
+        Self {
+            update_in: PreUpdate.intern(),
+        }
+    }
+}
+
+impl Plugin for PausePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GlobalAiPause>()
+            .init_resource::<ReplacedAiPauses>()
+            .add_systems(
+                self.update_in,
+                GlobalAiPause::apply.run_if(resource_changed::<GlobalAiPause>),
+            );
+
+        app.register_type::<AiPaused>().register_type::<GlobalAiPause>();
+
+        app.register_type::<OnActionSuspended>()
+            .register_type::<OnActionResumed>();
+    }
+}
+
+/// [`Component`] for actor entities whose AI is paused.
+///
+/// See the [module docs](crate::pause) for more information.
+#[derive(Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[reflect(Component, PartialEq, Debug, Default)]
+pub struct AiPaused {
+    /// Whether to suspend the current action while paused.
+    suspend_action: bool,
+    /// The action suspended by this pause, if any.
+    suspended: Option<ComponentId>,
+}
+
+impl AiPaused {
+    /// Creates a new [`AiPaused`] that lets the current action keep running.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new [`AiPaused`] that suspends the current action until resumed.
+    #[must_use]
+    pub fn suspending() -> Self {
+        Self {
+            suspend_action: true,
+            suspended: None,
+        }
+    }
+
+    /// Returns `true` if the current action is suspended while paused.
+    #[must_use]
+    pub fn suspends_action(&self) -> bool {
+        self.suspend_action
+    }
+
+    /// Returns the action suspended by this pause, if any.
+    #[must_use]
+    pub fn suspended(&self) -> Option<ComponentId> {
+        self.suspended
+    }
+}
+
+impl Component for AiPaused {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+    type Mutability = bevy::ecs::component::Mutable;
+
+    fn on_insert() -> Option<ComponentHook> {
+        Some(|mut world: DeferredWorld, context: HookContext| {
+            let actor = context.entity;
+            if let Some(action) = world
+                .get_resource_mut::<ReplacedAiPauses>()
+                .and_then(|mut replaced| replaced.0.remove(&actor))
+            {
+                // The actor was already paused, keep the action suspended by the replaced pause.
+                if let Some(mut paused) = world.get_mut::<AiPaused>(actor) {
+                    paused.suspended = Some(action);
+                }
+                return;
+            }
+            let Some(&paused) = world.get::<AiPaused>(actor) else {
+                return;
+            };
+            if !paused.suspend_action || paused.suspended.is_some() {
+                return;
+            }
+            let Some(&CurrentAction(action)) = world.get::<CurrentAction>(actor) else {
+                // There's nothing to suspend.
+                return;
+            };
+            if world
+                .get_resource::<GlobalAiPause>()
+                .is_some_and(|global| global.suspended.contains_key(&actor))
+            {
+                // The action is already suspended by the global pause.
+                return;
+            }
+
+            if let Some(mut paused) = world.get_mut::<AiPaused>(actor) {
+                paused.suspended = Some(action);
+            }
+            world.commands().trigger(OnActionSuspended { entity: actor, action });
+        })
+    }
+
+    fn on_replace() -> Option<ComponentHook> {
+        Some(|mut world: DeferredWorld, context: HookContext| {
+            let actor = context.entity;
+            let Some(action) = world.get::<AiPaused>(actor).and_then(|paused| paused.suspended) else {
+                return;
+            };
+            // Carry the suspended action over in case the pause is being replaced rather than removed.
+            if let Some(mut replaced) = world.get_resource_mut::<ReplacedAiPauses>() {
+                replaced.0.insert(actor, action);
+            }
+        })
+    }
+
+    fn on_despawn() -> Option<ComponentHook> {
+        Some(|mut world: DeferredWorld, context: HookContext| {
+            // Runs before `on_remove`, so the suspended action is neither resumed nor handed over to the global pause.
+            let actor = context.entity;
+            if let Some(mut paused) = world.get_mut::<AiPaused>(actor) {
+                paused.suspended = None;
+            }
+        })
+    }
+
+    fn on_remove() -> Option<ComponentHook> {
+        Some(|mut world: DeferredWorld, context: HookContext| {
+            let actor = context.entity;
+            if let Some(mut replaced) = world.get_resource_mut::<ReplacedAiPauses>() {
+                replaced.0.remove(&actor);
+            }
+            let Some(action) = world.get::<AiPaused>(actor).and_then(|paused| paused.suspended) else {
+                return;
+            };
+
+            if world
+                .get_resource::<GlobalAiPause>()
+                .is_some_and(|global| global.is_paused() && global.suspends_actions())
+            {
+                // Hand the suspended action over to the global pause.
+                if let Some(mut global) = world.get_resource_mut::<GlobalAiPause>() {
+                    global.bypass_change_detection().suspended.insert(actor, action);
+                }
+                return;
+            }
+
+            world.commands().queue(resume_action(actor, action));
+        })
+    }
+}
+
+/// Returns a [`Command`] that restores the suspended action of the actor and triggers [`OnActionResumed`],
+/// unless the actor was despawned in the meantime.
+fn resume_action(actor: Entity, action: ComponentId) -> impl Command {
+    move |world: &mut World| {
+        let Ok(mut entity) = world.get_entity_mut(actor) else {
+            // The actor was despawned while paused.
+            return;
+        };
+        entity.insert(CurrentAction(action));
+        world.trigger(OnActionResumed { entity: actor, action });
+    }
+}
+
+/// [`Resource`] for actions suspended by [`AiPaused`] components that are being replaced,
+/// carried over from the replaced component to the new one.
+#[derive(Resource, Default)]
+struct ReplacedAiPauses(EntityHashMap<ComponentId>);
+
+/// [`Resource`] for pausing the AI of all actors.
+///
+/// Actions are suspended and resumed when the pause changes, in the [`PausePlugin`]'s schedule,
+/// or by running [`GlobalAiPause::apply`] manually.
+///
+/// See the [module docs](crate::pause) for more information.
+#[derive(Resource, Reflect)]
+#[derive(Clone, PartialEq, Debug, Default)]
+#[reflect(Resource, PartialEq, Debug, Default)]
+pub struct GlobalAiPause {
+    /// Whether the AI of all actors is paused.
+    paused: bool,
+    /// Whether to suspend the current actions while paused.
+    suspend_actions: bool,
+    /// Map of actors to the actions suspended by the global pause.
+    suspended: EntityHashMap<ComponentId>,
+}
+
+impl GlobalAiPause {
+    /// Returns `true` if the AI of all actors is paused.
+    #[must_use]
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Returns `true` if current actions are suspended while paused.
+    #[must_use]
+    pub fn suspends_actions(&self) -> bool {
+        self.suspend_actions
+    }
+
+    /// Pauses the AI of all actors, letting current actions keep running.
+    pub fn pause(&mut self) {
+        self.paused = true;
+        self.suspend_actions = false;
+    }
+
+    /// Pauses the AI of all actors, suspending current actions until resumed.
+    pub fn pause_suspending(&mut self) {
+        self.paused = true;
+        self.suspend_actions = true;
+    }
+
+    /// Resumes the AI of all actors.
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    /// [`System`] that suspends or resumes the current actions of all actors to match the global pause.
+    ///
+    /// Actors despawned while suspended are forgotten.
+    pub fn apply(
+        mut commands: Commands,
+        mut global: ResMut<GlobalAiPause>,
+        mut actors: Query<(Entity, &CurrentAction, Option<&mut AiPaused>)>,
+        entities: &Entities,
+    ) {
+        let global = global.bypass_change_detection();
+        global.suspended.retain(|&actor, _| entities.contains(actor));
+        if global.paused && global.suspend_actions {
+            for (actor, &CurrentAction(action), paused) in actors.iter_mut() {
+                if global.suspended.contains_key(&actor) || paused.is_some_and(|paused| paused.suspended.is_some()) {
+                    // The action is already suspended.
+                    continue;
+                }
+                global.suspended.insert(actor, action);
+                commands.trigger(OnActionSuspended { entity: actor, action });
+            }
+        } else {
+            for (actor, action) in global.suspended.drain() {
+                if let Ok((_, _, Some(mut paused))) = actors.get_mut(actor)
+                    && paused.suspend_action
+                {
+                    // Hand the suspended action over to the actor's own pause.
+                    paused.suspended = Some(action);
+                    continue;
+                }
+                commands.queue(resume_action(actor, action));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use crate::{
+        acting::CurrentAction,
+        event::{OnActionInitiated, OnActionResumed, OnActionSuspended, RequestAction, RunPicking, RunScoring},
+        pause::{AiPaused, GlobalAiPause},
+        picking::{Highest, Picker},
+        scoring::{FixedScore, Score},
+    };
+
+    #[derive(Component)]
+    struct Idle;
+
+    #[derive(Component)]
+    struct Patrol;
+
+    #[derive(Resource, Default)]
+    struct Log(Vec<&'static str>);
+
+    #[test]
+    fn pause_and_resume() {
+        let mut app = App::new();
+        app.add_plugins(crate::ObservedUtilityPlugins::TurnBased);
+        app.init_resource::<Log>();
+        app.add_observer(|_: On<OnActionInitiated>, mut log: ResMut<Log>| log.0.push("initiated"));
+        app.add_observer(|_: On<OnActionSuspended>, mut log: ResMut<Log>| log.0.push("suspended"));
+        app.add_observer(|_: On<OnActionResumed>, mut log: ResMut<Log>| log.0.push("resumed"));
+        let world = app.world_mut();
+
+        let idle = world.register_component::<Idle>();
+        let patrol = world.register_component::<Patrol>();
+
+        let scorer = world.spawn((FixedScore::new(0.8), Score::default())).id();
+        let actor = world
+            .spawn((Picker::new(idle).with(scorer, patrol), Highest, CurrentAction(idle)))
+            .add_child(scorer)
+            .id();
+
+        // Nothing is scored, picked or requested while paused.
+        world.entity_mut(actor).insert(AiPaused::suspending());
+        world.trigger(RunScoring::all());
+        world.trigger(RunPicking::all());
+        world.flush();
+        world.trigger(RequestAction::picked(actor));
+        world.flush();
+        assert_eq!(world.get::<Score>(scorer).unwrap().get(), 0.);
+        assert_eq!(world.get::<Picker>(actor).unwrap().picked, idle);
+
+        world.entity_mut(actor).remove::<AiPaused>();
+        world.flush();
+        assert_eq!(world.get::<CurrentAction>(actor).unwrap().0, idle);
+        assert_eq!(world.resource::<Log>().0, ["suspended", "resumed"]);
+
+        // The global pause suspends all actors, and lets the actor's own pause take over.
+        world.trigger(RequestAction::specific(actor, patrol));
+        world.flush();
+        world.resource_mut::<GlobalAiPause>().pause_suspending();
+        world.run_system_cached(GlobalAiPause::apply).unwrap();
+        world.entity_mut(actor).insert(AiPaused::suspending());
+        world.resource_mut::<GlobalAiPause>().resume();
+        world.run_system_cached(GlobalAiPause::apply).unwrap();
+        world.trigger(RunScoring::all());
+        world.flush();
+        assert_eq!(world.get::<Score>(scorer).unwrap().get(), 0.);
+        assert_eq!(world.get::<AiPaused>(actor).unwrap().suspended(), Some(patrol));
+
+        world.entity_mut(actor).remove::<AiPaused>();
+        world.flush();
+        assert_eq!(world.get::<CurrentAction>(actor).unwrap().0, patrol);
+        assert_eq!(
+            world.resource::<Log>().0,
+            ["suspended", "resumed", "initiated", "suspended", "resumed"]
+        );
+    }
+
+    #[test]
+    fn despawn_while_paused() {
+        let mut app = App::new();
+        app.add_plugins(crate::ObservedUtilityPlugins::TurnBased);
+        app.init_resource::<Log>();
+        app.add_observer(|_: On<OnActionSuspended>, mut log: ResMut<Log>| log.0.push("suspended"));
+        app.add_observer(|_: On<OnActionResumed>, mut log: ResMut<Log>| log.0.push("resumed"));
+        let world = app.world_mut();
+
+        let idle = world.register_component::<Idle>();
+        let patrol = world.register_component::<Patrol>();
+
+        // Despawning a paused actor doesn't resume its action.
+        let actor = world.spawn((Picker::new(idle), CurrentAction(patrol))).id();
+        world.entity_mut(actor).insert(AiPaused::suspending());
+        world.flush();
+        world.despawn(actor);
+        world.flush();
+        assert_eq!(world.resource::<Log>().0, ["suspended"]);
+
+        // Nor does despawning an actor suspended by the global pause.
+        let actor = world.spawn((Picker::new(idle), CurrentAction(patrol))).id();
+        world.resource_mut::<GlobalAiPause>().pause_suspending();
+        world.run_system_cached(GlobalAiPause::apply).unwrap();
+        world.flush();
+        world.despawn(actor);
+        world.resource_mut::<GlobalAiPause>().resume();
+        world.run_system_cached(GlobalAiPause::apply).unwrap();
+        world.flush();
+        assert!(world.resource::<GlobalAiPause>().suspended.is_empty());
+        assert_eq!(world.resource::<Log>().0, ["suspended", "suspended"]);
+
+        // Nor does despawning a paused actor while the global pause would take its action over.
+        let actor = world.spawn((Picker::new(idle), CurrentAction(patrol))).id();
+        world.entity_mut(actor).insert(AiPaused::suspending());
+        world.resource_mut::<GlobalAiPause>().pause_suspending();
+        world.run_system_cached(GlobalAiPause::apply).unwrap();
+        world.flush();
+        world.despawn(actor);
+        assert!(world.resource::<GlobalAiPause>().suspended.is_empty());
+        assert_eq!(world.resource::<Log>().0, ["suspended", "suspended", "suspended"]);
+    }
+
+    #[test]
+    fn replace_pause() {
+        let mut app = App::new();
+        app.add_plugins(crate::ObservedUtilityPlugins::TurnBased);
+        app.init_resource::<Log>();
+        app.add_observer(|_: On<OnActionSuspended>, mut log: ResMut<Log>| log.0.push("suspended"));
+        app.add_observer(|_: On<OnActionResumed>, mut log: ResMut<Log>| log.0.push("resumed"));
+        let world = app.world_mut();
+
+        let idle = world.register_component::<Idle>();
+        let patrol = world.register_component::<Patrol>();
+
+        let actor = world.spawn((Picker::new(idle), CurrentAction(patrol))).id();
+
+        // Pausing an already paused actor keeps the action suspended.
+        world.entity_mut(actor).insert(AiPaused::suspending());
+        world.flush();
+        world.entity_mut(actor).insert(AiPaused::suspending());
+        world.flush();
+        assert_eq!(world.get::<AiPaused>(actor).unwrap().suspended(), Some(patrol));
+        assert_eq!(world.resource::<Log>().0, ["suspended"]);
+
+        world.entity_mut(actor).remove::<AiPaused>();
+        world.flush();
+        assert_eq!(world.get::<CurrentAction>(actor).unwrap().0, patrol);
+        assert_eq!(world.resource::<Log>().0, ["suspended", "resumed"]);
+    }
+}
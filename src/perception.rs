@@ -0,0 +1,603 @@
+//! Perception lets actors act on what they remember, rather than on omniscient queries of the world.
+//!
+//! Actors with [`Senses`] perceive [`Perceivable`] entities using pluggable [`Sensor`]s,
+//! such as a [`RadiusSensor`] or a [`ConeSensor`].
+//! Each perceived entity is stored in the actor's [`Memory`] with its last-seen position, time, and confidence.
+//! Confidence decays over time, and entities are forgotten once their confidence drops too low.
+//!
+//! Scorers read from the [`Memory`] of the closest ancestor entity that has one:
+//! - [`TimeSinceSeen`] scores how long ago a target was last seen.
+//! - [`RememberedCount`] scores how many remembered entities match a filter.
+//!
+//! Perception only reads [`GlobalTransform`]s and [`Time`], so it runs deterministically without rendering or physics.
+//! Without a [`Time`] resource, memories don't decay and are all seen at time `0`.
+//! Add the [`PerceptionPlugin`] to update memories.
+//!
+//! # Example
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_observed_utility::prelude::*;
+//!
+//! #[derive(Component)]
+//! struct Enemy;
+//!
+//! # let mut app = App::new();
+//! # app.add_plugins((ObservedUtilityPlugins::RealTime, TransformPlugin));
+//! app.add_plugins(PerceptionPlugin::default());
+//! # app.init_resource::<Time>();
+//!
+//! # let world = app.world_mut();
+//! let enemy = world.spawn((Enemy, Perceivable, Transform::from_xyz(0., 0., -5.))).id();
+//!
+//! // Feel outnumbered when remembering 2 or more enemies.
+//! let scorer = world
+//!     .spawn((RememberedCount::<With<Enemy>>::new(0., 2.), Score::default()))
+//!     .id();
+//!
+//! let actor = world
+//!     .spawn((
+//!         Senses::default().with(RadiusSensor::new(10.)),
+//!         Memory::default(),
+//!         Transform::default(),
+//!     ))
+//!     .add_child(scorer)
+//!     .id();
+//!
+//! # world.flush();
+//! # app.update();
+//! # let world = app.world_mut();
+//! # world.run_system_cached(PerceptionPlugin::perceive).unwrap();
+//! assert!(world.get::<Memory>(actor).unwrap().get(enemy).is_some());
+//! # world.trigger(RunScoring::entity(scorer));
+//! # world.flush();
+//! # assert_eq!(world.get::<Score>(scorer).unwrap().get(), 0.5);
+//! ```
+
+use std::marker::PhantomData;
+
+use bevy::{
+    ecs::{
+        component::StorageType,
+        entity::EntityHashMap,
+        lifecycle::{ComponentHook, HookContext},
+        query::QueryFilter,
+        schedule::{InternedScheduleLabel, ScheduleLabel},
+        world::DeferredWorld,
+    },
+    prelude::*,
+};
+
+use crate::{
+    ecs::{AncestorQuery, DeferredWorldExt, elapsed_secs},
+    event::OnScore,
+    scoring::{Evaluator, LinearEvaluator, Score},
+};
+
+/// [`Plugin`] that decays all [`Memory`]s and lets all [`Senses`] perceive in the configured [`Schedule`].
+pub struct PerceptionPlugin {
+    /// The [`ScheduleLabel`] to perceive in.
+    pub update_in: InternedScheduleLabel,
+}
+
+impl Default for PerceptionPlugin {
+    fn default() -> Self {
+        Self {
+            update_in: FixedUpdate.intern(),
+        }
+    }
+}
+
+impl Plugin for PerceptionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(self.update_in, Self::perceive);
+
+        app.register_type::<Perceivable>()
+            .register_type::<Memory>()
+            .register_type::<Remembered>()
+            .register_type::<RadiusSensor>()
+            .register_type::<ConeSensor>()
+            .register_type::<TimeSinceSeen>();
+
+        // Note: Senses cannot be reflected due to the boxed Sensor trait objects
+    }
+}
+
+impl PerceptionPlugin {
+    /// [`System`] that decays all [`Memory`]s, then remembers every [`Perceivable`] entity sensed by each actor's [`Senses`].
+    pub fn perceive(
+        time: Option<Res<Time>>,
+        mut actors: Query<(Entity, &GlobalTransform, &Senses, &mut Memory)>,
+        perceivables: Query<(Entity, &GlobalTransform), With<Perceivable>>,
+    ) {
+        let now = elapsed_secs(time.as_deref());
+        let delta = time.as_ref().map_or(0., |time| time.delta_secs());
+        for (actor, origin, senses, mut memory) in actors.iter_mut() {
+            memory.decay(delta);
+
+            for (target, transform) in perceivables.iter() {
+                if target == actor {
+                    continue;
+                }
+
+                let confidence = senses
+                    .sensors
+                    .iter()
+                    .filter_map(|sensor| sensor.sense(origin, transform.translation()))
+                    .reduce(f32::max);
+
+                if let Some(confidence) = confidence {
+                    memory.remember(target, transform.translation(), now, confidence);
+                }
+            }
+        }
+    }
+}
+
+/// Marker [`Component`] for entities that can be perceived by [`Senses`].
+#[derive(Component, Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[reflect(Component, PartialEq, Debug, Default)]
+pub struct Perceivable;
+
+/// A way of sensing a target, such as by sight or hearing.
+#[reflect_trait]
+pub trait Sensor: Send + Sync + 'static {
+    /// Returns the confidence, from `0` to `1`, with which the target position is sensed from the origin,
+    /// or [`None`] if it isn't sensed.
+    fn sense(&self, origin: &GlobalTransform, target: Vec3) -> Option<f32>;
+}
+
+impl<F> Sensor for F
+where
+    F: Fn(&GlobalTransform, Vec3) -> Option<f32> + Send + Sync + 'static,
+{
+    fn sense(&self, origin: &GlobalTransform, target: Vec3) -> Option<f32> {
+        self(origin, target)
+    }
+}
+
+/// [`Sensor`] that senses targets within a radius of the origin with full confidence.
+#[derive(Reflect, Clone, Copy, PartialEq, Debug)]
+#[reflect(Sensor, PartialEq, Debug)]
+pub struct RadiusSensor {
+    /// The distance within which targets are sensed.
+    pub radius: f32,
+}
+
+impl RadiusSensor {
+    /// Creates a new [`RadiusSensor`] with the given radius.
+    #[must_use]
+    pub fn new(radius: f32) -> Self {
+        Self { radius }
+    }
+}
+
+impl Sensor for RadiusSensor {
+    fn sense(&self, origin: &GlobalTransform, target: Vec3) -> Option<f32> {
+        (origin.translation().distance_squared(target) <= self.radius * self.radius).then_some(1.)
+    }
+}
+
+/// [`Sensor`] that senses targets within a cone in front of the origin,
+/// with confidence falling off linearly towards the edge of its range.
+#[derive(Reflect, Clone, Copy, PartialEq, Debug)]
+#[reflect(Sensor, PartialEq, Debug)]
+pub struct ConeSensor {
+    /// The distance within which targets are sensed.
+    pub range: f32,
+    /// The angle, in radians, between the forward direction and the edge of the cone.
+    pub half_angle: f32,
+    /// The origin's local facing direction.
+    pub forward: Vec3,
+}
+
+impl ConeSensor {
+    /// Creates a new [`ConeSensor`] with the given range and half angle, in radians.
+    ///
+    /// The origin faces its local [`Vec3::NEG_Z`] direction, see [`ConeSensor::with_forward`].
+    #[must_use]
+    pub fn new(range: f32, half_angle: f32) -> Self {
+        Self {
+            range,
+            half_angle,
+            forward: Vec3::NEG_Z,
+        }
+    }
+
+    /// Sets the origin's local facing direction, such as [`Vec3::Y`] for 2D games.
+    #[must_use]
+    pub fn with_forward(mut self, forward: Vec3) -> Self {
+        self.forward = forward;
+        self
+    }
+}
+
+impl Sensor for ConeSensor {
+    fn sense(&self, origin: &GlobalTransform, target: Vec3) -> Option<f32> {
+        let to_target = target - origin.translation();
+        let distance = to_target.length();
+        if distance > self.range {
+            return None;
+        }
+        if distance > 0. && (origin.rotation() * self.forward).angle_between(to_target) > self.half_angle {
+            return None;
+        }
+        Some(LinearEvaluator::from_range(self.range, 0.).evaluate(distance))
+    }
+}
+
+/// [`Component`] for actor entities that perceive [`Perceivable`] entities into their [`Memory`].
+#[derive(Component, Default)]
+pub struct Senses {
+    /// The sensors to perceive with. The most confident sensor wins.
+    sensors: Vec<Box<dyn Sensor>>,
+}
+
+impl Senses {
+    /// Adds a [`Sensor`] to perceive with.
+    #[must_use]
+    pub fn with(mut self, sensor: impl Sensor) -> Self {
+        self.sensors.push(Box::new(sensor));
+        self
+    }
+}
+
+/// What an actor remembers about a perceived entity.
+#[derive(Reflect)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[reflect(PartialEq, Debug)]
+pub struct Remembered {
+    /// The position the entity was last seen at.
+    pub position: Vec3,
+    /// The elapsed [`Time`], in seconds, the entity was last seen at.
+    pub last_seen: f64,
+    /// How confident the actor is in the memory, from `0` to `1`.
+    pub confidence: f32,
+}
+
+/// [`Component`] for actor entities that remember perceived entities.
+///
+/// See the [module docs](crate::perception) for more information.
+#[derive(Component, Reflect)]
+#[derive(Clone, PartialEq, Debug)]
+#[reflect(Component, PartialEq, Debug)]
+pub struct Memory {
+    /// Map of remembered entities to what's remembered about them.
+    entries: EntityHashMap<Remembered>,
+    /// How much confidence is lost per second.
+    decay: f32,
+    /// The confidence below which entities are forgotten.
+    forget_below: f32,
+}
+
+impl Default for Memory {
+    fn default() -> Self {
+        Self {
+            entries: EntityHashMap::default(),
+            decay: 0.1,
+            forget_below: 0.01,
+        }
+    }
+}
+
+impl Memory {
+    /// Sets how much confidence is lost per second.
+    #[must_use]
+    pub fn with_decay(mut self, decay: f32) -> Self {
+        self.decay = decay;
+        self
+    }
+
+    /// Sets the confidence below which entities are forgotten.
+    #[must_use]
+    pub fn with_forget_below(mut self, forget_below: f32) -> Self {
+        self.forget_below = forget_below;
+        self
+    }
+
+    /// Returns what's remembered about the entity, if anything.
+    #[must_use]
+    pub fn get(&self, entity: Entity) -> Option<&Remembered> {
+        self.entries.get(&entity)
+    }
+
+    /// Returns an iterator over all remembered entities.
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, &Remembered)> {
+        self.entries.iter().map(|(&entity, remembered)| (entity, remembered))
+    }
+
+    /// Returns the number of remembered entities.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if nothing is remembered.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Remembers seeing the entity at the given position and time.
+    ///
+    /// The confidence is raised to the given confidence, but never lowered.
+    pub fn remember(&mut self, entity: Entity, position: Vec3, now: f64, confidence: f32) {
+        let entry = self.entries.entry(entity).or_insert(Remembered {
+            position,
+            last_seen: now,
+            confidence: 0.,
+        });
+        entry.position = position;
+        entry.last_seen = now;
+        entry.confidence = entry.confidence.max(confidence.clamp(0., 1.));
+    }
+
+    /// Forgets the entity, returning what was remembered about it.
+    pub fn forget(&mut self, entity: Entity) -> Option<Remembered> {
+        self.entries.remove(&entity)
+    }
+
+    /// Decays the confidence of all memories by the given number of seconds,
+    /// forgetting those that drop below the threshold.
+    pub fn decay(&mut self, seconds: f32) {
+        let loss = self.decay * seconds;
+        let forget_below = self.forget_below;
+        self.entries.retain(|_, remembered| {
+            remembered.confidence = (remembered.confidence - loss).max(0.);
+            remembered.confidence >= forget_below
+        });
+    }
+}
+
+/// [`Score`] [`Component`] that scores how long ago a target entity was last seen, in seconds.
+///
+/// The time is read from the [`Memory`] of the closest ancestor entity that has one.
+/// The time is normalized from the `[min, max]` range, so recently seen targets score lower.
+/// If the target isn't remembered, the score is the maximum.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_observed_utility::prelude::*;
+///
+/// # let mut app = App::new();
+/// # app.add_plugins(ObservedUtilityPlugins::RealTime);
+/// # app.init_resource::<Time>();
+/// # let world = app.world_mut();
+/// let target = world.spawn_empty().id();
+///
+/// // Give up the search after 30 seconds.
+/// let scorer = world
+///     .spawn((TimeSinceSeen::new(target, 0., 30.), Score::default()))
+///     .id();
+///
+/// let mut memory = Memory::default();
+/// memory.remember(target, Vec3::ZERO, 0., 1.);
+/// world.spawn(memory).add_child(scorer);
+///
+/// # world.resource_mut::<Time>().advance_by(std::time::Duration::from_secs(15));
+/// # world.trigger(RunScoring::entity(scorer));
+/// # world.flush();
+/// # assert_eq!(world.get::<Score>(scorer).unwrap().get(), 0.5);
+/// ```
+#[derive(Reflect)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[reflect(Component, PartialEq, Debug)]
+pub struct TimeSinceSeen {
+    /// The entity to check the memory of.
+    target: Entity,
+    /// The time that scores the minimum.
+    min: f32,
+    /// The time that scores the maximum.
+    max: f32,
+}
+
+impl TimeSinceSeen {
+    /// Creates a new [`TimeSinceSeen`] that normalizes times, in seconds, from the `[min, max]` range.
+    #[must_use]
+    pub fn new(target: Entity, min: f32, max: f32) -> Self {
+        Self { target, min, max }
+    }
+
+    /// Returns the entity to check the memory of.
+    #[must_use]
+    pub fn target(&self) -> Entity {
+        self.target
+    }
+
+    /// [`Observer`] for [`TimeSinceSeen`] [`Score`] entities that scores the time since the target was seen.
+    fn observer(
+        trigger: On<OnScore>,
+        time: Option<Res<Time>>,
+        mut target: Query<(&mut Score, &TimeSinceSeen)>,
+        mut memories: AncestorQuery<&'static Memory>,
+    ) {
+        let entity = trigger.event().entity;
+        let Ok((mut actor_score, settings)) = target.get_mut(entity) else {
+            // The entity is not scoring for time since seen.
+            return;
+        };
+
+        let elapsed = memories
+            .get(entity)
+            .ok()
+            .and_then(|memory| memory.get(settings.target))
+            .map_or(f32::INFINITY, |remembered| {
+                (elapsed_secs(time.as_deref()) - remembered.last_seen) as f32
+            });
+
+        *actor_score = Score::new(LinearEvaluator::from_range(settings.min, settings.max).evaluate(elapsed));
+    }
+}
+
+impl Component for TimeSinceSeen {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+    type Mutability = bevy::ecs::component::Immutable;
+
+    fn on_add() -> Option<ComponentHook> {
+        Some(|mut world: DeferredWorld, _context: HookContext| {
+            #[derive(Resource, Default)]
+            struct TimeSinceSeenObserverSpawned;
+
+            world.once::<TimeSinceSeenObserverSpawned>().observe(Self::observer);
+        })
+    }
+}
+
+/// [`Resource`] marking that the observer for a [`RememberedCount`] filter has been spawned.
+struct RememberedCountObserverSpawned<F>(PhantomData<fn() -> F>);
+
+impl<F: 'static> Resource for RememberedCountObserverSpawned<F> {}
+
+impl<F> Default for RememberedCountObserverSpawned<F> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+/// [`Score`] [`Component`] that scores the number of remembered entities matching the filter `F`.
+///
+/// The entities are read from the [`Memory`] of the closest ancestor entity that has one.
+/// Only entities that still exist are counted. The count is normalized from the `[min, max]` range.
+pub struct RememberedCount<F: QueryFilter + 'static = ()> {
+    /// The count that scores the minimum.
+    min: f32,
+    /// The count that scores the maximum.
+    max: f32,
+    _filter: PhantomData<fn() -> F>,
+}
+
+impl<F: QueryFilter + 'static> RememberedCount<F> {
+    /// Creates a new [`RememberedCount`] that normalizes the count from the `[min, max]` range.
+    #[must_use]
+    pub fn new(min: f32, max: f32) -> Self {
+        Self {
+            min,
+            max,
+            _filter: PhantomData,
+        }
+    }
+
+    /// [`Observer`] for [`RememberedCount`] [`Score`] entities that scores the number of remembered entities.
+    fn observer(
+        trigger: On<OnScore>,
+        mut target: Query<(&mut Score, &RememberedCount<F>)>,
+        mut memories: AncestorQuery<&'static Memory>,
+        matching: Query<(), F>,
+    ) {
+        let entity = trigger.event().entity;
+        let Ok((mut actor_score, settings)) = target.get_mut(entity) else {
+            // The entity is not scoring for remembered count.
+            return;
+        };
+
+        let count = memories.get(entity).map_or(0, |memory| {
+            memory
+                .iter()
+                .filter(|&(remembered, _)| matching.contains(remembered))
+                .count()
+        });
+
+        *actor_score = Score::new(LinearEvaluator::from_range(settings.min, settings.max).evaluate(count as f32));
+    }
+}
+
+impl<F: QueryFilter + 'static> Component for RememberedCount<F> {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+    type Mutability = bevy::ecs::component::Immutable;
+
+    fn on_add() -> Option<ComponentHook> {
+        Some(|mut world: DeferredWorld, _context: HookContext| {
+            world
+                .once::<RememberedCountObserverSpawned<Self>>()
+                .observe(Self::observer);
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use approx::assert_relative_eq;
+    use bevy::prelude::*;
+
+    use crate::perception::{ConeSensor, Memory, Perceivable, PerceptionPlugin, RadiusSensor, Senses};
+
+    #[test]
+    fn sense_and_forget() {
+        let mut app = App::new();
+        app.add_plugins(TransformPlugin);
+        app.init_resource::<Time>();
+        let world = app.world_mut();
+
+        let ahead = world.spawn((Perceivable, Transform::from_xyz(0., 0., -5.))).id();
+        let behind = world.spawn((Perceivable, Transform::from_xyz(0., 0., 5.))).id();
+        let far = world.spawn((Perceivable, Transform::from_xyz(0., 0., -50.))).id();
+
+        let eyes = world
+            .spawn((
+                Senses::default().with(ConeSensor::new(10., std::f32::consts::FRAC_PI_4)),
+                Memory::default().with_decay(0.25).with_forget_below(0.5),
+                Transform::default(),
+            ))
+            .id();
+        let ears = world
+            .spawn((
+                Senses::default().with(RadiusSensor::new(6.)),
+                Memory::default(),
+                Transform::default(),
+            ))
+            .id();
+
+        app.update();
+        let world = app.world_mut();
+        world.run_system_cached(PerceptionPlugin::perceive).unwrap();
+
+        let memory = world.get::<Memory>(eyes).unwrap();
+        assert_relative_eq!(memory.get(ahead).unwrap().confidence, 0.5);
+        assert!(memory.get(behind).is_none());
+        assert!(memory.get(far).is_none());
+
+        let memory = world.get::<Memory>(ears).unwrap();
+        assert_eq!(memory.len(), 2);
+        assert_eq!(memory.get(behind).unwrap().position, Vec3::new(0., 0., 5.));
+
+        // Out of sight, the memory decays until it's forgotten.
+        world.entity_mut(ahead).insert(Transform::from_xyz(0., 0., 5.));
+        world.resource_mut::<Time>().advance_by(Duration::from_secs(1));
+        app.update();
+        let world = app.world_mut();
+        world.run_system_cached(PerceptionPlugin::perceive).unwrap();
+        assert!(world.get::<Memory>(eyes).unwrap().is_empty());
+
+        let remembered = world.get::<Memory>(ears).unwrap().get(ahead).unwrap();
+        assert_eq!(remembered.last_seen, 1.);
+        assert_eq!(remembered.position, Vec3::new(0., 0., 5.));
+    }
+
+    #[test]
+    fn without_time() {
+        let mut app = App::new();
+        app.add_plugins(TransformPlugin);
+        let world = app.world_mut();
+
+        let target = world.spawn((Perceivable, Transform::from_xyz(0., 0., -5.))).id();
+        let actor = world
+            .spawn((
+                Senses::default().with(RadiusSensor::new(10.)),
+                Memory::default(),
+                Transform::default(),
+            ))
+            .id();
+
+        app.update();
+        let world = app.world_mut();
+        world.run_system_cached(PerceptionPlugin::perceive).unwrap();
+        let remembered = world.get::<Memory>(actor).unwrap().get(target).unwrap();
+        assert_eq!(remembered.last_seen, 0.);
+        assert_eq!(remembered.confidence, 1.);
+    }
+}
@@ -0,0 +1,358 @@
+//! Personalities let many actors share one score tree design while still behaving differently.
+//!
+//! An actor's [`Personality`] component holds named traits, like bravery or greed, usually from `0` to `1`.
+//! Score entities annotated with [`TraitModulated`] have their score adjusted by a trait of the closest ancestor
+//! [`Personality`] after they're scored, either by scaling it, biasing it, or setting their [`Weighted`] value
+//! for a parent [`Measured`] score.
+//!
+//! This way, a brave guard and a cowardly merchant can be spawned from the same tree,
+//! without cloning and hand-editing it per archetype.
+//!
+//! # Example
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_observed_utility::prelude::*;
+//! # use approx::assert_relative_eq;
+//!
+//! # let mut app = App::new();
+//! # app.add_plugins(ObservedUtilityPlugins::RealTime);
+//! # let world = app.world_mut();
+//! // The urge to flee is scaled down for brave actors.
+//! let flee = world
+//!     .spawn((
+//!         FixedScore::new(0.8),
+//!         TraitModulated::new("bravery", TraitModulation::Scale { min: 1., max: 0. }),
+//!         Score::default(),
+//!     ))
+//!     .id();
+//!
+//! world
+//!     .spawn(Personality::default().with("bravery", 0.75))
+//!     .add_child(flee);
+//! # world.trigger(RunScoring::entity(flee));
+//! # world.flush();
+//! # assert_relative_eq!(world.get::<Score>(flee).unwrap().get(), 0.2);
+//! ```
+//!
+//! [`Measured`]: crate::scoring::Measured
+
+use std::borrow::Cow;
+
+use bevy::{
+    ecs::{
+        component::StorageType,
+        lifecycle::{ComponentHook, HookContext},
+        world::DeferredWorld,
+    },
+    platform::collections::HashMap,
+    prelude::*,
+};
+
+use crate::{
+    ecs::{AncestorQuery, DeferredWorldExt},
+    event::OnScorePostProcess,
+    scoring::{PostProcessed, Score, Weighted},
+};
+
+/// [`Plugin`] that registers the personality types.
+///
+/// This plugin is included in [`ObservedUtilityPlugins`](crate::ObservedUtilityPlugins).
+#[derive(Default)]
+pub struct PersonalityPlugin;
+
+impl Plugin for PersonalityPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Personality>()
+            .register_type::<TraitModulated>()
+            .register_type::<TraitModulation>();
+    }
+}
+
+/// [`Component`] for actor entities with named personality traits.
+///
+/// See the [module docs](crate::personality) for more information.
+#[derive(Component, Reflect)]
+#[derive(Clone, PartialEq, Debug, Default)]
+#[reflect(Component, PartialEq, Debug, Default)]
+pub struct Personality {
+    /// Map of trait names to their values.
+    traits: HashMap<Cow<'static, str>, f32>,
+}
+
+impl Personality {
+    /// Sets the value of a named trait.
+    #[must_use]
+    pub fn with(mut self, name: impl Into<Cow<'static, str>>, value: f32) -> Self {
+        self.set(name, value);
+        self
+    }
+
+    /// Returns the value of a named trait, if any.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<f32> {
+        self.traits.get(name).copied()
+    }
+
+    /// Sets the value of a named trait.
+    pub fn set(&mut self, name: impl Into<Cow<'static, str>>, value: f32) {
+        self.traits.insert(name.into(), value);
+    }
+
+    /// Removes a named trait, returning its value, if any.
+    pub fn remove(&mut self, name: &str) -> Option<f32> {
+        self.traits.remove(name)
+    }
+
+    /// Returns an iterator over all named traits.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, f32)> {
+        self.traits.iter().map(|(name, &value)| (name.as_ref(), value))
+    }
+}
+
+/// How a personality trait modulates a [`TraitModulated`] score.
+///
+/// The trait value is mapped linearly from `[0, 1]` onto `[min, max]`.
+#[derive(Reflect)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[reflect(PartialEq, Debug)]
+pub enum TraitModulation {
+    /// Multiplies the score by the mapped trait value.
+    Scale {
+        /// The factor when the trait is `0`.
+        min: f32,
+        /// The factor when the trait is `1`.
+        max: f32,
+    },
+    /// Adds the mapped trait value to the score.
+    Bias {
+        /// The bias when the trait is `0`.
+        min: f32,
+        /// The bias when the trait is `1`.
+        max: f32,
+    },
+    /// Sets the [`Weighted`] value of the score entity to the mapped trait value,
+    /// weighting it in its parent [`Measured`](crate::scoring::Measured) score.
+    Weight {
+        /// The weight when the trait is `0`.
+        min: f32,
+        /// The weight when the trait is `1`.
+        max: f32,
+    },
+}
+
+impl TraitModulation {
+    /// Maps the trait value from `[0, 1]` onto `[min, max]`.
+    #[must_use]
+    pub fn map(&self, value: f32) -> f32 {
+        let (Self::Scale { min, max } | Self::Bias { min, max } | Self::Weight { min, max }) = *self;
+        min + (max - min) * value
+    }
+}
+
+/// [`Component`] for [`Score`] entities whose score is modulated by a [`Personality`] trait of the closest ancestor.
+///
+/// If no ancestor has a [`Personality`] with the trait, the score is left as-is.
+#[derive(Reflect)]
+#[derive(Clone, PartialEq, Debug)]
+#[reflect(Component, PartialEq, Debug)]
+pub struct TraitModulated {
+    /// The name of the trait.
+    name: Cow<'static, str>,
+    /// How the trait modulates the score.
+    modulation: TraitModulation,
+}
+
+impl TraitModulated {
+    /// Creates a new [`TraitModulated`] for the named trait.
+    #[must_use]
+    pub fn new(name: impl Into<Cow<'static, str>>, modulation: TraitModulation) -> Self {
+        Self {
+            name: name.into(),
+            modulation,
+        }
+    }
+
+    /// Returns the name of the trait.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns how the trait modulates the score.
+    #[must_use]
+    pub fn modulation(&self) -> TraitModulation {
+        self.modulation
+    }
+
+    /// [`Observer`] for [`TraitModulated`] [`Score`] entities that applies the trait to the calculated score.
+    fn observer(
+        trigger: On<OnScorePostProcess>,
+        mut commands: Commands,
+        mut target: Query<(&mut Score, &TraitModulated, Option<&mut Weighted>)>,
+        mut personalities: AncestorQuery<&'static Personality>,
+    ) {
+        let entity = trigger.event().entity;
+        let Ok((mut score, settings, weighted)) = target.get_mut(entity) else {
+            // The entity is not modulated by a trait.
+            return;
+        };
+
+        let Some(value) = personalities
+            .get(entity)
+            .ok()
+            .and_then(|personality| personality.get(&settings.name))
+        else {
+            return;
+        };
+
+        let mapped = settings.modulation.map(value);
+        let current = score.get();
+        match settings.modulation {
+            TraitModulation::Scale { .. } => score.set(current * mapped),
+            TraitModulation::Bias { .. } => score.set(current + mapped),
+            TraitModulation::Weight { .. } => {
+                if let Some(mut weighted) = weighted {
+                    weighted.set(mapped);
+                } else {
+                    commands.entity(entity).insert(Weighted::new(mapped));
+                }
+            }
+        }
+    }
+}
+
+impl Component for TraitModulated {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+    type Mutability = bevy::ecs::component::Immutable;
+
+    fn on_add() -> Option<ComponentHook> {
+        Some(|mut world: DeferredWorld, context: HookContext| {
+            #[derive(Resource, Default)]
+            struct TraitModulatedObserverSpawned;
+
+            world.once::<TraitModulatedObserverSpawned>().observe(Self::observer);
+            PostProcessed::insert_for(&mut world, context);
+        })
+    }
+
+    fn on_remove() -> Option<ComponentHook> {
+        Some(|mut world: DeferredWorld, context: HookContext| {
+            PostProcessed::remove_for(&mut world, context);
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+    use bevy::prelude::*;
+
+    use crate::{
+        event::RunScoring,
+        personality::{Personality, TraitModulated, TraitModulation},
+        scoring::{FixedScore, Measured, PostProcessed, Score, Weighted, WeightedSum},
+    };
+
+    /// Spawns the shared "fight or flee" tree under an actor with the given personality.
+    fn spawn_actor(world: &mut World, personality: Personality) -> (Entity, Entity) {
+        let flee = world
+            .spawn((
+                FixedScore::new(0.6),
+                TraitModulated::new("bravery", TraitModulation::Scale { min: 1., max: 0. }),
+                Score::default(),
+            ))
+            .id();
+        let fight = world
+            .spawn((Measured::new(WeightedSum), Score::default()))
+            .with_children(|parent| {
+                parent.spawn((
+                    FixedScore::new(0.5),
+                    TraitModulated::new("bravery", TraitModulation::Weight { min: 0., max: 1. }),
+                    Weighted::new(1.),
+                    Score::default(),
+                ));
+                parent.spawn((
+                    FixedScore::new(0.2),
+                    TraitModulated::new("greed", TraitModulation::Bias { min: 0., max: 0.5 }),
+                    Score::default(),
+                ));
+            })
+            .id();
+        world.spawn(personality).add_children(&[flee, fight]);
+        (flee, fight)
+    }
+
+    #[test]
+    fn shared_tree() {
+        let mut app = App::new();
+        app.add_plugins(crate::ObservedUtilityPlugins::TurnBased);
+        let world = app.world_mut();
+
+        let (guard_flee, guard_fight) = spawn_actor(world, Personality::default().with("bravery", 0.9));
+        let (merchant_flee, merchant_fight) =
+            spawn_actor(world, Personality::default().with("bravery", 0.1).with("greed", 1.));
+
+        world.trigger(RunScoring::all());
+        world.flush();
+
+        let score = |world: &World, entity| world.get::<Score>(entity).unwrap().get();
+        assert_relative_eq!(score(world, guard_flee), 0.06);
+        assert_relative_eq!(score(world, merchant_flee), 0.54);
+        // The guard has no greed trait, so the greed bias is left as-is.
+        assert_relative_eq!(score(world, guard_fight), 0.45 + 0.2);
+        assert_relative_eq!(score(world, merchant_fight), 0.05 + 0.7);
+    }
+
+    #[test]
+    fn weight_without_weighted() {
+        let mut app = App::new();
+        app.add_plugins(crate::ObservedUtilityPlugins::TurnBased);
+        let world = app.world_mut();
+
+        let brave = world
+            .spawn((
+                FixedScore::new(0.5),
+                TraitModulated::new("bravery", TraitModulation::Weight { min: 0., max: 1. }),
+                Score::default(),
+            ))
+            .id();
+        let fight = world
+            .spawn((Measured::new(WeightedSum), Score::default()))
+            .add_child(brave)
+            .id();
+        world
+            .spawn(Personality::default().with("bravery", 0.5))
+            .add_child(fight);
+
+        // The missing weight is inserted once scored, and applies from the next scoring on.
+        world.trigger(RunScoring::entity(fight));
+        world.flush();
+        assert_relative_eq!(world.get::<Weighted>(brave).unwrap().get().get(), 0.5);
+
+        world.trigger(RunScoring::entity(fight));
+        world.flush();
+        assert_relative_eq!(world.get::<Score>(fight).unwrap().get(), 0.25);
+    }
+
+    #[test]
+    fn remove_post_processed() {
+        let mut app = App::new();
+        app.add_plugins(crate::ObservedUtilityPlugins::TurnBased);
+        let world = app.world_mut();
+
+        let entity = world
+            .spawn((
+                FixedScore::new(0.5),
+                TraitModulated::new("bravery", TraitModulation::Scale { min: 1., max: 0. }),
+                Score::default(),
+            ))
+            .id();
+        world.flush();
+        assert!(world.get::<PostProcessed>(entity).is_some());
+
+        world.entity_mut(entity).remove::<TraitModulated>();
+        world.flush();
+        assert!(world.get::<PostProcessed>(entity).is_none());
+    }
+}
@@ -2,10 +2,19 @@
 //!
 //! # Provided [`Picker`] implementations
 //!
+//! - [`Blend`]: Picks the action with the highest score, and blends all actions by their scores into [`BlendWeights`].
 //! - [`FirstToScore`]: Picks the first action to reach a certain score.
 //! - [`Highest`]: Picks the action with the highest score.
 //! - [`Random`] (requires `rand` feature): Picks a random action.
 //!
+//! Score entities marked [`Ineligible`], [`PendingScore`] or [`Reserved`] are skipped by all provided pickers,
+//! custom pickers can use the [`Eligible`] filter to do the same.
+//!
+//! Pickers also remember the full ranking of their last pick,
+//! so that a [rejected](crate::event::RejectAction) action can fall back to the next-best choice.
+//! Add a [`PickResult`] to an actor entity to inspect every choice of its last pick,
+//! and to get notified with [`OnPickChanged`] when its pick changes.
+//!
 //! [`Score`]: crate::scoring::Score
 
 use bevy::{
@@ -13,17 +22,24 @@ use bevy::{
     prelude::*,
 };
 
+mod blend;
 mod first_to_score;
 mod highest;
 #[cfg(feature = "rand")]
 mod random;
+mod result;
 
+pub use blend::*;
 pub use first_to_score::*;
 pub use highest::*;
 #[cfg(feature = "rand")]
 pub use random::*;
+pub use result::*;
 
-use crate::event::{OnPick, OnPicked, RunPicking};
+use crate::{
+    event::{OnPick, OnPickChanged, OnPicked, RunPicking},
+    pause::{AiPaused, GlobalAiPause},
+};
 
 /// [`Plugin`] for picking actions based on the scores of child entities.
 #[derive(Default)]
@@ -34,28 +50,54 @@ impl Plugin for PickingPlugin {
         app.add_observer(Self::run_picking);
 
         app.register_type::<Picker>()
+            .register_type::<Blend>()
+            .register_type::<BlendWeights>()
             .register_type::<FirstToScore>()
-            .register_type::<Highest>();
+            .register_type::<Highest>()
+            .register_type::<Ineligible>()
+            .register_type::<PendingScore>()
+            .register_type::<Reserved>()
+            .register_type::<SelfScheduled>()
+            .register_type::<PickResult>()
+            .register_type::<PickEntry>();
 
         // Note: PickRandom cannot be reflected due to the boxed Rng trait object
 
         app.register_type::<RunPicking>()
             .register_type::<OnPick>()
-            .register_type::<OnPicked>();
+            .register_type::<OnPicked>()
+            .register_type::<OnPickChanged>();
     }
 }
 
 impl PickingPlugin {
     /// [`Observer`] that triggers the [`OnPick`] event for one specific or all [`Picker`] entities.
-    pub fn run_picking(trigger: On<RunPicking>, mut commands: Commands, pickers: Query<Entity, With<Picker>>) {
+    ///
+    /// Actors whose AI is [paused](crate::pause) are skipped,
+    /// as are [`SelfScheduled`] pickers when picking globally.
+    pub fn run_picking(
+        trigger: On<RunPicking>,
+        mut commands: Commands,
+        pickers: Query<Entity, (With<Picker>, Without<AiPaused>)>,
+        paused: Query<(), With<AiPaused>>,
+        self_scheduled: Query<(), With<SelfScheduled>>,
+        global: Option<Res<GlobalAiPause>>,
+    ) {
         fn trigger_picking(entity: Entity, mut commands: Commands) {
             commands.trigger(OnPick { entity });
         }
 
+        if global.is_some_and(|global| global.is_paused()) {
+            return;
+        }
+
         if let Some(target) = trigger.event().entity {
+            if paused.contains(target) {
+                return;
+            }
             trigger_picking(target, commands.reborrow());
         } else {
-            for target in pickers.iter() {
+            for target in pickers.iter().filter(|&target| !self_scheduled.contains(target)) {
                 trigger_picking(target, commands.reborrow());
             }
         }
@@ -73,6 +115,12 @@ pub struct Picker {
     pub choices: EntityHashMap<ComponentId>,
     /// The last action [`ComponentId`] picked by the picker.
     pub picked: ComponentId,
+    /// The score [`Entity`] whose choice was last picked, or [`None`] if the default action was picked.
+    pub picked_entity: Option<Entity>,
+    /// The score [`Entity`]s of the last pick's choices, from most to least preferred, starting with the picked one.
+    ///
+    /// Used to fall back to the next-best choice when the picked action is [rejected](Picker::reject).
+    pub ranking: Vec<Entity>,
 }
 
 impl Picker {
@@ -83,22 +131,71 @@ impl Picker {
             default,
             choices: EntityHashMap::default(),
             picked: default,
+            picked_entity: None,
+            ranking: Vec::new(),
         }
     }
 
     /// Adds an action [`ComponentId`] to pick based on the provided score [`Entity`].
     #[must_use]
     pub fn with(mut self, score_entity: Entity, action: ComponentId) -> Self {
-        self.choices.insert(score_entity, action);
+        self.insert(score_entity, action);
         self
     }
 
+    /// Inserts an action [`ComponentId`] to pick based on the provided score [`Entity`],
+    /// returning the action it previously picked, if any.
+    pub fn insert(&mut self, score_entity: Entity, action: ComponentId) -> Option<ComponentId> {
+        self.choices.insert(score_entity, action)
+    }
+
+    /// Removes the choice for the provided score [`Entity`], returning its action, if any.
+    ///
+    /// This does not change the last picked action.
+    pub fn remove(&mut self, score_entity: Entity) -> Option<ComponentId> {
+        self.choices.remove(&score_entity)
+    }
+
     /// Grab the action [`ComponentId`] to pick based on the score [`Entity`] and the picker's choices.
+    ///
+    /// The ranking only contains the picked score [`Entity`], see [`Picker::pick_ranked`] to rank all choices.
     pub fn pick(&mut self, score_entity: Option<Entity>) -> ComponentId {
-        let action = score_entity
-            .and_then(|entity| self.choices.get(&entity).copied())
-            .unwrap_or(self.default);
+        self.pick_ranked(score_entity)
+    }
+
+    /// Grab the action [`ComponentId`] to pick based on the most preferred score [`Entity`] that is a choice,
+    /// remembering the full ranking to fall back to if the action is [rejected](Picker::reject).
+    pub fn pick_ranked(&mut self, ranking: impl IntoIterator<Item = Entity>) -> ComponentId {
+        self.ranking.clear();
+        for entity in ranking {
+            if self.choices.contains_key(&entity) {
+                self.ranking.push(entity);
+            }
+        }
+        self.select(self.ranking.first().copied())
+    }
+
+    /// Rejects the picked choice of the given action, picking the next-ranked choice instead,
+    /// or the default action if there are none left.
+    pub fn reject(&mut self, action: ComponentId) -> ComponentId {
+        let rejected = self.picked_entity.filter(|_| self.picked == action).or_else(|| {
+            self.ranking
+                .iter()
+                .copied()
+                .find(|entity| self.choices.get(entity) == Some(&action))
+        });
+        if let Some(rejected) = rejected {
+            self.ranking.retain(|&entity| entity != rejected);
+        }
+        self.select(self.ranking.first().copied())
+    }
+
+    /// Sets the picked action [`ComponentId`] to the choice of the score [`Entity`], or the default action.
+    fn select(&mut self, score_entity: Option<Entity>) -> ComponentId {
+        let choice = score_entity.and_then(|entity| self.choices.get(&entity).map(|&action| (entity, action)));
+        let action = choice.map_or(self.default, |(_, action)| action);
         self.picked = action;
+        self.picked_entity = choice.map(|(entity, _)| entity);
         action
     }
 
@@ -115,13 +212,57 @@ impl Picker {
     }
 }
 
+/// Marker [`Component`] for [`Score`](crate::scoring::Score) entities whose choice cannot currently be picked,
+/// such as when game logic rules it out.
+///
+/// The entity keeps being scored, but pickers skip it.
+#[derive(Component, Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[reflect(Component, PartialEq, Debug, Default)]
+pub struct Ineligible;
+
+/// Marker [`Component`] for [`Score`](crate::scoring::Score) entities whose score isn't known yet,
+/// such as while an [asynchronous](crate::async_score) task computes it.
+///
+/// Kept apart from [`Ineligible`], so that different sources of ineligibility don't clear each other's markers.
+/// The entity keeps being scored, but pickers skip it.
+#[derive(Component, Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[reflect(Component, PartialEq, Debug, Default)]
+pub struct PendingScore;
+
+/// Marker [`Component`] for [`Score`](crate::scoring::Score) entities whose choice cannot currently be picked,
+/// because its [target](crate::reservation::ReservesTarget) is fully reserved or its action is at [capacity](crate::reservation::ActionCapacity).
+///
+/// Kept apart from [`Ineligible`], so that different sources of ineligibility don't clear each other's markers.
+/// The entity keeps being scored, but pickers skip it.
+#[derive(Component, Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[reflect(Component, PartialEq, Debug, Default)]
+pub struct Reserved;
+
+/// [`QueryFilter`](bevy::ecs::query::QueryFilter) for [`Score`](crate::scoring::Score) entities whose choice can be picked,
+/// i.e. that are neither [`Ineligible`], [`PendingScore`] nor [`Reserved`].
+pub type Eligible = (Without<Ineligible>, Without<PendingScore>, Without<Reserved>);
+
+/// Marker [`Component`] for picker entities that are scored and picked on their own schedule,
+/// such as [decision layers](crate::layer) and actors with a [think rate](crate::think).
+///
+/// Their child [`Score`](crate::scoring::Score) entities are skipped by [`RunScoring::all`](crate::event::RunScoring::all),
+/// and they are skipped by [`RunPicking::all`].
+#[derive(Component, Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[reflect(Component, PartialEq, Debug, Default)]
+pub struct SelfScheduled;
+
 #[cfg(test)]
 mod tests {
+    use approx::assert_relative_eq;
     use bevy::prelude::*;
 
     use crate::{
-        event::{RunPicking, RunScoring},
-        picking::{FirstToScore, Highest, Picker},
+        event::{OnPickChanged, RejectAction, RequestAction, RunPicking, RunScoring},
+        picking::{Blend, BlendWeights, FirstToScore, Highest, Ineligible, PickResult, Picker},
         scoring::{FixedScore, Score},
     };
 
@@ -178,4 +319,188 @@ mod tests {
 
         assert_eq!(my_action, world.get::<Picker>(actor).unwrap().picked);
     }
+
+    #[test]
+    fn pick_result() {
+        #[derive(Resource, Default)]
+        struct Changes(Vec<OnPickChanged>);
+
+        let mut app = App::new();
+        app.add_plugins(crate::ObservedUtilityPlugins::TurnBased);
+        app.init_resource::<Changes>();
+        app.add_observer(|trigger: On<OnPickChanged>, mut changes: ResMut<Changes>| {
+            changes.0.push(*trigger.event());
+        });
+        let world = app.world_mut();
+
+        let my_action = world.register_component::<MyAction>();
+        let idle_action = world.register_component::<IdleAction>();
+
+        let low = world.spawn((FixedScore::new(0.2), Score::default())).id();
+        let high = world.spawn((FixedScore::new(0.7), Score::default())).id();
+        let blocked = world.spawn((FixedScore::new(0.9), Score::default(), Ineligible)).id();
+        let actor = world
+            .spawn((
+                Picker::new(idle_action)
+                    .with(low, my_action)
+                    .with(high, my_action)
+                    .with(blocked, my_action),
+                FirstToScore::new(0.5),
+                PickResult::default(),
+            ))
+            .add_children(&[low, high, blocked])
+            .id();
+
+        world.trigger(RunScoring::all());
+        world.trigger(RunPicking::entity(actor));
+        world.flush();
+
+        let result = world.get::<PickResult>(actor).unwrap();
+        let entries: Vec<_> = result
+            .entries()
+            .iter()
+            .map(|entry| (entry.entity, entry.rank, entry.eligible))
+            .collect();
+        assert_eq!(
+            entries,
+            [(high, Some(0), true), (blocked, None, false), (low, None, true)]
+        );
+        assert_eq!(result.picked_entity(), Some(high));
+        assert!(result.runner_up().is_none());
+        assert!(result.changed());
+
+        // Picking the same choice again doesn't signal a change.
+        world.trigger(RunPicking::entity(actor));
+        world.flush();
+        assert!(!world.get::<PickResult>(actor).unwrap().changed());
+
+        // Picking another choice of the same action does.
+        world.entity_mut(high).insert(FixedScore::new(0.1));
+        world.entity_mut(low).insert(FixedScore::new(0.6));
+        world.trigger(RunScoring::all());
+        world.trigger(RunPicking::entity(actor));
+        world.flush();
+        assert_eq!(world.get::<PickResult>(actor).unwrap().picked_entity(), Some(low));
+
+        let changes = &world.resource::<Changes>().0;
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].previous, None);
+        assert_eq!(changes[1].previous, Some(my_action));
+        assert_eq!(changes[1].action, my_action);
+    }
+
+    #[test]
+    fn pick_result_after_rejection() {
+        #[derive(Component)]
+        struct OtherAction;
+
+        #[derive(Resource, Default)]
+        struct Changes(Vec<OnPickChanged>);
+
+        let mut app = App::new();
+        app.add_plugins(crate::ObservedUtilityPlugins::TurnBased);
+        app.init_resource::<Changes>();
+        app.add_observer(|trigger: On<OnPickChanged>, mut changes: ResMut<Changes>| {
+            changes.0.push(*trigger.event());
+        });
+        let world = app.world_mut();
+
+        let my_action = world.register_component::<MyAction>();
+        let other_action = world.register_component::<OtherAction>();
+        let idle_action = world.register_component::<IdleAction>();
+
+        let high = world.spawn((FixedScore::new(0.7), Score::default())).id();
+        let low = world.spawn((FixedScore::new(0.4), Score::default())).id();
+        let actor = world
+            .spawn((
+                Picker::new(idle_action).with(high, my_action).with(low, other_action),
+                Highest,
+                PickResult::default(),
+            ))
+            .add_children(&[high, low])
+            .id();
+
+        world.trigger(RunScoring::all());
+        world.trigger(RunPicking::entity(actor));
+        world.flush();
+        world.trigger(RequestAction::picked(actor));
+        world.flush();
+
+        // Falling back to the next-ranked choice records it as the pick.
+        world.trigger(RejectAction {
+            entity: actor,
+            action: my_action,
+        });
+        world.flush();
+        let result = world.get::<PickResult>(actor).unwrap();
+        assert_eq!(result.picked_entity(), Some(low));
+        assert!(result.changed());
+
+        let changes = &world.resource::<Changes>().0;
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[1].previous, Some(my_action));
+        assert_eq!(changes[1].action, other_action);
+    }
+
+    #[test]
+    fn blend_weights() {
+        #[derive(Component)]
+        struct OtherAction;
+
+        let mut app = App::new();
+        app.add_plugins(crate::ObservedUtilityPlugins::TurnBased);
+        let world = app.world_mut();
+
+        let my_action = world.register_component::<MyAction>();
+        let other_action = world.register_component::<OtherAction>();
+        let idle_action = world.register_component::<IdleAction>();
+
+        let high = world.spawn((FixedScore::new(0.5), Score::default())).id();
+        let mid = world.spawn((FixedScore::new(0.3), Score::default())).id();
+        let low = world.spawn((FixedScore::new(0.2), Score::default())).id();
+        let zero = world.spawn((FixedScore::new(0.), Score::default())).id();
+        let actor = world
+            .spawn((
+                Picker::new(idle_action)
+                    .with(high, my_action)
+                    .with(mid, other_action)
+                    .with(low, my_action)
+                    .with(zero, other_action),
+                Blend::new(),
+            ))
+            .add_children(&[high, mid, low, zero])
+            .id();
+
+        world.trigger(RunScoring::all());
+        world.trigger(RunPicking::entity(actor));
+        world.flush();
+
+        let weights = world.get::<BlendWeights>(actor).unwrap();
+        assert_eq!(weights.len(), 3);
+        assert_eq!(weights.get(zero), 0.);
+        assert_relative_eq!(weights.weight_of(my_action), 0.7);
+        assert_relative_eq!(weights.weight_of(other_action), 0.3);
+        assert_eq!(world.get::<Picker>(actor).unwrap().picked_entity, Some(high));
+
+        // Keep the top 2 without renormalizing.
+        world
+            .entity_mut(actor)
+            .insert(Blend::new().with_top_k(2).with_renormalize(false));
+        world.trigger(RunPicking::entity(actor));
+        world.flush();
+        let weights = world.get::<BlendWeights>(actor).unwrap();
+        assert_eq!(
+            weights.iter().map(|(entity, _, _)| entity).collect::<Vec<_>>(),
+            [high, mid]
+        );
+        assert_relative_eq!(weights.get(mid), 0.3);
+
+        // Keep the top 2, renormalized.
+        world.entity_mut(actor).insert(Blend::new().with_top_k(2));
+        world.trigger(RunPicking::entity(actor));
+        world.flush();
+        let weights = world.get::<BlendWeights>(actor).unwrap();
+        assert_relative_eq!(weights.get(high), 0.625);
+        assert_relative_eq!(weights.get(mid), 0.375);
+    }
 }
@@ -0,0 +1,239 @@
+use bevy::{
+    ecs::{
+        component::{ComponentId, StorageType},
+        lifecycle::{ComponentHook, HookContext},
+        world::DeferredWorld,
+    },
+    prelude::*,
+};
+
+use crate::{
+    ecs::DeferredWorldExt,
+    event::{OnPick, OnPicked},
+    picking::{Eligible, Picker},
+    scoring::Score,
+};
+
+/// [`Picker`] [`Component`] that blends all choices by their [`Score`]s, for continuous outputs like steering.
+///
+/// Each pick writes the normalized weight of every choice to the actor's [`BlendWeights`],
+/// which is inserted automatically. Optionally, only the `K` highest scoring choices are kept,
+/// and their weights can be renormalized to sum to `1`.
+///
+/// The highest weighted choice is still picked, like [`Highest`](crate::picking::Highest),
+/// so discrete actions keep working alongside the blend.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_observed_utility::prelude::*;
+///
+/// # let mut app = App::new();
+/// # app.add_plugins(ObservedUtilityPlugins::RealTime);
+/// # let mut world = app.world_mut();
+/// #[derive(Component)]
+/// pub struct Steer;
+/// #[derive(Component)]
+/// pub struct Flee;
+/// #[derive(Component)]
+/// pub struct Seek;
+///
+/// let steer = world.register_component::<Steer>();
+/// let flee = world.register_component::<Flee>();
+/// let seek = world.register_component::<Seek>();
+///
+/// # let mut commands = world.commands();
+/// let danger = commands.spawn((FixedScore::new(0.75), Score::default())).id();
+/// let hunger = commands.spawn((FixedScore::new(0.25), Score::default())).id();
+///
+/// let actor = commands
+///     .spawn((
+///         Picker::new(steer).with(danger, flee).with(hunger, seek),
+///         Blend::default(),
+///     ))
+///     .add_children(&[danger, hunger])
+///     .id();
+///
+/// commands.trigger(RunScoring::all());
+/// commands.trigger(RunPicking::entity(actor));
+/// # world.flush();
+///
+/// // Blend the flee and seek vectors by utility.
+/// let weights = world.get::<BlendWeights>(actor).unwrap();
+/// assert_eq!(weights.weight_of(flee), 0.75);
+/// assert_eq!(weights.weight_of(seek), 0.25);
+/// ```
+#[derive(Reflect)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[reflect(Component, PartialEq, Debug, Default)]
+pub struct Blend {
+    /// The maximum number of choices to blend, or [`None`] to blend all choices.
+    top_k: Option<usize>,
+    /// Whether to renormalize the weights of the kept choices to sum to `1`.
+    renormalize: bool,
+}
+
+impl Default for Blend {
+    fn default() -> Self {
+        Self {
+            top_k: None,
+            renormalize: true,
+        }
+    }
+}
+
+impl Blend {
+    /// Creates a new [`Blend`] that blends all choices.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only blends the `k` highest scoring choices.
+    #[must_use]
+    pub fn with_top_k(mut self, k: usize) -> Self {
+        self.top_k = Some(k);
+        self
+    }
+
+    /// Sets whether the weights of the kept choices are renormalized to sum to `1`.
+    ///
+    /// If not, the weights are normalized over all choices, before the top `K` are kept.
+    #[must_use]
+    pub fn with_renormalize(mut self, renormalize: bool) -> Self {
+        self.renormalize = renormalize;
+        self
+    }
+
+    /// Returns the maximum number of choices to blend, or [`None`] if all choices are blended.
+    #[must_use]
+    pub fn top_k(&self) -> Option<usize> {
+        self.top_k
+    }
+
+    /// Returns `true` if the weights of the kept choices are renormalized to sum to `1`.
+    #[must_use]
+    pub fn renormalizes(&self) -> bool {
+        self.renormalize
+    }
+
+    /// Computes the normalized weights of the given choices and their scores.
+    ///
+    /// Choices are sorted by descending score, and choices without a positive score are dropped.
+    #[must_use]
+    pub fn weigh(&self, mut choices: Vec<(Entity, ComponentId, f32)>) -> Vec<(Entity, ComponentId, f32)> {
+        choices.retain(|&(_, _, score)| score > 0.);
+        // Stable, so the first of equal scores is preferred.
+        choices.sort_by(|(_, _, a), (_, _, b)| b.total_cmp(a));
+
+        let total: f32 = choices.iter().map(|&(_, _, score)| score).sum();
+        if let Some(k) = self.top_k {
+            choices.truncate(k);
+        }
+        let total = if self.renormalize {
+            choices.iter().map(|&(_, _, score)| score).sum()
+        } else {
+            total
+        };
+
+        for (_, _, weight) in choices.iter_mut() {
+            *weight /= total;
+        }
+        choices
+    }
+
+    /// [`Observer`] for the [`Blend`] [`Picker`] that weighs all choices and picks the highest weighted one.
+    fn observer(
+        trigger: On<OnPick>,
+        mut commands: Commands,
+        mut targets: Query<(Entity, &Children, &mut Picker, &Blend, &mut BlendWeights)>,
+        scores: Query<(Entity, &Score), Eligible>,
+    ) {
+        let event_entity = trigger.event().entity;
+        let Ok((target, children, mut picker, settings, mut weights)) = targets.get_mut(event_entity) else {
+            // The entity doesn't blend, or its weights haven't been inserted yet.
+            return;
+        };
+
+        let choices = scores
+            .iter_many(children)
+            .filter_map(|(score_entity, score)| {
+                let &action = picker.choices.get(&score_entity)?;
+                Some((score_entity, action, score.get()))
+            })
+            .collect();
+        weights.weights = settings.weigh(choices);
+
+        let action = picker.pick_ranked(weights.weights.iter().map(|&(entity, _, _)| entity));
+        commands.trigger(OnPicked { entity: target, action });
+    }
+}
+
+impl Component for Blend {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+    type Mutability = bevy::ecs::component::Immutable;
+
+    fn on_add() -> Option<ComponentHook> {
+        Some(|mut world: DeferredWorld, context: HookContext| {
+            #[derive(Resource, Default)]
+            struct BlendObserverSpawned;
+
+            world.once::<BlendObserverSpawned>().observe(Self::observer);
+            world
+                .commands()
+                .entity(context.entity)
+                .insert_if_new(BlendWeights::default());
+        })
+    }
+}
+
+/// [`Component`] for actor entities with a [`Blend`] [`Picker`] that holds the normalized weights of their choices.
+///
+/// See [`Blend`] for more information.
+#[derive(Component, Reflect)]
+#[derive(Clone, PartialEq, Debug, Default)]
+#[reflect(Component, PartialEq, Debug, Default)]
+pub struct BlendWeights {
+    /// The [`Score`] [`Entity`], action [`ComponentId`] and weight of each blended choice, by descending weight.
+    weights: Vec<(Entity, ComponentId, f32)>,
+}
+
+impl BlendWeights {
+    /// Returns an iterator over the [`Score`] [`Entity`], action [`ComponentId`] and weight of each blended choice,
+    /// by descending weight.
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, ComponentId, f32)> {
+        self.weights.iter().copied()
+    }
+
+    /// Returns the weight of the choice of the given [`Score`] [`Entity`], or `0` if it isn't blended.
+    #[must_use]
+    pub fn get(&self, score_entity: Entity) -> f32 {
+        self.weights
+            .iter()
+            .find(|&&(entity, _, _)| entity == score_entity)
+            .map_or(0., |&(_, _, weight)| weight)
+    }
+
+    /// Returns the total weight of all blended choices of the given action.
+    #[must_use]
+    pub fn weight_of(&self, action: ComponentId) -> f32 {
+        self.weights
+            .iter()
+            .filter(|&&(_, choice, _)| choice == action)
+            .map(|&(_, _, weight)| weight)
+            .sum()
+    }
+
+    /// Returns the number of blended choices.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.weights.len()
+    }
+
+    /// Returns `true` if no choices are blended, such as when none of them score above `0`.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.weights.is_empty()
+    }
+}
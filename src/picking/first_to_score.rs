@@ -10,7 +10,7 @@ use bevy::{
 use crate::{
     ecs::DeferredWorldExt,
     event::{OnPick, OnPicked},
-    picking::Picker,
+    picking::{Eligible, Picker},
     scoring::Score,
 };
 
@@ -92,7 +92,7 @@ impl FirstToScore {
         trigger: On<OnPick>,
         mut commands: Commands,
         mut targets: Query<(Entity, &Children, &mut Picker, &FirstToScore)>,
-        scores: Query<(Entity, &Score)>,
+        scores: Query<(Entity, &Score), Eligible>,
     ) {
         fn run(
             target: Entity,
@@ -100,18 +100,15 @@ impl FirstToScore {
             children: &Children,
             mut picker: Mut<Picker>,
             settings: &FirstToScore,
-            scores: &Query<(Entity, &Score)>,
+            scores: &Query<(Entity, &Score), Eligible>,
         ) {
-            for (score_entity, score) in scores.iter_many(children) {
-                if *score >= settings.threshold() {
-                    let action = picker.pick(Some(score_entity));
-                    commands.trigger(OnPicked { entity: target, action });
-                    return;
-                }
-            }
-
-            // If no score entity reached the threshold, pick the default action
-            let action = picker.pick(None);
+            // Rank the score entities that reached the threshold in order,
+            // if none did, the default action is picked
+            let ranking = scores
+                .iter_many(children)
+                .filter(|(_, score)| **score >= settings.threshold())
+                .map(|(score_entity, _)| score_entity);
+            let action = picker.pick_ranked(ranking);
             commands.trigger(OnPicked { entity: target, action });
         }
 
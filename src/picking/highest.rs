@@ -10,7 +10,7 @@ use bevy::{
 use crate::{
     ecs::DeferredWorldExt,
     event::{OnPick, OnPicked},
-    picking::Picker,
+    picking::{Eligible, Picker},
     scoring::Score,
 };
 
@@ -69,27 +69,23 @@ impl Highest {
         trigger: On<OnPick>,
         mut commands: Commands,
         mut targets: Query<(Entity, &Children, &mut Picker), With<Highest>>,
-        scores: Query<(Entity, &Score)>,
+        scores: Query<(Entity, &Score), Eligible>,
     ) {
         fn run(
             target: Entity,
             mut commands: Commands,
             children: &Children,
             mut picker: Mut<Picker>,
-            scores: &Query<(Entity, &Score)>,
+            scores: &Query<(Entity, &Score), Eligible>,
         ) {
-            let mut highest_score_entity: Option<(Entity, &Score)> = None;
-            for (score_entity, score) in scores.iter_many(children) {
-                if let Some((_, highest_score)) = highest_score_entity {
-                    if score.get() > highest_score.get() {
-                        highest_score_entity = Some((score_entity, score));
-                    }
-                } else {
-                    highest_score_entity = Some((score_entity, score));
-                }
-            }
+            let mut ranked: Vec<(Entity, f32)> = scores
+                .iter_many(children)
+                .map(|(score_entity, score)| (score_entity, score.get()))
+                .collect();
+            // Stable, so the first of equal scores is preferred.
+            ranked.sort_by(|(_, a), (_, b)| b.total_cmp(a));
 
-            let action = picker.pick(highest_score_entity.map(|(entity, _)| entity));
+            let action = picker.pick_ranked(ranked.into_iter().map(|(entity, _)| entity));
             commands.trigger(OnPicked { entity: target, action });
         }
 
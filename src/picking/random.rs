@@ -6,12 +6,12 @@ use bevy::{
     },
     prelude::*,
 };
-use rand::{Rng, seq::IteratorRandom};
+use rand::{Rng, seq::SliceRandom};
 
 use crate::{
     ecs::DeferredWorldExt,
     event::{OnPick, OnPicked},
-    picking::Picker,
+    picking::{Eligible, Picker},
 };
 
 /// [`Picker`] [`Component`] that picks randomly.
@@ -85,16 +85,29 @@ impl PickRandom {
         trigger: On<OnPick>,
         mut commands: Commands,
         mut targets: Query<(Entity, &mut Picker, &mut PickRandom)>,
+        eligible: Query<(), Eligible>,
     ) {
-        fn run(target: Entity, mut commands: Commands, mut picker: Mut<Picker>, settings: &mut PickRandom) {
-            let random = picker.choices.keys().choose(&mut *settings.rng()).copied();
-            let action = picker.pick(random);
+        fn run(
+            target: Entity,
+            mut commands: Commands,
+            mut picker: Mut<Picker>,
+            settings: &mut PickRandom,
+            eligible: &Query<(), Eligible>,
+        ) {
+            let mut ranking: Vec<Entity> = picker
+                .choices
+                .keys()
+                .copied()
+                .filter(|&entity| eligible.contains(entity))
+                .collect();
+            ranking.shuffle(&mut *settings.rng());
+            let action = picker.pick_ranked(ranking);
             commands.trigger(OnPicked { entity: target, action });
         }
 
         let event_entity = trigger.event().entity;
         if let Ok((target, picker, settings)) = targets.get_mut(event_entity) {
-            run(target, commands.reborrow(), picker, settings.into_inner());
+            run(target, commands.reborrow(), picker, settings.into_inner(), &eligible);
         }
     }
 }
@@ -0,0 +1,213 @@
+use bevy::{
+    ecs::{
+        component::{ComponentId, StorageType},
+        lifecycle::{ComponentHook, HookContext},
+        world::DeferredWorld,
+    },
+    prelude::*,
+};
+
+use crate::{
+    ecs::DeferredWorldExt,
+    event::{OnPickChanged, OnPicked},
+    picking::{Eligible, Picker},
+    scoring::Score,
+};
+
+/// A single choice of a pick, as recorded in a [`PickResult`].
+#[derive(Reflect)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[reflect(PartialEq, Debug)]
+pub struct PickEntry {
+    /// The [`Score`] [`Entity`] of the choice.
+    pub entity: Entity,
+    /// [`ComponentId`] of the choice's action.
+    pub action: ComponentId,
+    /// The score of the choice at the time of the pick.
+    pub score: f32,
+    /// The rank of the choice in the pick, starting at `0` for the picked choice,
+    /// or [`None`] if the picker didn't consider it, such as when it's below a threshold.
+    pub rank: Option<usize>,
+    /// Whether the choice could be picked, i.e. it matched the [`Eligible`] filter.
+    pub eligible: bool,
+}
+
+/// [`Component`] for actor entities that records the full result of their last pick.
+///
+/// Updated whenever the actor's [`Picker`] triggers [`OnPicked`], listing every choice with its score,
+/// rank and eligibility. If the picked action or choice differs from the previous pick,
+/// [`OnPickChanged`] is triggered, so systems can react to changes instead of polling [`Picker::picked`].
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_observed_utility::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Idle;
+/// #[derive(Component)]
+/// struct Eat;
+/// #[derive(Component)]
+/// struct Sleep;
+///
+/// # let mut app = App::new();
+/// # app.add_plugins(ObservedUtilityPlugins::TurnBased);
+/// # let world = app.world_mut();
+/// let idle = world.register_component::<Idle>();
+/// let eat = world.register_component::<Eat>();
+/// let sleep = world.register_component::<Sleep>();
+///
+/// let hunger = world.spawn((FixedScore::new(0.7), Score::default())).id();
+/// let tiredness = world.spawn((FixedScore::new(0.4), Score::default())).id();
+/// let actor = world
+///     .spawn((
+///         Picker::new(idle).with(hunger, eat).with(tiredness, sleep),
+///         Highest,
+///         PickResult::default(),
+///     ))
+///     .add_children(&[hunger, tiredness])
+///     .id();
+///
+/// world.trigger(RunScoring::all());
+/// world.trigger(RunPicking::entity(actor));
+/// # world.flush();
+///
+/// // Show what the actor is thinking about doing next.
+/// let result = world.get::<PickResult>(actor).unwrap();
+/// assert_eq!(result.runner_up().unwrap().action, sleep);
+/// assert!(result.changed());
+/// ```
+#[derive(Reflect)]
+#[derive(Clone, PartialEq, Debug, Default)]
+#[reflect(Component, PartialEq, Debug, Default)]
+pub struct PickResult {
+    /// Every choice of the last pick, ranked choices first, then the remaining choices by descending score.
+    entries: Vec<PickEntry>,
+    /// The action picked by the last pick, or [`None`] if nothing was picked yet.
+    picked: Option<ComponentId>,
+    /// The [`Score`] [`Entity`] picked by the last pick, or [`None`] if the default action was picked.
+    picked_entity: Option<Entity>,
+    /// The action picked by the pick before the last, or [`None`] if there was none.
+    previous: Option<ComponentId>,
+    /// Whether the last pick differs from the pick before it.
+    changed: bool,
+}
+
+impl PickResult {
+    /// Returns every choice of the last pick, ranked choices first, then the remaining choices by descending score.
+    #[must_use]
+    pub fn entries(&self) -> &[PickEntry] {
+        &self.entries
+    }
+
+    /// Returns the entry of the given [`Score`] [`Entity`], if it's a choice.
+    #[must_use]
+    pub fn get(&self, entity: Entity) -> Option<&PickEntry> {
+        self.entries.iter().find(|entry| entry.entity == entity)
+    }
+
+    /// Returns the ranked choices of the last pick, starting with the picked one.
+    pub fn ranked(&self) -> impl Iterator<Item = &PickEntry> {
+        self.entries.iter().take_while(|entry| entry.rank.is_some())
+    }
+
+    /// Returns the second-ranked choice of the last pick, if any.
+    #[must_use]
+    pub fn runner_up(&self) -> Option<&PickEntry> {
+        self.ranked().nth(1)
+    }
+
+    /// Returns the action picked by the last pick, or [`None`] if nothing was picked yet.
+    #[must_use]
+    pub fn picked(&self) -> Option<ComponentId> {
+        self.picked
+    }
+
+    /// Returns the [`Score`] [`Entity`] picked by the last pick, or [`None`] if the default action was picked.
+    #[must_use]
+    pub fn picked_entity(&self) -> Option<Entity> {
+        self.picked_entity
+    }
+
+    /// Returns the action picked by the pick before the last, or [`None`] if there was none.
+    #[must_use]
+    pub fn previous(&self) -> Option<ComponentId> {
+        self.previous
+    }
+
+    /// Returns `true` if the last pick's action or choice differs from the pick before it.
+    #[must_use]
+    pub fn changed(&self) -> bool {
+        self.changed
+    }
+
+    /// Records a pick of the [`Picker`], given the score and eligibility of each choice.
+    pub fn record(&mut self, picker: &Picker, mut score_of: impl FnMut(Entity) -> (f32, bool)) {
+        self.entries.clear();
+        for (&entity, &action) in picker.choices.iter() {
+            let (score, eligible) = score_of(entity);
+            let rank = picker.ranking.iter().position(|&ranked| ranked == entity);
+            self.entries.push(PickEntry {
+                entity,
+                action,
+                score,
+                rank,
+                eligible,
+            });
+        }
+        self.entries.sort_by(|a, b| match (a.rank, b.rank) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => b.score.total_cmp(&a.score).then(a.entity.cmp(&b.entity)),
+        });
+
+        self.changed = self.picked != Some(picker.picked) || self.picked_entity != picker.picked_entity;
+        self.previous = self.picked;
+        self.picked = Some(picker.picked);
+        self.picked_entity = picker.picked_entity;
+    }
+
+    /// [`Observer`] that records picks and triggers [`OnPickChanged`] if the pick changed.
+    fn observer(
+        trigger: On<OnPicked>,
+        mut commands: Commands,
+        mut actors: Query<(&Picker, &mut PickResult)>,
+        scores: Query<&Score>,
+        eligible: Query<(), Eligible>,
+    ) {
+        let entity = trigger.event().entity;
+        let Ok((picker, mut result)) = actors.get_mut(entity) else {
+            // The actor doesn't record its picks.
+            return;
+        };
+
+        result.record(picker, |choice| {
+            scores
+                .get(choice)
+                .map_or((0., false), |score| (score.get(), eligible.contains(choice)))
+        });
+        if result.changed {
+            commands.trigger(OnPickChanged {
+                entity,
+                previous: result.previous,
+                action: picker.picked,
+            });
+        }
+    }
+}
+
+impl Component for PickResult {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+    type Mutability = bevy::ecs::component::Mutable;
+
+    fn on_add() -> Option<ComponentHook> {
+        Some(|mut world: DeferredWorld, _context: HookContext| {
+            #[derive(Resource, Default)]
+            struct PickResultObserverSpawned;
+
+            world.once::<PickResultObserverSpawned>().observe(Self::observer);
+        })
+    }
+}
@@ -0,0 +1,458 @@
+//! Reservations stop actors from piling up on the same target or action.
+//!
+//! Targets that only a limited number of actors can act on at once, like a well, get a [`Reservable`] component.
+//! Choices that act on such a target get a [`ReservesTarget`] component on their [`Score`] entity.
+//! When the choice is initiated, the actor claims the target with a [`Reservation`],
+//! which is released automatically when the action ends or the actor is despawned.
+//!
+//! The number of actors performing an action at once can also be limited globally with the [`ActionCapacity`] resource.
+//!
+//! Choices whose target is fully reserved by other actors, or whose action is at capacity,
+//! are marked [`Reserved`] so that pickers skip them.
+//! If an actor still initiates such a choice, for example because several actors picked it in the same frame,
+//! the claim is rejected and the actor falls back to its next-best choice.
+//!
+//! Add the [`ReservationPlugin`] to handle reservations.
+//!
+//! # Example
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_observed_utility::prelude::*;
+//!
+//! #[derive(Component)]
+//! struct Idle;
+//!
+//! #[derive(Component)]
+//! struct Drink;
+//!
+//! #[derive(Component)]
+//! struct Harvest;
+//!
+//! # let mut app = App::new();
+//! # app.add_plugins(ObservedUtilityPlugins::RealTime);
+//! app.add_plugins(ReservationPlugin::default());
+//!
+//! # let world = app.world_mut();
+//! let idle = world.register_component::<Idle>();
+//! let drink = world.register_component::<Drink>();
+//! let harvest = world.register_component::<Harvest>();
+//!
+//! // At most 3 actors can harvest at once.
+//! world.resource_mut::<ActionCapacity>().set_limit(harvest, 3);
+//!
+//! // A well that only one actor can drink from at once.
+//! let well = world.spawn(Reservable::new(1)).id();
+//!
+//! // A choice to drink from the well, which reserves it when initiated.
+//! let scorer = world.spawn((FixedScore::new(0.8), Score::default(), ReservesTarget(well))).id();
+//! let actor = world
+//!     .spawn((Picker::new(idle).with(scorer, drink), Highest))
+//!     .add_child(scorer)
+//!     .id();
+//! # world.run_system_cached(ReservationPlugin::update_eligibility).unwrap();
+//! # world.flush();
+//! # assert!(world.get::<Reserved>(scorer).is_none());
+//! ```
+//!
+//! [`Score`]: crate::scoring::Score
+
+use bevy::{
+    ecs::{
+        component::{ComponentId, StorageType},
+        entity::EntityHashSet,
+        lifecycle::{ComponentHook, HookContext},
+        schedule::{InternedScheduleLabel, ScheduleLabel},
+        world::DeferredWorld,
+    },
+    platform::collections::HashMap,
+    prelude::*,
+};
+
+use crate::{
+    acting::CurrentAction,
+    event::{OnActionEnded, OnActionInitiated, RejectAction},
+    picking::{Picker, Reserved},
+};
+
+/// [`Plugin`] that claims and releases [`Reservation`]s, enforces [`ActionCapacity`] limits,
+/// and updates the [`Reserved`] marker of reserving choices in the configured [`Schedule`].
+pub struct ReservationPlugin {
+    /// The [`ScheduleLabel`] to update the eligibility of choices in.
+    pub update_in: InternedScheduleLabel,
+}
+
+impl Default for ReservationPlugin {
+    fn default() -> Self {
+        Self {
+            update_in: FixedUpdate.intern(),
+        }
+    }
+}
+
+impl Plugin for ReservationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ActionCapacity>();
+
+        app.add_systems(self.update_in, Self::update_eligibility);
+
+        app.add_observer(Self::on_action_initiated_reserve)
+            .add_observer(Self::on_action_ended_release);
+
+        app.register_type::<ActionCapacity>()
+            .register_type::<Reservable>()
+            .register_type::<ReservesTarget>()
+            .register_type::<Reservation>();
+    }
+}
+
+impl ReservationPlugin {
+    /// [`System`] that marks choices [`Reserved`] if their target is fully reserved by other actors
+    /// or their action is at capacity, and unmarks them otherwise.
+    ///
+    /// Only choices with a [`ReservesTarget`] or a limited action are updated.
+    pub fn update_eligibility(
+        mut commands: Commands,
+        capacity: Res<ActionCapacity>,
+        actors: Query<(Entity, &Picker, Option<&CurrentAction>)>,
+        reserves: Query<&ReservesTarget>,
+        targets: Query<&Reservable>,
+        reserved_choices: Query<(), With<Reserved>>,
+    ) {
+        let mut active: HashMap<ComponentId, usize> = HashMap::default();
+        for (_, _, current_action) in actors.iter() {
+            if let Some(&CurrentAction(action)) = current_action
+                && capacity.limit(action).is_some()
+            {
+                *active.entry(action).or_default() += 1;
+            }
+        }
+
+        for (actor, picker, current_action) in actors.iter() {
+            for (&score_entity, &action) in picker.choices.iter() {
+                let reserved = reserves.get(score_entity).ok();
+                let limit = capacity.limit(action);
+                if reserved.is_none() && limit.is_none() {
+                    continue;
+                }
+
+                let performing = current_action.is_some_and(|ca| ca.0 == action);
+                let others = active
+                    .get(&action)
+                    .copied()
+                    .unwrap_or_default()
+                    .saturating_sub(usize::from(performing));
+                let eligible = limit.is_none_or(|limit| others < limit)
+                    && reserved.is_none_or(|reserved| {
+                        targets
+                            .get(reserved.0)
+                            .is_ok_and(|reservable| reservable.is_available_for(actor))
+                    });
+
+                if eligible && reserved_choices.contains(score_entity) {
+                    commands.entity(score_entity).remove::<Reserved>();
+                } else if !eligible && !reserved_choices.contains(score_entity) {
+                    commands.entity(score_entity).insert(Reserved);
+                }
+            }
+        }
+    }
+
+    /// [`Observer`] that claims the target of the initiated choice and enforces [`ActionCapacity`] limits.
+    ///
+    /// If the claim fails, the choice is marked [`Reserved`] and the action is [rejected](RejectAction),
+    /// falling back to the next-best choice.
+    pub fn on_action_initiated_reserve(
+        trigger: On<OnActionInitiated>,
+        mut commands: Commands,
+        capacity: Res<ActionCapacity>,
+        actors: Query<(Entity, &Picker, Option<&CurrentAction>)>,
+        reserves: Query<&ReservesTarget>,
+        targets: Query<&Reservable>,
+    ) {
+        let actor = trigger.event().entity;
+        let action = trigger.event().action;
+        let Ok((_, picker, _)) = actors.get(actor) else {
+            return;
+        };
+        if picker.is_default(action) {
+            return;
+        }
+
+        let choice = picker.picked_entity.filter(|_| picker.picked == action);
+
+        let at_capacity = capacity.limit(action).is_some_and(|limit| {
+            let others = actors
+                .iter()
+                .filter(|&(other, _, current_action)| other != actor && current_action.is_some_and(|ca| ca.0 == action))
+                .count();
+            others >= limit
+        });
+
+        let target = choice
+            .and_then(|choice| reserves.get(choice).ok())
+            .map(|reserved| reserved.0);
+        let target_available = target.is_none_or(|target| {
+            targets
+                .get(target)
+                .is_ok_and(|reservable| reservable.is_available_for(actor))
+        });
+
+        if at_capacity || !target_available {
+            if let Some(choice) = choice {
+                commands.entity(choice).insert(Reserved);
+            }
+            commands.trigger(RejectAction { entity: actor, action });
+            return;
+        }
+
+        if let Some(target) = target {
+            commands.entity(actor).insert(Reservation { target, action });
+        }
+    }
+
+    /// [`Observer`] that releases the actor's [`Reservation`] when its action ends.
+    pub fn on_action_ended_release(trigger: On<OnActionEnded>, mut commands: Commands, actors: Query<&Reservation>) {
+        let actor = trigger.event().entity;
+        if actors
+            .get(actor)
+            .is_ok_and(|reservation| reservation.action == trigger.event().action)
+        {
+            commands.entity(actor).remove::<Reservation>();
+        }
+    }
+}
+
+/// [`Resource`] for limiting the number of actors performing an action at the same time.
+#[derive(Resource, Reflect)]
+#[derive(Clone, PartialEq, Debug, Default)]
+#[reflect(Resource, PartialEq, Debug, Default)]
+pub struct ActionCapacity {
+    /// Map of action [`ComponentId`]s to the maximum number of actors performing them.
+    limits: HashMap<ComponentId, usize>,
+}
+
+impl ActionCapacity {
+    /// Returns the maximum number of actors that can perform the action at once, if limited.
+    #[must_use]
+    pub fn limit(&self, action: ComponentId) -> Option<usize> {
+        self.limits.get(&action).copied()
+    }
+
+    /// Limits the number of actors that can perform the action at once.
+    pub fn set_limit(&mut self, action: ComponentId, limit: usize) {
+        self.limits.insert(action, limit);
+    }
+
+    /// Removes the limit for the action.
+    pub fn remove_limit(&mut self, action: ComponentId) {
+        self.limits.remove(&action);
+    }
+}
+
+/// [`Component`] for target entities that a limited number of actors can reserve at once.
+#[derive(Component, Reflect)]
+#[derive(Clone, PartialEq, Debug)]
+#[reflect(Component, PartialEq, Debug)]
+pub struct Reservable {
+    /// The number of actors that can reserve the target at once.
+    pub capacity: usize,
+    /// The actors currently holding a [`Reservation`] on the target.
+    holders: EntityHashSet,
+}
+
+impl Reservable {
+    /// Creates a new [`Reservable`] with the given capacity.
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            holders: EntityHashSet::default(),
+        }
+    }
+
+    /// Returns the actors currently holding a [`Reservation`] on the target.
+    #[must_use]
+    pub fn holders(&self) -> &EntityHashSet {
+        &self.holders
+    }
+
+    /// Returns `true` if the actor holds a reservation or can claim one.
+    #[must_use]
+    pub fn is_available_for(&self, actor: Entity) -> bool {
+        self.holders.contains(&actor) || self.holders.len() < self.capacity
+    }
+}
+
+impl Default for Reservable {
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+/// [`Component`] for [`Score`](crate::scoring::Score) entities whose choice reserves a [`Reservable`] target when initiated.
+#[derive(Component, Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[reflect(Component, PartialEq, Debug)]
+pub struct ReservesTarget(pub Entity);
+
+/// [`Component`] inserted onto the actor entity while it holds a claim on a [`Reservable`] target.
+///
+/// Removing this component, or despawning the actor, releases the claim.
+#[derive(Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[reflect(Component, PartialEq, Debug)]
+pub struct Reservation {
+    /// The reserved target entity.
+    pub target: Entity,
+    /// The action [`ComponentId`] the target is reserved for.
+    pub action: ComponentId,
+}
+
+impl Component for Reservation {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+    type Mutability = bevy::ecs::component::Immutable;
+
+    fn on_insert() -> Option<ComponentHook> {
+        Some(|mut world: DeferredWorld, context: HookContext| {
+            let Some(&reservation) = world.get::<Reservation>(context.entity) else {
+                return;
+            };
+            if let Some(mut target) = world.get_mut::<Reservable>(reservation.target) {
+                target.holders.insert(context.entity);
+            }
+        })
+    }
+
+    fn on_replace() -> Option<ComponentHook> {
+        Some(|mut world: DeferredWorld, context: HookContext| {
+            let Some(&reservation) = world.get::<Reservation>(context.entity) else {
+                return;
+            };
+            if let Some(mut target) = world.get_mut::<Reservable>(reservation.target) {
+                target.holders.remove(&context.entity);
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::component::ComponentId, prelude::*};
+
+    use crate::{
+        acting::CurrentAction,
+        event::{OnActionEnded, RequestAction, RunPicking, RunScoring},
+        picking::{Highest, Ineligible, Picker, Reserved},
+        reservation::{ActionCapacity, Reservable, Reservation, ReservationPlugin, ReservesTarget},
+        scoring::{FixedScore, Score},
+    };
+
+    #[derive(Component)]
+    struct Idle;
+
+    #[derive(Component)]
+    struct Drink;
+
+    #[derive(Component)]
+    struct Harvest;
+
+    fn spawn_actor(
+        world: &mut World,
+        idle: ComponentId,
+        action: ComponentId,
+        target: Option<Entity>,
+    ) -> (Entity, Entity) {
+        let scorer = world.spawn((FixedScore::new(0.8), Score::default())).id();
+        if let Some(target) = target {
+            world.entity_mut(scorer).insert(ReservesTarget(target));
+        }
+        let actor = world
+            .spawn((Picker::new(idle).with(scorer, action), Highest))
+            .add_child(scorer)
+            .id();
+        (actor, scorer)
+    }
+
+    fn pick_and_request(world: &mut World, actor: Entity) {
+        world.run_system_cached(ReservationPlugin::update_eligibility).unwrap();
+        world.flush();
+        world.trigger(RunScoring::all());
+        world.trigger(RunPicking::entity(actor));
+        world.flush();
+        world.trigger(RequestAction::picked(actor));
+        world.flush();
+    }
+
+    #[test]
+    fn reserve_target() {
+        let mut app = App::new();
+        app.add_plugins((crate::ObservedUtilityPlugins::TurnBased, ReservationPlugin::default()));
+        let world = app.world_mut();
+
+        let idle = world.register_component::<Idle>();
+        let drink = world.register_component::<Drink>();
+
+        let well = world.spawn(Reservable::new(1)).id();
+        let (first, _) = spawn_actor(world, idle, drink, Some(well));
+        let (second, second_scorer) = spawn_actor(world, idle, drink, Some(well));
+
+        pick_and_request(world, first);
+        assert_eq!(world.get::<Reservation>(first).unwrap().target, well);
+        assert!(world.get::<Reservable>(well).unwrap().holders().contains(&first));
+
+        // The well is reserved, so the second actor can't pick it.
+        pick_and_request(world, second);
+        assert!(world.get::<Reserved>(second_scorer).is_some());
+        assert_eq!(world.get::<CurrentAction>(second).unwrap().0, idle);
+
+        // Ending the action releases the well.
+        world.trigger(OnActionEnded::completed(first, drink));
+        world.flush();
+        assert!(world.get::<Reservation>(first).is_none());
+        assert!(world.get::<Reservable>(well).unwrap().holders().is_empty());
+
+        world.run_system_cached(ReservationPlugin::update_eligibility).unwrap();
+        world.flush();
+        assert!(world.get::<Reserved>(second_scorer).is_none());
+
+        // Despawning the holder releases the well too.
+        pick_and_request(world, second);
+        assert!(world.get::<Reservable>(well).unwrap().holders().contains(&second));
+        world.despawn(second);
+        assert!(world.get::<Reservable>(well).unwrap().holders().is_empty());
+    }
+
+    #[test]
+    fn action_capacity() {
+        let mut app = App::new();
+        app.add_plugins((crate::ObservedUtilityPlugins::TurnBased, ReservationPlugin::default()));
+        let world = app.world_mut();
+
+        let idle = world.register_component::<Idle>();
+        let harvest = world.register_component::<Harvest>();
+        world.resource_mut::<ActionCapacity>().set_limit(harvest, 1);
+
+        let (first, first_scorer) = spawn_actor(world, idle, harvest, None);
+        let (second, second_scorer) = spawn_actor(world, idle, harvest, None);
+
+        // Both actors pick to harvest in the same frame, but only one gets to.
+        world.trigger(RunScoring::all());
+        world.trigger(RunPicking::all());
+        world.flush();
+        world.trigger(RequestAction::picked(first));
+        world.trigger(RequestAction::picked(second));
+        world.flush();
+
+        assert_eq!(world.get::<CurrentAction>(first).unwrap().0, harvest);
+        assert_eq!(world.get::<CurrentAction>(second).unwrap().0, idle);
+        assert!(world.get::<Reserved>(second_scorer).is_some());
+
+        // Updating eligibility leaves markers from other sources alone.
+        world.entity_mut(first_scorer).insert(Ineligible);
+        world.run_system_cached(ReservationPlugin::update_eligibility).unwrap();
+        world.flush();
+        assert!(world.get::<Ineligible>(first_scorer).is_some());
+        assert!(world.get::<Reserved>(first_scorer).is_none());
+    }
+}
@@ -0,0 +1,436 @@
+//! Routines score actions by time of day, such as working from 9 to 17 and sleeping from 22 to 6.
+//!
+//! The time of day is kept by the [`GameClock`] resource, which can be scaled or paused independently of [`Time`].
+//! A [`Routine`] scorer scores high inside its [`RoutineWindow`]s, ramping in and out at their edges.
+//!
+//! Routines are regular [`Score`] entities, so they combine with other considerations through the usual aggregators.
+//! For example, a [`Measured`] [`WeightedMax`] of a sleep routine and a [`NeedScore`] for energy
+//! lets an exhausted actor nap in the afternoon, while a rested one still goes to bed at night.
+//!
+//! Add the [`RoutinePlugin`] to advance the clock.
+//!
+//! # Example
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_observed_utility::prelude::*;
+//!
+//! # let mut app = App::new();
+//! # app.add_plugins(ObservedUtilityPlugins::RealTime);
+//! // One in-game hour passes every real minute, starting at 8 in the morning.
+//! app.add_plugins(RoutinePlugin::default())
+//!     .insert_resource(GameClock::new(8.).with_scale(1. / 60.));
+//!
+//! # let world = app.world_mut();
+//! // Work from 9 to 17, ramping in over the hour before and out over the hour after.
+//! let work = world
+//!     .spawn((Routine::new(RoutineWindow::new(9., 17.).with_ramps(1., 1.)), Score::default()))
+//!     .id();
+//! # world.trigger(RunScoring::entity(work));
+//! # world.flush();
+//! // At 8, the ramp in to work is just starting.
+//! assert_eq!(world.get::<Score>(work).unwrap().get(), 0.);
+//! ```
+//!
+//! [`Measured`]: crate::scoring::Measured
+//! [`WeightedMax`]: crate::scoring::WeightedMax
+//! [`NeedScore`]: crate::needs::NeedScore
+
+use bevy::{
+    ecs::{
+        component::StorageType,
+        lifecycle::{ComponentHook, HookContext},
+        schedule::{InternedScheduleLabel, ScheduleLabel},
+        world::DeferredWorld,
+    },
+    prelude::*,
+};
+
+use crate::{ecs::DeferredWorldExt, event::OnScore, scoring::Score};
+
+/// The number of hours in a [`GameClock`] day.
+pub const HOURS_PER_DAY: f32 = 24.;
+
+/// [`Plugin`] that advances the [`GameClock`] in the configured [`Schedule`].
+pub struct RoutinePlugin {
+    /// The [`ScheduleLabel`] to advance the clock in.
+    pub update_in: InternedScheduleLabel,
+}
+
+impl Default for RoutinePlugin {
+    fn default() -> Self {
+        Self {
+            update_in: FixedUpdate.intern(),
+        }
+    }
+}
+
+impl Plugin for RoutinePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameClock>();
+
+        app.add_systems(self.update_in, Self::advance_clock);
+
+        app.register_type::<GameClock>()
+            .register_type::<RoutineWindow>()
+            .register_type::<Routine>();
+    }
+}
+
+impl RoutinePlugin {
+    /// [`System`] that advances the [`GameClock`] by the [`Time`] delta.
+    pub fn advance_clock(time: Res<Time>, mut clock: ResMut<GameClock>) {
+        if !clock.paused {
+            clock.advance(time.delta_secs());
+        }
+    }
+}
+
+/// [`Resource`] for the in-game time of day.
+#[derive(Resource, Reflect)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[reflect(Resource, PartialEq, Debug, Default)]
+pub struct GameClock {
+    /// The hour of the day, from `0` up to [`HOURS_PER_DAY`].
+    hour: f32,
+    /// The number of days that have passed.
+    day: u32,
+    /// The number of in-game hours that pass per real second.
+    scale: f32,
+    /// Whether the clock is paused.
+    paused: bool,
+}
+
+impl Default for GameClock {
+    fn default() -> Self {
+        Self::new(0.)
+    }
+}
+
+impl GameClock {
+    /// Creates a new [`GameClock`] starting at the given hour, with one in-game hour passing every real second.
+    #[must_use]
+    pub fn new(hour: f32) -> Self {
+        Self {
+            hour: hour.rem_euclid(HOURS_PER_DAY),
+            day: 0,
+            scale: 1.,
+            paused: false,
+        }
+    }
+
+    /// Sets the number of in-game hours that pass per real second.
+    #[must_use]
+    pub fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Returns the hour of the day, from `0` up to [`HOURS_PER_DAY`].
+    #[must_use]
+    pub fn hour(&self) -> f32 {
+        self.hour
+    }
+
+    /// Sets the hour of the day, without changing the day.
+    pub fn set_hour(&mut self, hour: f32) {
+        self.hour = hour.rem_euclid(HOURS_PER_DAY);
+    }
+
+    /// Returns the number of days that have passed.
+    #[must_use]
+    pub fn day(&self) -> u32 {
+        self.day
+    }
+
+    /// Returns the number of in-game hours that pass per real second.
+    #[must_use]
+    pub fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    /// Sets the number of in-game hours that pass per real second.
+    pub fn set_scale(&mut self, scale: f32) {
+        self.scale = scale;
+    }
+
+    /// Returns `true` if the clock is paused.
+    #[must_use]
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Pauses the clock.
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Resumes the clock.
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    /// Advances the clock by the given number of real seconds, scaled, wrapping into the next day(s).
+    pub fn advance(&mut self, seconds: f32) {
+        let hours = self.hour + seconds * self.scale;
+        #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let days = (hours / HOURS_PER_DAY).floor().max(0.) as u32;
+        self.day += days;
+        self.hour = hours.rem_euclid(HOURS_PER_DAY);
+    }
+}
+
+/// A window of hours in a [`Routine`], which may wrap past midnight.
+///
+/// Inside the window the routine scores the maximum. Before the start, it ramps in from the minimum,
+/// and after the end, it ramps out back down to the minimum.
+#[derive(Reflect)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[reflect(PartialEq, Debug)]
+pub struct RoutineWindow {
+    /// The hour the window starts.
+    pub start: f32,
+    /// The hour the window ends, or `start + 24` if the window covers the whole day.
+    pub end: f32,
+    /// The number of hours before the start to ramp in over.
+    pub ramp_in: f32,
+    /// The number of hours after the end to ramp out over.
+    pub ramp_out: f32,
+}
+
+impl RoutineWindow {
+    /// Creates a new [`RoutineWindow`] from the start hour to the end hour, without ramps.
+    ///
+    /// If the end is before the start, the window wraps past midnight.
+    /// If the window spans 24 hours or more, it covers the whole day.
+    #[must_use]
+    pub fn new(start: f32, end: f32) -> Self {
+        let wrapped_start = start.rem_euclid(HOURS_PER_DAY);
+        Self {
+            start: wrapped_start,
+            end: if end - start >= HOURS_PER_DAY {
+                wrapped_start + HOURS_PER_DAY
+            } else {
+                end.rem_euclid(HOURS_PER_DAY)
+            },
+            ramp_in: 0.,
+            ramp_out: 0.,
+        }
+    }
+
+    /// Sets the number of hours to ramp in before the start and ramp out after the end.
+    #[must_use]
+    pub fn with_ramps(mut self, ramp_in: f32, ramp_out: f32) -> Self {
+        self.ramp_in = ramp_in.max(0.);
+        self.ramp_out = ramp_out.max(0.);
+        self
+    }
+
+    /// Returns the score of the window at the given hour, from `0` to `1`.
+    #[must_use]
+    pub fn evaluate(&self, hour: f32) -> f32 {
+        let length = if self.end - self.start >= HOURS_PER_DAY {
+            HOURS_PER_DAY
+        } else {
+            (self.end - self.start).rem_euclid(HOURS_PER_DAY)
+        };
+        let since_start = (hour - self.start).rem_euclid(HOURS_PER_DAY);
+        if since_start < length {
+            return 1.;
+        }
+
+        let until_start = (self.start - hour).rem_euclid(HOURS_PER_DAY);
+        let since_end = (hour - self.end).rem_euclid(HOURS_PER_DAY);
+        let ramp_in = if until_start < self.ramp_in {
+            1. - until_start / self.ramp_in
+        } else {
+            0.
+        };
+        let ramp_out = if since_end < self.ramp_out {
+            1. - since_end / self.ramp_out
+        } else {
+            0.
+        };
+        ramp_in.max(ramp_out)
+    }
+}
+
+/// [`Score`] [`Component`] that scores high inside its [`RoutineWindow`]s, according to the [`GameClock`].
+///
+/// If the windows overlap, the highest score wins.
+#[derive(Reflect)]
+#[derive(Clone, PartialEq, Debug)]
+#[reflect(Component, PartialEq, Debug)]
+pub struct Routine {
+    /// The windows to score high in.
+    windows: Vec<RoutineWindow>,
+}
+
+impl Routine {
+    /// Creates a new [`Routine`] with a single window.
+    #[must_use]
+    pub fn new(window: RoutineWindow) -> Self {
+        Self { windows: vec![window] }
+    }
+
+    /// Adds another window.
+    #[must_use]
+    pub fn with(mut self, window: RoutineWindow) -> Self {
+        self.windows.push(window);
+        self
+    }
+
+    /// Returns the windows to score high in.
+    #[must_use]
+    pub fn windows(&self) -> &[RoutineWindow] {
+        &self.windows
+    }
+
+    /// Returns the score of the routine at the given hour, from `0` to `1`.
+    #[must_use]
+    pub fn evaluate(&self, hour: f32) -> f32 {
+        self.windows
+            .iter()
+            .map(|window| window.evaluate(hour))
+            .fold(0., f32::max)
+    }
+
+    /// [`Observer`] for [`Routine`] [`Score`] entities that scores the time of day.
+    fn observer(trigger: On<OnScore>, clock: Option<Res<GameClock>>, mut target: Query<(&mut Score, &Routine)>) {
+        let entity = trigger.event().entity;
+        let Ok((mut actor_score, settings)) = target.get_mut(entity) else {
+            // The entity is not scoring for routine.
+            return;
+        };
+
+        let Some(clock) = clock else {
+            // If there is no clock, set the score to the minimum.
+            *actor_score = Score::MIN;
+            return;
+        };
+
+        *actor_score = Score::new(settings.evaluate(clock.hour()));
+    }
+}
+
+impl Component for Routine {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+    type Mutability = bevy::ecs::component::Immutable;
+
+    fn on_add() -> Option<ComponentHook> {
+        Some(|mut world: DeferredWorld, _context: HookContext| {
+            #[derive(Resource, Default)]
+            struct RoutineObserverSpawned;
+
+            world.once::<RoutineObserverSpawned>().observe(Self::observer);
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+    use bevy::prelude::*;
+
+    use crate::{
+        event::RunScoring,
+        needs::{Need, NeedScore, Needs},
+        routine::{GameClock, Routine, RoutinePlugin, RoutineWindow},
+        scoring::{Measured, Score, WeightedMax},
+    };
+
+    #[test]
+    fn windows_and_ramps() {
+        let work = RoutineWindow::new(9., 17.).with_ramps(1., 2.);
+        assert_relative_eq!(work.evaluate(7.), 0.);
+        assert_relative_eq!(work.evaluate(8.5), 0.5);
+        assert_relative_eq!(work.evaluate(12.), 1.);
+        assert_relative_eq!(work.evaluate(18.), 0.5);
+        assert_relative_eq!(work.evaluate(20.), 0.);
+
+        // Sleep wraps past midnight.
+        let sleep = RoutineWindow::new(22., 6.).with_ramps(2., 0.);
+        assert_relative_eq!(sleep.evaluate(21.), 0.5);
+        assert_relative_eq!(sleep.evaluate(23.), 1.);
+        assert_relative_eq!(sleep.evaluate(3.), 1.);
+        assert_relative_eq!(sleep.evaluate(6.), 0.);
+
+        // A window spanning the whole day scores the maximum all day.
+        for all_day in [RoutineWindow::new(0., 24.), RoutineWindow::new(6., 30.)] {
+            for hour in [0., 6., 12., 23.5] {
+                assert_relative_eq!(all_day.evaluate(hour), 1.);
+            }
+        }
+        // An empty window doesn't.
+        assert_relative_eq!(RoutineWindow::new(12., 12.).evaluate(12.), 0.);
+    }
+
+    #[test]
+    fn clock_scale_and_pause() {
+        let mut clock = GameClock::new(23.).with_scale(0.5);
+        clock.advance(4.);
+        assert_relative_eq!(clock.hour(), 1.);
+        assert_eq!(clock.day(), 1);
+
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        world.insert_resource(GameClock::new(12.));
+        world.resource_mut::<GameClock>().pause();
+        world
+            .resource_mut::<Time>()
+            .advance_by(std::time::Duration::from_secs(3));
+        world.run_system_cached(RoutinePlugin::advance_clock).unwrap();
+        assert_relative_eq!(world.resource::<GameClock>().hour(), 12.);
+
+        world.resource_mut::<GameClock>().resume();
+        world.run_system_cached(RoutinePlugin::advance_clock).unwrap();
+        assert_relative_eq!(world.resource::<GameClock>().hour(), 15.);
+    }
+
+    #[test]
+    fn combine_with_needs() {
+        let mut app = App::new();
+        app.add_plugins((crate::ObservedUtilityPlugins::TurnBased, RoutinePlugin::default()));
+        app.insert_resource(GameClock::new(14.));
+        let world = app.world_mut();
+
+        let sleep = world
+            .spawn((Measured::new(WeightedMax), Score::default()))
+            .with_children(|parent| {
+                parent.spawn((Routine::new(RoutineWindow::new(22., 6.)), Score::default()));
+                parent.spawn((NeedScore::new("tiredness"), Score::default()));
+            })
+            .id();
+        let actor = world
+            .spawn(Needs::default().with("tiredness", Need::new(1.).with_value(30.)))
+            .add_child(sleep)
+            .id();
+
+        world.trigger(RunScoring::entity(sleep));
+        world.flush();
+        assert_relative_eq!(world.get::<Score>(sleep).unwrap().get(), 0.3);
+
+        // An exhausted actor naps in the afternoon.
+        world
+            .get_mut::<Needs>(actor)
+            .unwrap()
+            .get_mut("tiredness")
+            .unwrap()
+            .value = 90.;
+        world.trigger(RunScoring::entity(sleep));
+        world.flush();
+        assert_relative_eq!(world.get::<Score>(sleep).unwrap().get(), 0.9);
+
+        // A rested actor still goes to bed at night.
+        world
+            .get_mut::<Needs>(actor)
+            .unwrap()
+            .get_mut("tiredness")
+            .unwrap()
+            .value = 0.;
+        world.resource_mut::<GameClock>().set_hour(23.);
+        world.trigger(RunScoring::entity(sleep));
+        world.flush();
+        assert_relative_eq!(world.get::<Score>(sleep).unwrap().get(), 1.);
+    }
+}
@@ -3,14 +3,20 @@
 //! # Provided [`Score`] implementations
 //!
 //! - [`AllOrNothing`]: Scores the sum of all child scores, but only if the sum reaches a certain threshold. Otherwise, the score is 0.
+//! - [`CountInRadius`]: Scores the number of matching entities within a radius of the actor.
+//! - [`DistanceToNearest`]: Scores the distance from the actor to the nearest matching entity.
 //! - [`Evaluated`]: Scores a single child entity based on an [`Evaluator`] function. See the struct docs for the list of provided evaluators.
+//! - [`FacingTarget`]: Scores the angle between the actor's facing direction and a target entity.
 //! - [`FixedScore`]: Scores a fixed value.
 //! - [`Measured`]: Scores all child entities based on a [`Measure`] function. See the struct docs for the list of provided measures.
 //! - [`Product`]: Scores the product of all child scores.
 //! - [`Random`] (requires `rand` feature): Scores a random value, optionally within a range.
 //! - [`Sum`]: Scores the sum of all child scores.
+//! - [`TacticalPoints`]: Scores the best of a set of candidate positions generated around the actor.
 //! - [`Winning`]: Scores the highest child score.
 //!
+//! [`Score`] entities with a [`ScoreCache`] keep their last score, skipping their whole subtree, until their interval elapses.
+//!
 //! # Provided [`Observer`] utilities
 //!
 //! - [`score_ancestor`]: Does the busy work of scoring a child entity based on its closest ancestor entity with a given component.
@@ -20,31 +26,43 @@ use std::{
     ops::{Bound, RangeBounds},
 };
 
-use bevy::prelude::*;
+use bevy::{
+    ecs::{component::ComponentId, lifecycle::HookContext, system::SystemParam, world::DeferredWorld},
+    platform::collections::HashSet,
+    prelude::*,
+};
 
 use crate::{
     ecs::{AncestorQuery, DFSPostTraversal},
-    event::{OnScore, RunScoring},
+    event::{InvalidateScoreCache, OnScore, OnScorePostProcess, RunScoring},
+    pause::{AiPaused, GlobalAiPause},
+    picking::SelfScheduled,
 };
 
 mod all_or_nothing;
+mod cache;
 mod evaluator;
 mod fixed;
 mod measured;
 mod product;
 #[cfg(feature = "rand")]
 mod random;
+mod spatial;
 mod sum;
+mod tactical;
 mod winning;
 
 pub use self::all_or_nothing::*;
+pub use self::cache::*;
 pub use self::evaluator::*;
 pub use self::fixed::*;
 pub use self::measured::*;
 pub use self::product::*;
 #[cfg(feature = "rand")]
 pub use self::random::*;
+pub use self::spatial::*;
 pub use self::sum::*;
+pub use self::tactical::*;
 pub use self::winning::*;
 
 /// [`Plugin`] for scoring entities.
@@ -56,6 +74,8 @@ impl Plugin for ScoringPlugin {
         app.add_observer(Self::run_scoring_post_order_dfs);
 
         app.register_type::<Score>()
+            .register_type::<PostProcessed>()
+            .register_type::<ScoreCache>()
             .register_type::<AllOrNothing>()
             // .register_type::<Evaluated>() // TODO: Implement reflection for Evaluated
             .register_type::<LinearEvaluator>()
@@ -72,56 +92,136 @@ impl Plugin for ScoringPlugin {
             .register_type::<WeightedRMS>()
             .register_type::<Product>()
             .register_type::<Sum>()
-            .register_type::<Winning>();
+            .register_type::<Winning>()
+            // .register_type::<TacticalPoints>() // TODO: Implement reflection for TacticalPoints
+            .register_type::<TacticalThreat>()
+            .register_type::<TacticalTarget>()
+            .register_type::<RingGenerator>()
+            .register_type::<GridGenerator>()
+            .register_type::<DistanceToOrigin>()
+            .register_type::<DistanceToNearestThreat>()
+            .register_type::<FacingTarget>();
 
         // Note: RandomScore cannot be reflected due to the boxed Rng trait object
 
-        app.register_type::<RunScoring>().register_type::<OnScore>();
+        app.register_type::<RunScoring>()
+            .register_type::<InvalidateScoreCache>()
+            .register_type::<OnScore>()
+            .register_type::<OnScorePostProcess>();
     }
 }
 
 impl ScoringPlugin {
     /// For each scoreable root entity, perform post-order depth-first traversal,
-    /// triggering [`OnScore`] for each entity on the way back up.
+    /// triggering [`OnScore`] for each entity on the way back up, followed by [`OnScorePostProcess`]
+    /// if the entity or its parent is [`PostProcessed`].
+    ///
+    /// Roots of actors whose AI is [paused](crate::pause) are skipped,
+    /// as are roots of [`SelfScheduled`] pickers when scoring globally.
+    /// Subtrees of fresh [`ScoreCache`]s are skipped, unless the event [bypasses](RunScoring::bypassing_caches) them.
     pub fn run_scoring_post_order_dfs(
         trigger: On<RunScoring>,
         mut commands: Commands,
         scoreable_roots: Query<(Entity, Option<&ChildOf>), With<Score>>,
-        root_parents: Query<(), Without<Score>>,
         mut dfs: DFSPostTraversal<With<Score>>,
+        mut checks: ScoringChecks,
     ) {
-        fn trigger_in_order(root: Entity, mut commands: Commands, dfs: &mut DFSPostTraversal<With<Score>>) {
-            let sorted = dfs.iter(root);
+        if checks.is_globally_paused() {
+            return;
+        }
+
+        fn trigger_in_order(
+            root: Entity,
+            bypass_caches: bool,
+            mut commands: Commands,
+            dfs: &mut DFSPostTraversal<With<Score>>,
+            checks: &mut ScoringChecks,
+        ) {
+            let sorted = dfs.iter_pruned(root, |entity| {
+                if bypass_caches {
+                    checks.caches.refresh(entity);
+                    false
+                } else {
+                    checks.caches.prune(entity)
+                }
+            });
+            let post_processed = |entity| {
+                checks.post_processed.get(entity).is_ok_and(|(marked, parent)| {
+                    marked
+                        || parent.is_some_and(|parent| {
+                            checks
+                                .post_processed
+                                .get(parent.parent())
+                                .is_ok_and(|(marked, _)| marked)
+                        })
+                })
+            };
 
             for entity in sorted {
                 commands.trigger(OnScore { entity });
+                if post_processed(entity) {
+                    commands.trigger(OnScorePostProcess { entity });
+                }
             }
         }
 
+        let bypass_caches = trigger.event().bypass_caches;
         if let Some(targeted_root) = trigger.event().entity {
+            if checks.is_paused(targeted_root) {
+                return;
+            }
             // Do scoring for the given entity
-            trigger_in_order(targeted_root, commands.reborrow(), &mut dfs);
+            trigger_in_order(targeted_root, bypass_caches, commands.reborrow(), &mut dfs, &mut checks);
         } else {
             // Do scoring globally
             // Find all score entities that have no parents at all, or whose parents are not score entities
-            let roots = scoreable_roots.iter().filter_map(|(entity, parent)| {
-                if let Some(parent) = parent {
-                    if root_parents.contains(parent.parent()) {
-                        Some(entity)
-                    } else {
-                        None
-                    }
-                } else {
-                    Some(entity)
+            // nor self-scheduled pickers, which are scored on their own schedule
+            for (root, parent) in scoreable_roots.iter() {
+                if !checks.is_global_root(parent) || checks.is_paused(root) {
+                    continue;
                 }
-            });
-            for root in roots {
-                trigger_in_order(root, commands.reborrow(), &mut dfs);
+                trigger_in_order(root, bypass_caches, commands.reborrow(), &mut dfs, &mut checks);
             }
         }
     }
 }
 
+/// [`SystemParam`] for deciding which score trees and entities [`RunScoring`] skips,
+/// and which entities it post-processes.
+#[derive(SystemParam)]
+pub struct ScoringChecks<'w, 's> {
+    global: Option<Res<'w, GlobalAiPause>>,
+    paused: AncestorQuery<'w, 's, &'static AiPaused>,
+    root_parents: Query<'w, 's, Has<SelfScheduled>, Without<Score>>,
+    post_processed: Query<'w, 's, (Has<PostProcessed>, Option<&'static ChildOf>)>,
+    caches: ScoreCaches<'w, 's>,
+}
+
+impl ScoringChecks<'_, '_> {
+    /// Returns `true` if the AI of all actors is paused by the [`GlobalAiPause`].
+    #[must_use]
+    pub fn is_globally_paused(&self) -> bool {
+        self.global.as_ref().is_some_and(|global| global.is_paused())
+    }
+
+    /// Returns `true` if the entity belongs to an actor whose AI is [`AiPaused`].
+    #[must_use]
+    pub fn is_paused(&mut self, entity: Entity) -> bool {
+        self.paused.entity(entity).is_ok()
+    }
+
+    /// Returns `true` if a [`Score`] entity with the given parent is the root of a score tree when scoring globally,
+    /// i.e. its parent is neither a [`Score`] entity nor a [`SelfScheduled`] picker.
+    #[must_use]
+    pub fn is_global_root(&self, parent: Option<&ChildOf>) -> bool {
+        parent.is_none_or(|parent| {
+            self.root_parents
+                .get(parent.parent())
+                .is_ok_and(|self_scheduled| !self_scheduled)
+        })
+    }
+}
+
 /// [`Component`] for an entity's score for a given score type, ranging from 0 to 1.
 #[derive(Component, Reflect)]
 #[derive(Clone, Copy, PartialEq, PartialOrd, Debug, Default)]
@@ -231,6 +331,51 @@ impl std::iter::Sum<Score> for f32 {
     }
 }
 
+/// Marker [`Component`] for entities whose [`Score`], or whose children's [`Score`]s,
+/// are adjusted by [`OnScorePostProcess`] observers.
+///
+/// [`OnScorePostProcess`] is only triggered for entities that are marked or whose parent is marked.
+/// Post-processing components such as [`TraitModulated`](crate::personality::TraitModulated),
+/// [`ScoreModifiers`](crate::modifier::ScoreModifiers) and [`RepetitionPenalty`](crate::history::RepetitionPenalty)
+/// insert it themselves,
+/// and remove it again once none of them is left on the entity;
+/// custom post-processing observers need it inserted by hand.
+#[derive(Component, Reflect)]
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+#[reflect(Component, PartialEq, Debug, Default)]
+pub struct PostProcessed;
+
+impl PostProcessed {
+    /// Inserts [`PostProcessed`] from the `on_add` hook of a post-processing component.
+    pub(crate) fn insert_for(world: &mut DeferredWorld, context: HookContext) {
+        let component_id = context.component_id;
+        world.commands().entity(context.entity).insert(PostProcessed);
+        world.commands().queue(move |world: &mut World| {
+            world.get_resource_or_init::<PostProcessors>().0.insert(component_id);
+        });
+    }
+
+    /// Removes [`PostProcessed`] from the `on_remove` hook of a post-processing component,
+    /// unless another post-processing component is left on the entity.
+    pub(crate) fn remove_for(world: &mut DeferredWorld, context: HookContext) {
+        let entity = context.entity;
+        world.commands().queue(move |world: &mut World| {
+            let processors = world.get_resource_or_init::<PostProcessors>().0.clone();
+            let Ok(mut entity) = world.get_entity_mut(entity) else {
+                // The entity was despawned.
+                return;
+            };
+            if !processors.iter().any(|&id| entity.contains_id(id)) {
+                entity.remove::<PostProcessed>();
+            }
+        });
+    }
+}
+
+/// [`Resource`] for the [`ComponentId`]s of components that insert [`PostProcessed`].
+#[derive(Resource, Default)]
+struct PostProcessors(HashSet<ComponentId>);
+
 // TODO: implement Reflect when Bound is reflectable
 /// A range of [`Score`]s.
 #[derive(Clone, Copy, PartialEq, Debug)]
@@ -384,14 +529,23 @@ pub fn score_ancestor<T: Component, ScoreMarker: Component>(
 
 #[cfg(test)]
 mod tests {
+    use std::f32::consts::PI;
+
     use approx::assert_relative_eq;
-    use bevy::app::App;
+    use bevy::{
+        app::App,
+        ecs::{component::Component, observer::On, query::With, resource::Resource, system::ResMut},
+        math::{Quat, Vec3},
+        transform::{TransformPlugin, components::Transform},
+    };
 
     use crate::{
-        event::RunScoring,
+        event::{OnScorePostProcess, RunScoring},
         scoring::{
-            AllOrNothing, Evaluated, FixedScore, Measured, PowerEvaluator, Product, Score, ScoringPlugin, Sum,
-            Weighted, WeightedMax, WeightedProduct, WeightedRMS, WeightedSum, Winning,
+            AllOrNothing, CountInRadius, DistanceToNearest, DistanceToNearestThreat, DistanceToOrigin, Evaluated,
+            FacingTarget, FixedScore, GridGenerator, LinearEvaluator, Measured, PostProcessed, PowerEvaluator, Product,
+            Score, ScoringPlugin, Sum, TacticalPoints, TacticalThreat, Weighted, WeightedMax, WeightedProduct,
+            WeightedRMS, WeightedSum, Winning,
         },
     };
 
@@ -609,4 +763,142 @@ mod tests {
             "Parent score should be 0.9."
         );
     }
+
+    #[test]
+    fn tactical_points() {
+        let mut app = App::new();
+        app.add_plugins((ScoringPlugin, TransformPlugin));
+
+        let world = app.world_mut();
+
+        world.spawn((TacticalThreat, Transform::from_xyz(0., 0., 3.)));
+
+        let scorer = world
+            .spawn((
+                Score::default(),
+                TacticalPoints::new(GridGenerator::new(1., 2), WeightedProduct)
+                    .with(DistanceToNearestThreat, LinearEvaluator::from_range(0., 20.))
+                    .with(DistanceToOrigin, LinearEvaluator::from_range(5., 0.)),
+            ))
+            .id();
+        world.spawn((TacticalThreat, Transform::default())).add_child(scorer);
+        app.update();
+
+        let world = app.world_mut();
+        world.trigger(RunScoring::entity(scorer));
+        world.flush();
+
+        let tactical = world.get::<TacticalPoints>(scorer).unwrap();
+        // The actor is a threat itself, but it should not be considered.
+        assert_eq!(Some(Vec3::new(0., 0., -1.)), tactical.best());
+        assert_relative_eq!(0.16, world.get::<Score>(scorer).unwrap().get());
+    }
+
+    #[test]
+    fn spatial() {
+        #[derive(Component)]
+        struct Enemy;
+
+        let mut app = App::new();
+        app.add_plugins((ScoringPlugin, TransformPlugin));
+
+        let world = app.world_mut();
+
+        world.spawn((Enemy, Transform::from_xyz(3., 0., 4.)));
+        world.spawn((Enemy, Transform::from_xyz(0., 8., 0.)));
+        world.spawn(Transform::from_xyz(1., 0., 0.));
+
+        let nearest = world
+            .spawn((Score::default(), DistanceToNearest::<With<Enemy>>::new(0., 10.)))
+            .id();
+        let count = world
+            .spawn((Score::default(), CountInRadius::<With<Enemy>>::new(6., 0., 4.)))
+            .id();
+        world
+            .spawn((Enemy, Transform::default()))
+            .add_children(&[nearest, count]);
+        app.update();
+
+        let world = app.world_mut();
+        world.trigger(RunScoring::entity(nearest));
+        world.trigger(RunScoring::entity(count));
+        world.flush();
+
+        assert_relative_eq!(0.5, world.get::<Score>(nearest).unwrap().get());
+        assert_relative_eq!(0.25, world.get::<Score>(count).unwrap().get());
+    }
+
+    #[test]
+    fn facing_target() {
+        let mut app = App::new();
+        app.add_plugins((ScoringPlugin, TransformPlugin));
+
+        let world = app.world_mut();
+
+        // The actor is turned around by its parent, and the target is moved by its own parent.
+        let target = world.spawn(Transform::default()).id();
+        world.spawn(Transform::from_xyz(0., 0., 10.)).add_child(target);
+        let facing = world.spawn((Score::default(), FacingTarget::new(target, 0., PI))).id();
+        let actor = world.spawn(Transform::default()).add_child(facing).id();
+        world
+            .spawn(Transform::from_rotation(Quat::from_rotation_y(PI)))
+            .add_child(actor);
+        app.update();
+
+        let world = app.world_mut();
+        world.trigger(RunScoring::entity(facing));
+        world.flush();
+        // Facing +Z in world space, towards the target.
+        assert_relative_eq!(0., world.get::<Score>(facing).unwrap().get(), epsilon = 1e-4);
+
+        world.entity_mut(target).insert(Transform::from_xyz(10., 0., -10.));
+        app.update();
+
+        let world = app.world_mut();
+        world.trigger(RunScoring::entity(facing));
+        world.flush();
+        assert_relative_eq!(0.5, world.get::<Score>(facing).unwrap().get(), epsilon = 1e-4);
+
+        world.entity_mut(target).despawn();
+        world.trigger(RunScoring::entity(facing));
+        world.flush();
+        assert_relative_eq!(1., world.get::<Score>(facing).unwrap().get());
+    }
+
+    #[test]
+    fn post_process_only_marked() {
+        #[derive(Resource, Default)]
+        struct PostProcessedCount(usize);
+
+        let mut app = App::new();
+        app.add_plugins(ScoringPlugin);
+        app.init_resource::<PostProcessedCount>();
+        app.add_observer(
+            |_trigger: On<OnScorePostProcess>, mut count: ResMut<PostProcessedCount>| {
+                count.0 += 1;
+            },
+        );
+
+        let world = app.world_mut();
+
+        let unmarked = world.spawn((Score::default(), FixedScore::new(0.5))).id();
+        let marked = world
+            .spawn((Score::default(), FixedScore::new(0.5), PostProcessed))
+            .id();
+        let child = world.spawn((Score::default(), FixedScore::new(0.5))).id();
+        world.spawn(PostProcessed).add_child(child);
+        world.flush();
+
+        world.trigger(RunScoring::entity(unmarked));
+        world.flush();
+        assert_eq!(0, world.resource::<PostProcessedCount>().0);
+
+        world.trigger(RunScoring::entity(marked));
+        world.flush();
+        assert_eq!(1, world.resource::<PostProcessedCount>().0);
+
+        world.trigger(RunScoring::entity(child));
+        world.flush();
+        assert_eq!(2, world.resource::<PostProcessedCount>().0);
+    }
 }
@@ -0,0 +1,231 @@
+use bevy::{
+    ecs::{
+        component::StorageType,
+        lifecycle::{ComponentHook, HookContext},
+        system::SystemParam,
+        world::DeferredWorld,
+    },
+    prelude::*,
+};
+
+use crate::{ecs::DeferredWorldExt, event::InvalidateScoreCache};
+
+/// [`Component`] for [`Score`](crate::scoring::Score) entities that are expensive to score,
+/// and only need to be rescored every `interval` seconds.
+///
+/// Until the interval has elapsed since the entity was last scored, [`RunScoring`](crate::event::RunScoring)
+/// skips the entity and its whole subtree, keeping their last [`Score`](crate::scoring::Score)s.
+/// Trigger [`InvalidateScoreCache`] to rescore the entity on the next [`RunScoring`](crate::event::RunScoring),
+/// or [bypass](crate::event::RunScoring::bypassing_caches) caches to rescore right away,
+/// as an [`Interrupt`](crate::event::Interrupt) does.
+///
+/// The interval is measured in [`Time`]. Without a [`Time`] resource, the entity is rescored every time.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_observed_utility::prelude::*;
+///
+/// # let mut app = App::new();
+/// # app.add_plugins(ObservedUtilityPlugins::TurnBased);
+/// # app.init_resource::<Time>();
+/// # let mut world = app.world_mut();
+/// // A line-of-sight check only needs to be rescored every half second.
+/// let line_of_sight = world
+///     .spawn((FixedScore::new(0.7), Score::default(), ScoreCache::new(0.5)))
+///     .id();
+///
+/// world.trigger(RunScoring::entity(line_of_sight));
+/// # world.flush();
+/// # assert_eq!(world.get::<Score>(line_of_sight).unwrap().get(), 0.7);
+///
+/// // The target moved behind a wall, so rescore it right away.
+/// world.trigger(InvalidateScoreCache { entity: line_of_sight });
+/// world.trigger(RunScoring::entity(line_of_sight));
+/// # world.flush();
+/// ```
+#[derive(Reflect)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[reflect(Component, PartialEq, Debug)]
+pub struct ScoreCache {
+    /// The number of seconds to keep the last score for.
+    interval: f32,
+    /// The elapsed [`Time`], in seconds, the entity was last scored at, or [`None`] if it needs to be rescored.
+    scored_at: Option<f64>,
+}
+
+impl ScoreCache {
+    /// Creates a new [`ScoreCache`] that keeps the last score for `interval` seconds.
+    #[must_use]
+    pub fn new(interval: f32) -> Self {
+        Self {
+            interval: interval.max(0.),
+            scored_at: None,
+        }
+    }
+
+    /// Returns the number of seconds to keep the last score for.
+    #[must_use]
+    pub fn interval(&self) -> f32 {
+        self.interval
+    }
+
+    /// Returns `true` if the last score is still fresh at the given time, in seconds.
+    #[must_use]
+    pub fn is_fresh(&self, now: f64) -> bool {
+        self.scored_at
+            .is_some_and(|scored_at| now - scored_at < f64::from(self.interval))
+    }
+
+    /// Records that the entity was scored at the given time, in seconds.
+    pub fn refresh(&mut self, now: f64) {
+        self.scored_at = Some(now);
+    }
+
+    /// Makes the entity be rescored on the next [`RunScoring`](crate::event::RunScoring).
+    pub fn invalidate(&mut self) {
+        self.scored_at = None;
+    }
+
+    /// [`Observer`] that invalidates caches for [`InvalidateScoreCache`] events.
+    fn on_invalidate(trigger: On<InvalidateScoreCache>, mut caches: Query<&mut ScoreCache>) {
+        let entity = trigger.event().entity;
+        let Ok(mut cache) = caches.get_mut(entity) else {
+            // The entity isn't cached.
+            return;
+        };
+
+        cache.invalidate();
+    }
+}
+
+impl Component for ScoreCache {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+    type Mutability = bevy::ecs::component::Mutable;
+
+    fn on_add() -> Option<ComponentHook> {
+        Some(|mut world: DeferredWorld, _context: HookContext| {
+            #[derive(Resource, Default)]
+            struct ScoreCacheObserverSpawned;
+
+            world.once::<ScoreCacheObserverSpawned>().observe(Self::on_invalidate);
+        })
+    }
+}
+
+/// [`SystemParam`] for checking and refreshing [`ScoreCache`]s while traversing score trees.
+#[derive(SystemParam)]
+pub struct ScoreCaches<'w, 's> {
+    time: Option<Res<'w, Time>>,
+    caches: Query<'w, 's, &'static mut ScoreCache>,
+}
+
+impl ScoreCaches<'_, '_> {
+    /// Returns `true` if the entity's cached score is still fresh, so it and its subtree should be skipped.
+    ///
+    /// Otherwise, the entity is about to be scored, so its cache is refreshed.
+    /// Without a [`Time`] resource, caches are never fresh.
+    pub fn prune(&mut self, entity: Entity) -> bool {
+        let Ok(mut cache) = self.caches.get_mut(entity) else {
+            return false;
+        };
+        let Some(time) = &self.time else {
+            // The interval can't be measured.
+            return false;
+        };
+
+        let now = time.elapsed_secs_f64();
+        if cache.is_fresh(now) {
+            return true;
+        }
+        cache.refresh(now);
+        false
+    }
+
+    /// Refreshes the entity's cache, as it's about to be scored regardless of its freshness.
+    pub fn refresh(&mut self, entity: Entity) {
+        if let Ok(mut cache) = self.caches.get_mut(entity)
+            && let Some(time) = &self.time
+        {
+            cache.refresh(time.elapsed_secs_f64());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::prelude::*;
+
+    use crate::{
+        event::{InvalidateScoreCache, RunScoring},
+        scoring::{FixedScore, Score, ScoreCache, Sum},
+    };
+
+    #[test]
+    fn skip_cached_subtree() {
+        let mut app = App::new();
+        app.add_plugins(crate::ObservedUtilityPlugins::TurnBased);
+        app.init_resource::<Time>();
+        let world = app.world_mut();
+
+        let child = world.spawn((FixedScore::new(0.25), Score::default())).id();
+        let cached = world
+            .spawn((Sum::default(), Score::default(), ScoreCache::new(1.)))
+            .add_child(child)
+            .id();
+        let root = world.spawn((Sum::default(), Score::default())).add_child(cached).id();
+        let score = |world: &World, entity: Entity| world.get::<Score>(entity).unwrap().get();
+        let run = |world: &mut World| {
+            world.trigger(RunScoring::all());
+            world.flush();
+        };
+
+        run(world);
+        assert_eq!(score(world, root), 0.25);
+
+        // The cached subtree keeps its last score until the interval elapses.
+        world.entity_mut(child).insert(FixedScore::new(0.5));
+        world.resource_mut::<Time>().advance_by(Duration::from_millis(500));
+        run(world);
+        assert_eq!(score(world, child), 0.25);
+        assert_eq!(score(world, root), 0.25);
+
+        world.resource_mut::<Time>().advance_by(Duration::from_millis(500));
+        run(world);
+        assert_eq!(score(world, root), 0.5);
+
+        // Invalidating rescores it right away.
+        world.entity_mut(child).insert(FixedScore::new(0.75));
+        world.trigger(InvalidateScoreCache { entity: cached });
+        run(world);
+        assert_eq!(score(world, root), 0.75);
+
+        // Bypassing caches rescores right away too, and restarts the interval.
+        world.entity_mut(child).insert(FixedScore::new(1.));
+        world.trigger(RunScoring::all().bypassing_caches());
+        world.flush();
+        assert_eq!(score(world, root), 1.);
+        assert!(world.get::<ScoreCache>(cached).unwrap().is_fresh(1.));
+    }
+
+    #[test]
+    fn never_fresh_without_time() {
+        let mut app = App::new();
+        app.add_plugins(crate::ObservedUtilityPlugins::TurnBased);
+        let world = app.world_mut();
+
+        let cached = world
+            .spawn((FixedScore::new(0.25), Score::default(), ScoreCache::new(1.)))
+            .id();
+        world.trigger(RunScoring::entity(cached));
+        world.flush();
+
+        world.entity_mut(cached).insert(FixedScore::new(0.5));
+        world.trigger(RunScoring::entity(cached));
+        world.flush();
+        assert_eq!(world.get::<Score>(cached).unwrap().get(), 0.5);
+    }
+}
@@ -0,0 +1,378 @@
+use std::marker::PhantomData;
+
+use bevy::{
+    ecs::{
+        component::StorageType,
+        lifecycle::{ComponentHook, HookContext},
+        query::QueryFilter,
+        world::DeferredWorld,
+    },
+    prelude::*,
+};
+
+use crate::{
+    ecs::{AncestorQuery, DeferredWorldExt},
+    event::OnScore,
+    scoring::{Evaluator, LinearEvaluator, Score},
+};
+
+/// Normalizes a raw value from the `[min, max]` range into a [`Score`].
+fn normalize(value: f32, min: f32, max: f32) -> Score {
+    Score::new(LinearEvaluator::from_range(min, max).evaluate(value))
+}
+
+/// [`Resource`] marking that the observer for a generic spatial [`Score`] [`Component`] has been spawned.
+struct SpatialObserverSpawned<T>(PhantomData<fn() -> T>);
+
+impl<T: 'static> Resource for SpatialObserverSpawned<T> {}
+
+impl<T> Default for SpatialObserverSpawned<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+/// [`Score`] [`Component`] that scores the distance from the actor to the nearest entity matching the filter `F`.
+///
+/// The actor's position is read from the [`GlobalTransform`] of the closest ancestor entity that has one.
+/// The distance is normalized from the `[min, max]` range, so nearer entities score lower.
+/// If there are no matching entities, the score is the maximum.
+/// Use [`Evaluated`](crate::scoring::Evaluated) with a [`LinearEvaluator::not`] to score nearer entities higher.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_observed_utility::prelude::*;
+/// # use approx::assert_relative_eq;
+///
+/// #[derive(Component)]
+/// struct Enemy;
+///
+/// # let mut app = App::new();
+/// # app.add_plugins((ObservedUtilityPlugins::RealTime, TransformPlugin));
+/// # let mut world = app.world_mut();
+/// # let mut commands = world.commands();
+/// commands.spawn((Enemy, Transform::from_xyz(5., 0., 0.)));
+///
+/// let scorer = commands
+///     .spawn((DistanceToNearest::<With<Enemy>>::new(0., 10.), Score::default()))
+///     .id();
+///
+/// commands.spawn(Transform::default()).add_child(scorer);
+/// # world.flush();
+/// # app.update();
+/// # let world = app.world_mut();
+/// # world.trigger(RunScoring::entity(scorer));
+/// # world.flush();
+/// # assert_relative_eq!(world.get::<Score>(scorer).unwrap().get(), 0.5);
+/// ```
+pub struct DistanceToNearest<F: QueryFilter + 'static = ()> {
+    /// The distance that scores the minimum.
+    min: f32,
+    /// The distance that scores the maximum.
+    max: f32,
+    _filter: PhantomData<fn() -> F>,
+}
+
+impl<F: QueryFilter + 'static> DistanceToNearest<F> {
+    /// Creates a new [`DistanceToNearest`] that normalizes distances from the `[min, max]` range.
+    #[must_use]
+    pub fn new(min: f32, max: f32) -> Self {
+        Self {
+            min,
+            max,
+            _filter: PhantomData,
+        }
+    }
+
+    /// Returns the distance that scores the minimum.
+    #[must_use]
+    pub fn min(&self) -> f32 {
+        self.min
+    }
+
+    /// Returns the distance that scores the maximum.
+    #[must_use]
+    pub fn max(&self) -> f32 {
+        self.max
+    }
+
+    /// [`Observer`] for [`DistanceToNearest`] [`Score`] entities that scores the distance to the nearest entity.
+    fn observer(
+        trigger: On<OnScore>,
+        mut target: Query<(&mut Score, &DistanceToNearest<F>)>,
+        mut actors: AncestorQuery<&'static GlobalTransform>,
+        others: Query<(Entity, &GlobalTransform), F>,
+    ) {
+        let entity = trigger.event().entity;
+        let Ok((mut actor_score, settings)) = target.get_mut(entity) else {
+            // The entity is not scoring for distance to nearest.
+            return;
+        };
+
+        let (Ok(actor), Ok(&origin)) = (actors.entity(entity), actors.get(entity)) else {
+            // If there is no actor, set the score to the minimum.
+            *actor_score = Score::MIN;
+            return;
+        };
+
+        let nearest = others
+            .iter()
+            .filter(|(other, _)| *other != actor && *other != entity)
+            .map(|(_, transform)| transform.translation().distance(origin.translation()))
+            .fold(f32::INFINITY, f32::min);
+
+        *actor_score = normalize(nearest, settings.min, settings.max);
+    }
+}
+
+impl<F: QueryFilter + 'static> Component for DistanceToNearest<F> {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+    type Mutability = bevy::ecs::component::Immutable;
+
+    fn on_add() -> Option<ComponentHook> {
+        Some(|mut world: DeferredWorld, _context: HookContext| {
+            world.once::<SpatialObserverSpawned<Self>>().observe(Self::observer);
+        })
+    }
+}
+
+/// [`Score`] [`Component`] that scores the number of entities matching the filter `F` within a radius of the actor.
+///
+/// The actor's position is read from the [`GlobalTransform`] of the closest ancestor entity that has one.
+/// The count is normalized from the `[min, max]` range.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_observed_utility::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Ally;
+///
+/// # let mut app = App::new();
+/// # app.add_plugins((ObservedUtilityPlugins::RealTime, TransformPlugin));
+/// # let mut world = app.world_mut();
+/// # let mut commands = world.commands();
+/// commands.spawn((Ally, Transform::from_xyz(1., 0., 0.)));
+/// commands.spawn((Ally, Transform::from_xyz(100., 0., 0.)));
+///
+/// // Feel safe with 2 or more allies within 10 units.
+/// let scorer = commands
+///     .spawn((CountInRadius::<With<Ally>>::new(10., 0., 2.), Score::default()))
+///     .id();
+///
+/// commands.spawn(Transform::default()).add_child(scorer);
+/// # world.flush();
+/// # app.update();
+/// # let world = app.world_mut();
+/// # world.trigger(RunScoring::entity(scorer));
+/// # world.flush();
+/// # assert_eq!(world.get::<Score>(scorer).unwrap().get(), 0.5);
+/// ```
+pub struct CountInRadius<F: QueryFilter + 'static = ()> {
+    /// The radius around the actor to count entities in.
+    radius: f32,
+    /// The count that scores the minimum.
+    min: f32,
+    /// The count that scores the maximum.
+    max: f32,
+    _filter: PhantomData<fn() -> F>,
+}
+
+impl<F: QueryFilter + 'static> CountInRadius<F> {
+    /// Creates a new [`CountInRadius`] that normalizes the count within the radius from the `[min, max]` range.
+    #[must_use]
+    pub fn new(radius: f32, min: f32, max: f32) -> Self {
+        Self {
+            radius,
+            min,
+            max,
+            _filter: PhantomData,
+        }
+    }
+
+    /// Returns the radius around the actor to count entities in.
+    #[must_use]
+    pub fn radius(&self) -> f32 {
+        self.radius
+    }
+
+    /// Returns the count that scores the minimum.
+    #[must_use]
+    pub fn min(&self) -> f32 {
+        self.min
+    }
+
+    /// Returns the count that scores the maximum.
+    #[must_use]
+    pub fn max(&self) -> f32 {
+        self.max
+    }
+
+    /// [`Observer`] for [`CountInRadius`] [`Score`] entities that scores the number of entities within the radius.
+    fn observer(
+        trigger: On<OnScore>,
+        mut target: Query<(&mut Score, &CountInRadius<F>)>,
+        mut actors: AncestorQuery<&'static GlobalTransform>,
+        others: Query<(Entity, &GlobalTransform), F>,
+    ) {
+        let entity = trigger.event().entity;
+        let Ok((mut actor_score, settings)) = target.get_mut(entity) else {
+            // The entity is not scoring for count in radius.
+            return;
+        };
+
+        let (Ok(actor), Ok(&origin)) = (actors.entity(entity), actors.get(entity)) else {
+            // If there is no actor, set the score to the minimum.
+            *actor_score = Score::MIN;
+            return;
+        };
+
+        let radius_squared = settings.radius * settings.radius;
+        let count = others
+            .iter()
+            .filter(|(other, _)| *other != actor && *other != entity)
+            .filter(|(_, transform)| transform.translation().distance_squared(origin.translation()) <= radius_squared)
+            .count();
+
+        *actor_score = normalize(count as f32, settings.min, settings.max);
+    }
+}
+
+impl<F: QueryFilter + 'static> Component for CountInRadius<F> {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+    type Mutability = bevy::ecs::component::Immutable;
+
+    fn on_add() -> Option<ComponentHook> {
+        Some(|mut world: DeferredWorld, _context: HookContext| {
+            world.once::<SpatialObserverSpawned<Self>>().observe(Self::observer);
+        })
+    }
+}
+
+/// [`Score`] [`Component`] that scores the angle between the actor's facing direction and the direction to a target entity.
+///
+/// The actor's position and rotation are read from the [`GlobalTransform`] of the closest ancestor entity that has one.
+/// The angle, in radians, is normalized from the `[min, max]` range, so facing the target directly scores lower.
+/// If the target has no [`GlobalTransform`], the score is the maximum.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_observed_utility::prelude::*;
+/// # use approx::assert_relative_eq;
+///
+/// # let mut app = App::new();
+/// # app.add_plugins((ObservedUtilityPlugins::RealTime, TransformPlugin));
+/// # let mut world = app.world_mut();
+/// # let mut commands = world.commands();
+/// // Directly to the right of the actor, who is looking down -Z.
+/// let target = commands.spawn(Transform::from_xyz(1., 0., 0.)).id();
+///
+/// let scorer = commands
+///     .spawn((FacingTarget::new(target, 0., std::f32::consts::PI), Score::default()))
+///     .id();
+///
+/// commands.spawn(Transform::default()).add_child(scorer);
+/// # world.flush();
+/// # app.update();
+/// # let world = app.world_mut();
+/// # world.trigger(RunScoring::entity(scorer));
+/// # world.flush();
+/// # assert_relative_eq!(world.get::<Score>(scorer).unwrap().get(), 0.5);
+/// ```
+#[derive(Reflect)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[reflect(Component, PartialEq, Debug)]
+pub struct FacingTarget {
+    /// The entity to measure the facing angle to.
+    target: Entity,
+    /// The angle that scores the minimum.
+    min: f32,
+    /// The angle that scores the maximum.
+    max: f32,
+    /// The actor's local facing direction.
+    forward: Vec3,
+}
+
+impl FacingTarget {
+    /// Creates a new [`FacingTarget`] that normalizes angles, in radians, from the `[min, max]` range.
+    ///
+    /// The actor faces its local [`Vec3::NEG_Z`] direction, see [`FacingTarget::with_forward`].
+    #[must_use]
+    pub fn new(target: Entity, min: f32, max: f32) -> Self {
+        Self {
+            target,
+            min,
+            max,
+            forward: Vec3::NEG_Z,
+        }
+    }
+
+    /// Sets the actor's local facing direction, such as [`Vec3::Y`] for 2D games.
+    #[must_use]
+    pub fn with_forward(mut self, forward: Vec3) -> Self {
+        self.forward = forward;
+        self
+    }
+
+    /// Returns the entity to measure the facing angle to.
+    #[must_use]
+    pub fn target(&self) -> Entity {
+        self.target
+    }
+
+    /// [`Observer`] for [`FacingTarget`] [`Score`] entities that scores the angle to the target.
+    fn observer(
+        trigger: On<OnScore>,
+        mut target: Query<(&mut Score, &FacingTarget)>,
+        mut actors: AncestorQuery<&'static GlobalTransform>,
+        transforms: Query<&GlobalTransform>,
+    ) {
+        let entity = trigger.event().entity;
+        let Ok((mut actor_score, settings)) = target.get_mut(entity) else {
+            // The entity is not scoring for facing target.
+            return;
+        };
+
+        let Ok(&origin) = actors.get(entity) else {
+            // If there is no actor, set the score to the minimum.
+            *actor_score = Score::MIN;
+            return;
+        };
+
+        let Ok(target) = transforms.get(settings.target) else {
+            // If there is no target, set the score to the maximum.
+            *actor_score = Score::MAX;
+            return;
+        };
+
+        let forward = origin.rotation() * settings.forward;
+        let to_target = target.translation() - origin.translation();
+        let angle = if to_target == Vec3::ZERO {
+            0.
+        } else {
+            forward.angle_between(to_target)
+        };
+
+        *actor_score = normalize(angle, settings.min, settings.max);
+    }
+}
+
+impl Component for FacingTarget {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+    type Mutability = bevy::ecs::component::Immutable;
+
+    fn on_add() -> Option<ComponentHook> {
+        Some(|mut world: DeferredWorld, _context: HookContext| {
+            #[derive(Resource, Default)]
+            struct FacingTargetObserverSpawned;
+
+            world.once::<FacingTargetObserverSpawned>().observe(Self::observer);
+        })
+    }
+}
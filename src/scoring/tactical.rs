@@ -0,0 +1,446 @@
+use bevy::{
+    ecs::{
+        component::StorageType,
+        lifecycle::{ComponentHook, HookContext},
+        world::DeferredWorld,
+    },
+    prelude::*,
+};
+
+use crate::{
+    ecs::{AncestorQuery, DeferredWorldExt},
+    event::{OnActionInitiated, OnScore},
+    picking::Picker,
+    scoring::{Evaluator, Measure, Score, Weighted},
+};
+
+/// [`Score`] [`Component`] that generates candidate positions around the actor and scores each one
+/// with a list of [`PointConsideration`]s, combined by a [`Measure`].
+///
+/// The actor's position is read from the [`GlobalTransform`] of the closest ancestor entity that has one.
+/// The score entity scores the value of the best candidate point, which is stored and can be read with
+/// [`TacticalPoints::best`]. When the choice is picked and its action is initiated,
+/// the best point is inserted onto the actor as a [`TacticalTarget`].
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_observed_utility::prelude::*;
+///
+/// # let mut app = App::new();
+/// # app.add_plugins((ObservedUtilityPlugins::RealTime, TransformPlugin));
+/// # let mut world = app.world_mut();
+/// # let mut commands = world.commands();
+/// // Something to take cover from.
+/// commands.spawn((TacticalThreat, Transform::from_xyz(10., 0., 0.)));
+///
+/// let cover = commands
+///     .spawn((
+///         // Check 8 points on a ring of radius 5 around the actor.
+///         TacticalPoints::new(RingGenerator::new(5., 8), WeightedProduct)
+///             // The further from the nearest threat the better, up to 20 units away.
+///             .with(DistanceToNearestThreat, LinearEvaluator::from_range(0., 20.)),
+///         Score::default(),
+///     ))
+///     .id();
+///
+/// commands.spawn(Transform::default()).add_child(cover);
+/// # world.flush();
+/// # app.update();
+/// # let world = app.world_mut();
+/// # world.trigger(RunScoring::entity(cover));
+/// # world.flush();
+/// # let best = world.get::<TacticalPoints>(cover).unwrap().best().unwrap();
+/// # assert!(best.x < -4.9);
+/// ```
+pub struct TacticalPoints {
+    /// The generator of candidate points.
+    generator: Box<dyn PointGenerator>,
+    /// The considerations each candidate point is scored with.
+    considerations: Vec<Consideration>,
+    /// The measure combining the considerations of a single candidate point.
+    measure: Box<dyn Measure>,
+    /// The best point and its score, from the last time this entity was scored.
+    best: Option<(Vec3, Score)>,
+}
+
+impl TacticalPoints {
+    /// Creates a new [`TacticalPoints`] with the given [`PointGenerator`] and [`Measure`].
+    #[must_use]
+    pub fn new(generator: impl PointGenerator, measure: impl Measure) -> Self {
+        Self {
+            generator: Box::new(generator),
+            considerations: Vec::new(),
+            measure: Box::new(measure),
+            best: None,
+        }
+    }
+
+    /// Adds a fully [`Weighted`] [`PointConsideration`], normalized into a [`Score`] with the given [`Evaluator`].
+    #[must_use]
+    pub fn with(self, consideration: impl PointConsideration, evaluator: impl Evaluator) -> Self {
+        self.with_weighted(consideration, evaluator, Weighted::MAX)
+    }
+
+    /// Adds a [`Weighted`] [`PointConsideration`], normalized into a [`Score`] with the given [`Evaluator`].
+    #[must_use]
+    pub fn with_weighted(
+        mut self,
+        consideration: impl PointConsideration,
+        evaluator: impl Evaluator,
+        weight: Weighted,
+    ) -> Self {
+        self.considerations.push(Consideration {
+            consideration: Box::new(consideration),
+            evaluator: Box::new(evaluator),
+            weight,
+        });
+        self
+    }
+
+    /// Returns the [`PointGenerator`] used for generating candidate points.
+    #[must_use]
+    pub fn generator(&self) -> &dyn PointGenerator {
+        self.generator.as_ref()
+    }
+
+    /// Sets the [`PointGenerator`] used for generating candidate points.
+    pub fn set_generator(&mut self, generator: impl PointGenerator) {
+        self.generator = Box::new(generator);
+    }
+
+    /// Returns the best point from the last time this entity was scored, if any points were generated.
+    #[must_use]
+    pub fn best(&self) -> Option<Vec3> {
+        self.best.map(|(point, _)| point)
+    }
+
+    /// Returns the [`Score`] of the best point from the last time this entity was scored.
+    #[must_use]
+    pub fn best_score(&self) -> Score {
+        self.best.map_or(Score::MIN, |(_, score)| score)
+    }
+
+    /// Scores a single candidate point with all considerations.
+    #[must_use]
+    pub fn score_point(&self, point: Vec3, context: &PointContext) -> Score {
+        self.score_point_with(point, context, &mut Vec::new())
+    }
+
+    /// Scores a single candidate point with all considerations, reusing the `scores` buffer.
+    fn score_point_with(&self, point: Vec3, context: &PointContext, scores: &mut Vec<(Score, Weighted)>) -> Score {
+        scores.clear();
+        scores.extend(self.considerations.iter().map(|c| {
+            let value = c.consideration.consider(point, context);
+            (Score::new(c.evaluator.evaluate(value)), c.weight)
+        }));
+        self.measure
+            .calculate(scores.iter().map(|(score, weight)| (score, weight)).collect())
+    }
+
+    /// [`Observer`] for [`TacticalPoints`] [`Score`] entities that scores the best candidate point.
+    fn observer(
+        trigger: On<OnScore>,
+        mut target: Query<(&mut Score, &mut TacticalPoints)>,
+        mut origins: AncestorQuery<&'static GlobalTransform>,
+        threats: Query<(Entity, &GlobalTransform), With<TacticalThreat>>,
+        entities: Query<EntityRef<'static>, Without<Score>>,
+        mut buffers: Local<TacticalBuffers>,
+    ) {
+        let entity = trigger.event().entity;
+        let Ok((mut actor_score, mut settings)) = target.get_mut(entity) else {
+            // The entity is not scoring for tactical points.
+            return;
+        };
+
+        let Ok(actor) = origins.entity(entity) else {
+            // There is no actor to generate points around.
+            settings.best = None;
+            *actor_score = Score::MIN;
+            return;
+        };
+        let origin = origins
+            .get(entity)
+            .map_or(Vec3::ZERO, |transform| transform.translation());
+
+        let TacticalBuffers {
+            points,
+            threats: threat_positions,
+            scores,
+        } = &mut *buffers;
+        threat_positions.clear();
+        threat_positions.extend(
+            threats
+                .iter()
+                .filter(|(threat, _)| *threat != actor)
+                .map(|(_, transform)| transform.translation()),
+        );
+        let context = PointContext {
+            origin,
+            threats: threat_positions,
+            entities: &entities,
+        };
+
+        points.clear();
+        settings.generator.generate(origin, points);
+
+        let mut best: Option<(Vec3, Score)> = None;
+        for &point in points.iter() {
+            let score = settings.score_point_with(point, &context, scores);
+            if best.is_none_or(|(_, best_score)| score > best_score) {
+                best = Some((point, score));
+            }
+        }
+
+        settings.best = best;
+        *actor_score = settings.best_score();
+    }
+
+    /// [`Observer`] that inserts the best point of the picked [`TacticalPoints`] choice onto the actor
+    /// as a [`TacticalTarget`] when its action is initiated.
+    fn on_action_initiated(
+        trigger: On<OnActionInitiated>,
+        mut commands: Commands,
+        actors: Query<&Picker>,
+        tactical: Query<&TacticalPoints>,
+    ) {
+        let actor = trigger.event().entity;
+        let Ok(picker) = actors.get(actor) else {
+            return;
+        };
+
+        let best = picker
+            .picked_entity
+            .filter(|_| picker.picked == trigger.event().action)
+            .and_then(|entity| tactical.get(entity).ok())
+            .and_then(TacticalPoints::best);
+
+        if let Some(point) = best {
+            commands.entity(actor).insert(TacticalTarget(point));
+        } else {
+            commands.entity(actor).remove::<TacticalTarget>();
+        }
+    }
+}
+
+impl Component for TacticalPoints {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+    type Mutability = bevy::ecs::component::Mutable;
+
+    fn on_add() -> Option<ComponentHook> {
+        Some(|mut world: DeferredWorld, _context: HookContext| {
+            #[derive(Resource, Default)]
+            struct TacticalPointsObserverSpawned;
+
+            world.once::<TacticalPointsObserverSpawned>().observe(Self::observer);
+
+            #[derive(Resource, Default)]
+            struct TacticalTargetObserverSpawned;
+
+            world
+                .once::<TacticalTargetObserverSpawned>()
+                .observe(Self::on_action_initiated);
+        })
+    }
+}
+
+/// Buffers reused across [`TacticalPoints`] scoring, to avoid allocating for every score entity.
+#[derive(Default)]
+struct TacticalBuffers {
+    /// The candidate points.
+    points: Vec<Vec3>,
+    /// The positions of all threats, excluding the actor.
+    threats: Vec<Vec3>,
+    /// The considerations' scores of a single candidate point.
+    scores: Vec<(Score, Weighted)>,
+}
+
+/// A single [`PointConsideration`] of [`TacticalPoints`], with its normalization and weight.
+struct Consideration {
+    consideration: Box<dyn PointConsideration>,
+    evaluator: Box<dyn Evaluator>,
+    weight: Weighted,
+}
+
+/// Marker [`Component`] for entities whose [`GlobalTransform`] is considered a threat by [`TacticalPoints`].
+#[derive(Component, Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[reflect(Component, PartialEq, Debug, Default)]
+pub struct TacticalThreat;
+
+/// [`Component`] inserted onto the actor entity when a [`TacticalPoints`] choice is picked and initiated,
+/// holding the best candidate point.
+#[derive(Component, Reflect)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[reflect(Component, PartialEq, Debug)]
+pub struct TacticalTarget(pub Vec3);
+
+/// The world view that candidate points are scored against.
+#[derive(Clone, Copy)]
+pub struct PointContext<'a, 'w, 's> {
+    /// The position of the actor the points were generated around.
+    pub origin: Vec3,
+    /// The positions of all [`TacticalThreat`]s, excluding the actor.
+    pub threats: &'a [Vec3],
+    /// All entities that aren't [`Score`] entities, for considerations that read other components,
+    /// such as [`InfluenceMap`](crate::influence::InfluenceMap)s.
+    pub entities: &'a Query<'w, 's, EntityRef<'static>, Without<Score>>,
+}
+
+impl<'a> PointContext<'a, '_, '_> {
+    /// Returns the distance from the given point to the nearest threat, or [`f32::INFINITY`] if there are none.
+    #[must_use]
+    pub fn distance_to_nearest_threat(&self, point: Vec3) -> f32 {
+        self.threats
+            .iter()
+            .map(|threat| threat.distance(point))
+            .fold(f32::INFINITY, f32::min)
+    }
+
+    /// Returns the component `C` of the given entity, if it exists and isn't a [`Score`] entity.
+    #[must_use]
+    pub fn get<C: Component>(&self, entity: Entity) -> Option<&'a C> {
+        self.entities.get(entity).ok()?.get::<C>()
+    }
+}
+
+/// Generates candidate points around an origin.
+#[reflect_trait]
+pub trait PointGenerator: Send + Sync + 'static {
+    /// Pushes the candidate points around the given origin onto `points`.
+    fn generate(&self, origin: Vec3, points: &mut Vec<Vec3>);
+}
+
+/// [`PointGenerator`] that generates evenly spaced points on a ring around the origin.
+#[derive(Reflect, Clone, Copy, PartialEq, Debug)]
+#[reflect(PointGenerator, PartialEq, Debug)]
+pub struct RingGenerator {
+    /// The distance of the points from the origin.
+    pub radius: f32,
+    /// The number of points on the ring.
+    pub count: usize,
+    /// The normal of the plane the ring lies in.
+    pub normal: Vec3,
+}
+
+impl RingGenerator {
+    /// Creates a new ring generator in the XZ plane.
+    #[must_use]
+    pub fn new(radius: f32, count: usize) -> Self {
+        Self {
+            radius,
+            count,
+            normal: Vec3::Y,
+        }
+    }
+
+    /// Sets the normal of the plane the ring lies in, such as [`Vec3::Z`] for 2D games.
+    #[must_use]
+    pub fn with_normal(mut self, normal: Vec3) -> Self {
+        self.normal = normal;
+        self
+    }
+}
+
+impl PointGenerator for RingGenerator {
+    fn generate(&self, origin: Vec3, points: &mut Vec<Vec3>) {
+        let (u, v) = self.normal.normalize_or(Vec3::Y).any_orthonormal_pair();
+        for i in 0..self.count {
+            let angle = std::f32::consts::TAU * i as f32 / self.count as f32;
+            let (sin, cos) = angle.sin_cos();
+            points.push(origin + (u * cos + v * sin) * self.radius);
+        }
+    }
+}
+
+/// [`PointGenerator`] that generates a square grid of points centered on the origin.
+#[derive(Reflect, Clone, Copy, PartialEq, Debug)]
+#[reflect(PointGenerator, PartialEq, Debug)]
+pub struct GridGenerator {
+    /// The distance between neighboring points.
+    pub spacing: f32,
+    /// The number of points from the origin to each edge of the grid.
+    pub extent: u32,
+    /// The normal of the plane the grid lies in.
+    pub normal: Vec3,
+}
+
+impl GridGenerator {
+    /// Creates a new grid generator in the XZ plane.
+    #[must_use]
+    pub fn new(spacing: f32, extent: u32) -> Self {
+        Self {
+            spacing,
+            extent,
+            normal: Vec3::Y,
+        }
+    }
+
+    /// Sets the normal of the plane the grid lies in, such as [`Vec3::Z`] for 2D games.
+    #[must_use]
+    pub fn with_normal(mut self, normal: Vec3) -> Self {
+        self.normal = normal;
+        self
+    }
+}
+
+impl PointGenerator for GridGenerator {
+    fn generate(&self, origin: Vec3, points: &mut Vec<Vec3>) {
+        let (u, v) = self.normal.normalize_or(Vec3::Y).any_orthonormal_pair();
+        let extent = self.extent as i32;
+        for x in -extent..=extent {
+            for y in -extent..=extent {
+                points.push(origin + (u * x as f32 + v * y as f32) * self.spacing);
+            }
+        }
+    }
+}
+
+/// Any [`Fn`] can generate points, such as one sampling a navmesh around the origin.
+impl<F> PointGenerator for F
+where
+    F: Fn(Vec3, &mut Vec<Vec3>) + Send + Sync + 'static,
+{
+    fn generate(&self, origin: Vec3, points: &mut Vec<Vec3>) {
+        self(origin, points);
+    }
+}
+
+/// Measures a single candidate point, returning a raw value to be normalized by an [`Evaluator`].
+#[reflect_trait]
+pub trait PointConsideration: Send + Sync + 'static {
+    /// Measures the given candidate point.
+    fn consider(&self, point: Vec3, context: &PointContext) -> f32;
+}
+
+/// [`PointConsideration`] that measures the distance from the candidate point to the actor.
+#[derive(Reflect, Clone, Copy, PartialEq, Debug, Default)]
+#[reflect(PointConsideration, PartialEq, Debug, Default)]
+pub struct DistanceToOrigin;
+
+impl PointConsideration for DistanceToOrigin {
+    fn consider(&self, point: Vec3, context: &PointContext) -> f32 {
+        point.distance(context.origin)
+    }
+}
+
+/// [`PointConsideration`] that measures the distance from the candidate point to the nearest [`TacticalThreat`].
+#[derive(Reflect, Clone, Copy, PartialEq, Debug, Default)]
+#[reflect(PointConsideration, PartialEq, Debug, Default)]
+pub struct DistanceToNearestThreat;
+
+impl PointConsideration for DistanceToNearestThreat {
+    fn consider(&self, point: Vec3, context: &PointContext) -> f32 {
+        context.distance_to_nearest_threat(point)
+    }
+}
+
+impl<F> PointConsideration for F
+where
+    F: Fn(Vec3, &PointContext) -> f32 + Send + Sync + 'static,
+{
+    fn consider(&self, point: Vec3, context: &PointContext) -> f32 {
+        self(point, context)
+    }
+}
@@ -0,0 +1,546 @@
+//! Smart objects are world entities, like a well or a bed, that advertise the actions they offer to nearby actors.
+//!
+//! Instead of every actor knowing about every action up front, a [`SmartObject`] carries a list of [`Advertisement`]s.
+//! Actors marked with [`SmartObjectSeeker`] pick up those advertisements as [`Picker`] choices while they're within
+//! the object's range, and drop them again when they leave.
+//! Each advertisement is added as a child [`Score`] entity of the actor, holding an [`AdvertisedChoice`].
+//!
+//! When an advertised action is initiated, the actor gets a [`UsingSmartObject`] component holding the object entity,
+//! so that action systems know which object to interact with.
+//! Objects have a limited number of slots: once all slots are in use, the object stops advertising to other actors.
+//! If an actor still initiates an advertised action on a full object, for example because several actors picked it
+//! in the same frame, the action is rejected and the actor falls back to its next-best choice.
+//!
+//! Add the [`SmartObjectPlugin`] to discover smart objects.
+//!
+//! # Example
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_observed_utility::prelude::*;
+//!
+//! #[derive(Component)]
+//! struct Idle;
+//!
+//! #[derive(Component)]
+//! struct Drink;
+//!
+//! # let mut app = App::new();
+//! # app.add_plugins((ObservedUtilityPlugins::RealTime, TransformPlugin));
+//! app.add_plugins(SmartObjectPlugin::default());
+//!
+//! # let world = app.world_mut();
+//! let idle = world.register_component::<Idle>();
+//! let drink = world.register_component::<Drink>();
+//!
+//! // A well that one actor at a time can drink from, within 5 units.
+//! world.spawn((
+//!     SmartObject::new(5.).with(Advertisement::new(drink, 0.8).satisfies("thirst", 8.)),
+//!     Transform::from_xyz(3., 0., 0.),
+//! ));
+//!
+//! let actor = world
+//!     .spawn((SmartObjectSeeker, Picker::new(idle), Highest, Transform::default()))
+//!     .id();
+//!
+//! # world.flush();
+//! # app.update();
+//! # let world = app.world_mut();
+//! # world.run_system_cached(SmartObjectPlugin::discover_smart_objects).unwrap();
+//! # world.flush();
+//! // The actor can now pick to drink from the well.
+//! assert_eq!(world.get::<Picker>(actor).unwrap().choices.len(), 1);
+//! ```
+
+use std::borrow::Cow;
+
+use bevy::{
+    ecs::{
+        component::{ComponentId, StorageType},
+        entity::EntityHashSet,
+        lifecycle::{ComponentHook, HookContext},
+        schedule::{InternedScheduleLabel, ScheduleLabel},
+        world::DeferredWorld,
+    },
+    prelude::*,
+};
+
+use crate::{
+    event::{OnActionEnded, OnActionInitiated, RejectAction},
+    picking::Picker,
+    scoring::{FixedScore, Score},
+};
+
+/// [`Plugin`] that discovers [`SmartObject`]s for [`SmartObjectSeeker`]s in the configured [`Schedule`],
+/// and tracks which actors are using which objects.
+pub struct SmartObjectPlugin {
+    /// The [`ScheduleLabel`] to discover smart objects in.
+    pub discover_in: InternedScheduleLabel,
+}
+
+impl Default for SmartObjectPlugin {
+    fn default() -> Self {
+        Self {
+            discover_in: FixedUpdate.intern(),
+        }
+    }
+}
+
+impl Plugin for SmartObjectPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(self.discover_in, Self::discover_smart_objects);
+
+        app.add_observer(Self::on_action_initiated_use)
+            .add_observer(Self::on_action_ended_stop_using);
+
+        app.register_type::<SmartObject>()
+            .register_type::<Advertisement>()
+            .register_type::<SmartObjectSeeker>()
+            .register_type::<AdvertisedChoice>()
+            .register_type::<UsingSmartObject>();
+    }
+}
+
+impl SmartObjectPlugin {
+    /// [`System`] that adds advertised choices to the [`Picker`] of each [`SmartObjectSeeker`]
+    /// for every [`SmartObject`] in range with a free slot, and removes choices for objects that are not.
+    ///
+    /// Choices for the object an actor is currently using are kept, even if it has moved out of range.
+    pub fn discover_smart_objects(
+        mut commands: Commands,
+        mut seekers: Query<SeekerData, With<SmartObjectSeeker>>,
+        objects: Query<(Entity, &GlobalTransform, &SmartObject)>,
+        offers: Query<&AdvertisedChoice>,
+    ) {
+        for (actor, transform, mut picker, children, using) in seekers.iter_mut() {
+            let available = |object: Entity, object_transform: &GlobalTransform, smart_object: &SmartObject| {
+                if using.is_some_and(|using| using.object == object) {
+                    return true;
+                }
+                smart_object.has_free_slot()
+                    && object_transform.translation().distance_squared(transform.translation())
+                        <= smart_object.range * smart_object.range
+            };
+
+            // Remove offers that are no longer available.
+            let mut offered = Vec::new();
+            for &offer_entity in children.into_iter().flatten() {
+                let Ok(offer) = offers.get(offer_entity) else {
+                    continue;
+                };
+
+                let still_available =
+                    objects
+                        .get(offer.object)
+                        .is_ok_and(|(object, object_transform, smart_object)| {
+                            offer.index < smart_object.advertisements.len()
+                                && available(object, object_transform, smart_object)
+                        });
+
+                if still_available {
+                    offered.push((offer.object, offer.index));
+                } else {
+                    picker.remove(offer_entity);
+                    commands.entity(offer_entity).despawn();
+                }
+            }
+
+            // Add offers for newly available objects.
+            for (object, object_transform, smart_object) in objects.iter() {
+                if object == actor || !available(object, object_transform, smart_object) {
+                    continue;
+                }
+
+                for (index, advertisement) in smart_object.advertisements.iter().enumerate() {
+                    if offered.contains(&(object, index)) {
+                        continue;
+                    }
+
+                    let offer_entity = commands
+                        .spawn((
+                            AdvertisedChoice {
+                                object,
+                                index,
+                                action: advertisement.action,
+                            },
+                            FixedScore::new(advertisement.score),
+                            Score::default(),
+                            ChildOf(actor),
+                        ))
+                        .id();
+                    picker.insert(offer_entity, advertisement.action);
+                }
+            }
+        }
+    }
+
+    /// [`Observer`] that inserts [`UsingSmartObject`] onto the actor when an advertised action is initiated,
+    /// and removes it when any other action is initiated.
+    ///
+    /// If all of the object's slots are already in use, the action is [rejected](RejectAction) instead.
+    pub fn on_action_initiated_use(
+        trigger: On<OnActionInitiated>,
+        mut commands: Commands,
+        actors: Query<&Picker>,
+        offers: Query<&AdvertisedChoice>,
+        objects: Query<&SmartObject>,
+    ) {
+        let actor = trigger.event().entity;
+        let action = trigger.event().action;
+        let Ok(picker) = actors.get(actor) else {
+            return;
+        };
+
+        let offer = picker
+            .picked_entity
+            .filter(|_| picker.picked == action)
+            .and_then(|entity| offers.get(entity).ok())
+            .filter(|offer| offer.action == action);
+
+        if let Some(offer) = offer {
+            let full = objects
+                .get(offer.object)
+                .is_ok_and(|object| !object.users.contains(&actor) && !object.has_free_slot());
+            if full {
+                commands.trigger(RejectAction { entity: actor, action });
+                return;
+            }
+
+            commands.entity(actor).insert(UsingSmartObject {
+                object: offer.object,
+                action,
+            });
+        } else {
+            commands.entity(actor).remove::<UsingSmartObject>();
+        }
+    }
+
+    /// [`Observer`] that removes [`UsingSmartObject`] from the actor when its advertised action ends.
+    pub fn on_action_ended_stop_using(
+        trigger: On<OnActionEnded>,
+        mut commands: Commands,
+        actors: Query<&UsingSmartObject>,
+    ) {
+        let actor = trigger.event().entity;
+        if actors
+            .get(actor)
+            .is_ok_and(|using| using.action == trigger.event().action)
+        {
+            commands.entity(actor).remove::<UsingSmartObject>();
+        }
+    }
+}
+
+/// [`QueryData`](bevy::ecs::query::QueryData) for actors that discover smart objects.
+type SeekerData = (
+    Entity,
+    &'static GlobalTransform,
+    &'static mut Picker,
+    Option<&'static Children>,
+    Option<&'static UsingSmartObject>,
+);
+
+/// [`Component`] for world entities that advertise actions to nearby [`SmartObjectSeeker`]s.
+///
+/// See the [module docs](crate::smart_object) for more information.
+#[derive(Component, Reflect)]
+#[derive(Clone, PartialEq, Debug)]
+#[reflect(Component, PartialEq, Debug)]
+pub struct SmartObject {
+    /// The distance from the object within which actors discover its advertisements.
+    pub range: f32,
+    /// The number of actors that can use the object at the same time.
+    pub slots: usize,
+    /// The actions offered by the object.
+    pub advertisements: Vec<Advertisement>,
+    /// The actors currently using the object.
+    users: EntityHashSet,
+}
+
+impl SmartObject {
+    /// Creates a new [`SmartObject`] with the given range, a single slot, and no advertisements.
+    #[must_use]
+    pub fn new(range: f32) -> Self {
+        Self {
+            range,
+            slots: 1,
+            advertisements: Vec::new(),
+            users: EntityHashSet::default(),
+        }
+    }
+
+    /// Sets the number of actors that can use the object at the same time.
+    #[must_use]
+    pub fn with_slots(mut self, slots: usize) -> Self {
+        self.slots = slots;
+        self
+    }
+
+    /// Adds an [`Advertisement`] for an action offered by the object.
+    #[must_use]
+    pub fn with(mut self, advertisement: Advertisement) -> Self {
+        self.advertisements.push(advertisement);
+        self
+    }
+
+    /// Returns the actors currently using the object.
+    #[must_use]
+    pub fn users(&self) -> &EntityHashSet {
+        &self.users
+    }
+
+    /// Returns `true` if another actor can use the object.
+    #[must_use]
+    pub fn has_free_slot(&self) -> bool {
+        self.users.len() < self.slots
+    }
+}
+
+/// An action offered by a [`SmartObject`], along with its score and the needs it satisfies.
+#[derive(Reflect)]
+#[derive(Clone, PartialEq, Debug)]
+#[reflect(PartialEq, Debug)]
+pub struct Advertisement {
+    /// The action [`ComponentId`] to add as a choice.
+    pub action: ComponentId,
+    /// The fixed score of the choice.
+    pub score: Score,
+    /// The names of the needs the action satisfies, and by how much per second.
+    ///
+    /// These are applied by the [`NeedsPlugin`](crate::needs::NeedsPlugin) while the object is used.
+    pub satisfies: Vec<(Cow<'static, str>, f32)>,
+}
+
+impl Advertisement {
+    /// Creates a new [`Advertisement`] for the given action with a fixed score.
+    #[must_use]
+    pub fn new(action: ComponentId, score: impl Into<Score>) -> Self {
+        Self {
+            action,
+            score: score.into(),
+            satisfies: Vec::new(),
+        }
+    }
+
+    /// Adds a need that the action satisfies, and by how much per second.
+    #[must_use]
+    pub fn satisfies(mut self, need: impl Into<Cow<'static, str>>, amount: f32) -> Self {
+        self.satisfies.push((need.into(), amount));
+        self
+    }
+
+    /// Returns how much the action satisfies the given need, or `0` if it doesn't.
+    #[must_use]
+    pub fn satisfaction(&self, need: &str) -> f32 {
+        self.satisfies
+            .iter()
+            .filter(|(name, _)| name == need)
+            .map(|(_, amount)| amount)
+            .sum()
+    }
+}
+
+/// Marker [`Component`] for actor entities that discover [`SmartObject`]s around their [`GlobalTransform`].
+#[derive(Component, Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[reflect(Component, PartialEq, Debug, Default)]
+pub struct SmartObjectSeeker;
+
+/// [`Component`] for [`Score`] entities spawned under a [`SmartObjectSeeker`] for an [`Advertisement`].
+#[derive(Component, Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[reflect(Component, PartialEq, Debug)]
+pub struct AdvertisedChoice {
+    /// The [`SmartObject`] entity that advertised the choice.
+    pub object: Entity,
+    /// The index of the [`Advertisement`] in the object's advertisements.
+    pub index: usize,
+    /// The advertised action [`ComponentId`].
+    pub action: ComponentId,
+}
+
+/// [`Component`] inserted onto the actor entity while it performs an action advertised by a [`SmartObject`].
+///
+/// While present, the actor occupies one of the object's slots.
+#[derive(Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[reflect(Component, PartialEq, Debug)]
+pub struct UsingSmartObject {
+    /// The [`SmartObject`] entity being used.
+    pub object: Entity,
+    /// The advertised action [`ComponentId`] being performed.
+    pub action: ComponentId,
+}
+
+impl Component for UsingSmartObject {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+    type Mutability = bevy::ecs::component::Immutable;
+
+    fn on_insert() -> Option<ComponentHook> {
+        Some(|mut world: DeferredWorld, context: HookContext| {
+            let Some(&using) = world.get::<UsingSmartObject>(context.entity) else {
+                return;
+            };
+            if let Some(mut object) = world.get_mut::<SmartObject>(using.object) {
+                object.users.insert(context.entity);
+            }
+        })
+    }
+
+    fn on_replace() -> Option<ComponentHook> {
+        Some(|mut world: DeferredWorld, context: HookContext| {
+            let Some(&using) = world.get::<UsingSmartObject>(context.entity) else {
+                return;
+            };
+            if let Some(mut object) = world.get_mut::<SmartObject>(using.object) {
+                object.users.remove(&context.entity);
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use crate::{
+        acting::CurrentAction,
+        event::{OnActionEnded, RequestAction, RunPicking, RunScoring},
+        picking::{Highest, Picker},
+        smart_object::{Advertisement, SmartObject, SmartObjectPlugin, SmartObjectSeeker, UsingSmartObject},
+    };
+
+    #[derive(Component)]
+    struct Idle;
+
+    #[derive(Component)]
+    struct Sleep;
+
+    #[test]
+    fn discovery_and_slots() {
+        let mut app = App::new();
+        app.add_plugins((
+            crate::ObservedUtilityPlugins::TurnBased,
+            SmartObjectPlugin::default(),
+            TransformPlugin,
+        ));
+        let world = app.world_mut();
+
+        let idle = world.register_component::<Idle>();
+        let sleep = world.register_component::<Sleep>();
+
+        let bed = world
+            .spawn((
+                SmartObject::new(5.).with(Advertisement::new(sleep, 0.9)),
+                Transform::default(),
+            ))
+            .id();
+        let first = world
+            .spawn((
+                SmartObjectSeeker,
+                Picker::new(idle),
+                Highest,
+                Transform::from_xyz(1., 0., 0.),
+            ))
+            .id();
+        let second = world
+            .spawn((
+                SmartObjectSeeker,
+                Picker::new(idle),
+                Highest,
+                Transform::from_xyz(2., 0., 0.),
+            ))
+            .id();
+
+        app.update();
+        let world = app.world_mut();
+
+        let discover = |world: &mut World| {
+            world
+                .run_system_cached(SmartObjectPlugin::discover_smart_objects)
+                .unwrap();
+            world.flush();
+        };
+
+        discover(world);
+        assert_eq!(world.get::<Picker>(first).unwrap().choices.len(), 1);
+        assert_eq!(world.get::<Picker>(second).unwrap().choices.len(), 1);
+
+        // The first actor takes the only slot.
+        world.trigger(RunScoring::all());
+        world.trigger(RunPicking::entity(first));
+        world.flush();
+        world.trigger(RequestAction::picked(first));
+        world.flush();
+        assert_eq!(world.get::<UsingSmartObject>(first).unwrap().object, bed);
+        assert!(world.get::<SmartObject>(bed).unwrap().users().contains(&first));
+
+        // The bed is no longer advertised to the second actor, but the first keeps it even out of range.
+        world.get_mut::<Transform>(first).unwrap().translation.x = 100.;
+        app.update();
+        let world = app.world_mut();
+        discover(world);
+        assert_eq!(world.get::<Picker>(first).unwrap().choices.len(), 1);
+        assert!(world.get::<Picker>(second).unwrap().choices.is_empty());
+
+        // Once the first actor is done, the slot frees up again.
+        world.trigger(OnActionEnded::cancelled(first, sleep));
+        world.flush();
+        assert!(world.get::<UsingSmartObject>(first).is_none());
+        assert!(world.get::<SmartObject>(bed).unwrap().users().is_empty());
+
+        discover(world);
+        assert!(world.get::<Picker>(first).unwrap().choices.is_empty());
+        assert_eq!(world.get::<Picker>(second).unwrap().choices.len(), 1);
+    }
+
+    #[test]
+    fn reject_over_capacity() {
+        let mut app = App::new();
+        app.add_plugins((
+            crate::ObservedUtilityPlugins::TurnBased,
+            SmartObjectPlugin::default(),
+            TransformPlugin,
+        ));
+        let world = app.world_mut();
+
+        let idle = world.register_component::<Idle>();
+        let sleep = world.register_component::<Sleep>();
+
+        let bed = world
+            .spawn((
+                SmartObject::new(5.).with(Advertisement::new(sleep, 0.9)),
+                Transform::default(),
+            ))
+            .id();
+        let [first, second] = [1., 2.].map(|x| {
+            world
+                .spawn((
+                    SmartObjectSeeker,
+                    Picker::new(idle),
+                    Highest,
+                    Transform::from_xyz(x, 0., 0.),
+                ))
+                .id()
+        });
+        app.update();
+        let world = app.world_mut();
+        world
+            .run_system_cached(SmartObjectPlugin::discover_smart_objects)
+            .unwrap();
+        world.flush();
+
+        // Both actors pick the bed in the same frame, but only one gets the single slot.
+        world.trigger(RunScoring::all());
+        world.trigger(RunPicking::all());
+        world.flush();
+        world.trigger(RequestAction::picked(first));
+        world.trigger(RequestAction::picked(second));
+        world.flush();
+
+        assert_eq!(world.get::<CurrentAction>(first).unwrap().0, sleep);
+        assert_eq!(world.get::<CurrentAction>(second).unwrap().0, idle);
+        assert!(world.get::<UsingSmartObject>(second).is_none());
+        assert_eq!(world.get::<SmartObject>(bed).unwrap().users().len(), 1);
+    }
+}
@@ -0,0 +1,404 @@
+//! Stimuli are event-driven inputs, such as a heard gunshot or an insult, that spike scores and decay over time.
+//!
+//! Unlike [`score_ancestor`](crate::scoring::score_ancestor), which only reads current state,
+//! stimuli let gameplay code push one-off events at an actor:
+//! - Trigger [`Stimulate`] on an actor entity with a [`Stimuli`] component to record a stimulus of a named kind.
+//! - [`Stimulus`] score entities read the [`Stimuli`] of the closest ancestor entity that has one,
+//!   jumping to the stimulus intensity and decaying back along a curve over time.
+//!
+//! Multiple stimuli of the same kind either stack or take the maximum, see [`StimulusStacking`].
+//! Stimuli decay over the elapsed [`Time`], so without a [`Time`] resource they never decay.
+//!
+//! # Example
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_observed_utility::prelude::*;
+//!
+//! # let mut app = App::new();
+//! # app.add_plugins(ObservedUtilityPlugins::RealTime);
+//! # app.init_resource::<Time>();
+//! # let world = app.world_mut();
+//! // Startled by gunshots for 4 seconds.
+//! let startled = world
+//!     .spawn((Stimulus::new("gunshot", 4.), Score::default()))
+//!     .id();
+//!
+//! let actor = world.spawn(Stimuli::default()).add_child(startled).id();
+//!
+//! world.trigger(Stimulate::new(actor, "gunshot", 0.8));
+//! # world.flush();
+//! # world.resource_mut::<Time>().advance_by(std::time::Duration::from_secs(1));
+//! # world.trigger(RunScoring::entity(startled));
+//! # world.flush();
+//! # approx::assert_relative_eq!(world.get::<Score>(startled).unwrap().get(), 0.6);
+//! ```
+
+use std::borrow::Cow;
+
+use bevy::{
+    ecs::{
+        component::StorageType,
+        lifecycle::{ComponentHook, HookContext},
+        world::DeferredWorld,
+    },
+    prelude::*,
+};
+
+use crate::{
+    ecs::{AncestorQuery, DeferredWorldExt, elapsed_secs},
+    event::OnScore,
+    scoring::{Evaluator, LinearEvaluator, Score},
+};
+
+/// [`Plugin`] that registers the stimulus types.
+///
+/// This plugin is included in [`ObservedUtilityPlugins`](crate::ObservedUtilityPlugins).
+#[derive(Default)]
+pub struct StimulusPlugin;
+
+impl Plugin for StimulusPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Stimuli>()
+            .register_type::<ReceivedStimulus>()
+            .register_type::<StimulusStacking>()
+            .register_type::<Stimulate>();
+
+        // Note: Stimulus cannot be reflected due to the boxed Evaluator trait object
+    }
+}
+
+/// Trigger this [`Event`] to record a stimulus of a named kind on the target actor entity's [`Stimuli`].
+#[derive(Event, Reflect)]
+#[derive(Clone, PartialEq, Debug)]
+#[reflect(PartialEq, Debug)]
+pub struct Stimulate {
+    /// The actor entity that received the stimulus.
+    pub entity: Entity,
+    /// The kind of stimulus, such as `"gunshot"`.
+    pub kind: Cow<'static, str>,
+    /// The intensity of the stimulus, usually from `0` to `1`.
+    pub intensity: f32,
+}
+
+impl Stimulate {
+    /// Creates a new [`Stimulate`] event for the target actor entity.
+    #[must_use]
+    pub fn new(entity: Entity, kind: impl Into<Cow<'static, str>>, intensity: f32) -> Self {
+        Self {
+            entity,
+            kind: kind.into(),
+            intensity,
+        }
+    }
+}
+
+/// A stimulus received by an actor, as recorded in its [`Stimuli`].
+#[derive(Reflect)]
+#[derive(Clone, PartialEq, Debug)]
+#[reflect(PartialEq, Debug)]
+pub struct ReceivedStimulus {
+    /// The kind of stimulus.
+    pub kind: Cow<'static, str>,
+    /// The intensity of the stimulus.
+    pub intensity: f32,
+    /// The elapsed [`Time`], in seconds, the stimulus was received at.
+    pub received: f64,
+}
+
+/// [`Component`] for actor entities that receive [`Stimulate`] events.
+///
+/// Stimuli older than the maximum age are forgotten whenever a new stimulus is received.
+///
+/// See the [module docs](crate::stimulus) for more information.
+#[derive(Reflect)]
+#[derive(Clone, PartialEq, Debug)]
+#[reflect(Component, PartialEq, Debug)]
+pub struct Stimuli {
+    /// The received stimuli, oldest first.
+    received: Vec<ReceivedStimulus>,
+    /// How long, in seconds, stimuli are kept for.
+    max_age: f32,
+}
+
+impl Default for Stimuli {
+    fn default() -> Self {
+        Self {
+            received: Vec::new(),
+            max_age: 60.,
+        }
+    }
+}
+
+impl Stimuli {
+    /// Sets how long, in seconds, stimuli are kept for.
+    ///
+    /// This should be at least as long as the longest [`Stimulus`] duration reading them.
+    #[must_use]
+    pub fn with_max_age(mut self, max_age: f32) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    /// Returns an iterator over all received stimuli, oldest first.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &ReceivedStimulus> {
+        self.received.iter()
+    }
+
+    /// Returns an iterator over all received stimuli of the given kind, oldest first.
+    pub fn of_kind<'a>(&'a self, kind: &'a str) -> impl DoubleEndedIterator<Item = &'a ReceivedStimulus> {
+        self.received.iter().filter(move |stimulus| stimulus.kind == kind)
+    }
+
+    /// Records a stimulus received at the given time, forgetting stimuli older than the maximum age.
+    pub fn receive(&mut self, kind: impl Into<Cow<'static, str>>, intensity: f32, now: f64) {
+        let max_age = f64::from(self.max_age);
+        self.received.retain(|stimulus| now - stimulus.received <= max_age);
+        self.received.push(ReceivedStimulus {
+            kind: kind.into(),
+            intensity,
+            received: now,
+        });
+    }
+
+    /// Forgets all received stimuli.
+    pub fn clear(&mut self) {
+        self.received.clear();
+    }
+
+    /// [`Observer`] that records [`Stimulate`] events on actors with [`Stimuli`].
+    fn observer(trigger: On<Stimulate>, time: Option<Res<Time>>, mut actors: Query<&mut Stimuli>) {
+        let Stimulate {
+            entity,
+            ref kind,
+            intensity,
+        } = *trigger.event();
+        let Ok(mut stimuli) = actors.get_mut(entity) else {
+            // The entity does not receive stimuli.
+            return;
+        };
+
+        stimuli.receive(kind.clone(), intensity, elapsed_secs(time.as_deref()));
+    }
+}
+
+impl Component for Stimuli {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+    type Mutability = bevy::ecs::component::Mutable;
+
+    fn on_add() -> Option<ComponentHook> {
+        Some(|mut world: DeferredWorld, _context: HookContext| {
+            #[derive(Resource, Default)]
+            struct StimuliObserverSpawned;
+
+            world.once::<StimuliObserverSpawned>().observe(Self::observer);
+        })
+    }
+}
+
+/// How a [`Stimulus`] combines multiple stimuli of its kind.
+#[derive(Reflect)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[reflect(PartialEq, Debug, Default)]
+pub enum StimulusStacking {
+    /// Takes the strongest decayed stimulus.
+    #[default]
+    Max,
+    /// Adds up all decayed stimuli.
+    Stack,
+}
+
+/// [`Score`] [`Component`] that jumps to the intensity of a received stimulus and decays back to `0` over time.
+///
+/// The stimuli are read from the [`Stimuli`] of the closest ancestor entity that has one.
+/// Each stimulus of the configured kind is multiplied by the decay curve, evaluated with its age
+/// as a fraction of the duration, from `0` when received to `1` when it has fully decayed.
+/// The default curve decays linearly.
+pub struct Stimulus {
+    /// The kind of stimulus to score.
+    kind: Cow<'static, str>,
+    /// How long, in seconds, a stimulus takes to decay.
+    duration: f32,
+    /// How multiple stimuli are combined.
+    stacking: StimulusStacking,
+    /// The decay curve, mapping the fraction of the duration passed to a multiplier, if not linear.
+    curve: Option<Box<dyn Evaluator>>,
+}
+
+impl Stimulus {
+    /// Creates a new [`Stimulus`] for the named kind that decays linearly over `duration` seconds.
+    #[must_use]
+    pub fn new(kind: impl Into<Cow<'static, str>>, duration: f32) -> Self {
+        Self {
+            kind: kind.into(),
+            duration,
+            stacking: StimulusStacking::default(),
+            curve: None,
+        }
+    }
+
+    /// Sets how multiple stimuli are combined.
+    #[must_use]
+    pub fn with_stacking(mut self, stacking: StimulusStacking) -> Self {
+        self.stacking = stacking;
+        self
+    }
+
+    /// Decays stimuli along the given [`Evaluator`] curve,
+    /// mapping the fraction of the duration passed, from `0` to `1`, to a multiplier.
+    #[must_use]
+    pub fn with_curve(mut self, curve: impl Evaluator) -> Self {
+        self.curve = Some(Box::new(curve));
+        self
+    }
+
+    /// Returns the kind of stimulus to score.
+    #[must_use]
+    pub fn kind(&self) -> &str {
+        &self.kind
+    }
+
+    /// Returns how multiple stimuli are combined.
+    #[must_use]
+    pub fn stacking(&self) -> StimulusStacking {
+        self.stacking
+    }
+
+    /// Returns the decayed value of the received stimuli at the given time, in seconds.
+    #[must_use]
+    pub fn evaluate(&self, stimuli: &Stimuli, now: f64) -> f32 {
+        let linear = LinearEvaluator::from_range(1., 0.);
+        let decayed = stimuli.of_kind(&self.kind).filter_map(|stimulus| {
+            let progress = (now - stimulus.received) as f32 / self.duration;
+            if !(0. ..=1.).contains(&progress) {
+                return None;
+            }
+            let multiplier = self
+                .curve
+                .as_ref()
+                .map_or_else(|| linear.evaluate(progress), |curve| curve.evaluate(progress));
+            Some(stimulus.intensity * multiplier)
+        });
+
+        match self.stacking {
+            StimulusStacking::Max => decayed.fold(0., f32::max),
+            StimulusStacking::Stack => decayed.sum(),
+        }
+    }
+
+    /// [`Observer`] for [`Stimulus`] [`Score`] entities that scores the decayed stimuli.
+    fn observer(
+        trigger: On<OnScore>,
+        time: Option<Res<Time>>,
+        mut target: Query<(&mut Score, &Stimulus)>,
+        mut actors: AncestorQuery<&'static Stimuli>,
+    ) {
+        let entity = trigger.event().entity;
+        let Ok((mut actor_score, settings)) = target.get_mut(entity) else {
+            // The entity is not scoring for a stimulus.
+            return;
+        };
+
+        let value = actors
+            .get(entity)
+            .map_or(0., |stimuli| settings.evaluate(stimuli, elapsed_secs(time.as_deref())));
+        *actor_score = Score::new(value);
+    }
+}
+
+impl Component for Stimulus {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+    type Mutability = bevy::ecs::component::Immutable;
+
+    fn on_add() -> Option<ComponentHook> {
+        Some(|mut world: DeferredWorld, _context: HookContext| {
+            #[derive(Resource, Default)]
+            struct StimulusObserverSpawned;
+
+            world.once::<StimulusObserverSpawned>().observe(Self::observer);
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use approx::assert_relative_eq;
+    use bevy::prelude::*;
+
+    use crate::{
+        event::RunScoring,
+        scoring::{PowerEvaluator, Score},
+        stimulus::{Stimulate, Stimuli, Stimulus, StimulusStacking},
+    };
+
+    #[test]
+    fn stack_and_decay() {
+        let mut app = App::new();
+        app.add_plugins(crate::ObservedUtilityPlugins::TurnBased);
+        app.init_resource::<Time>();
+        let world = app.world_mut();
+
+        let max = world.spawn((Stimulus::new("insult", 10.), Score::default())).id();
+        let stack = world
+            .spawn((
+                Stimulus::new("insult", 10.).with_stacking(StimulusStacking::Stack),
+                Score::default(),
+            ))
+            .id();
+        let curved = world
+            .spawn((
+                // Eases out, lingering before dropping off.
+                Stimulus::new("insult", 10.).with_curve(PowerEvaluator::new(2., Vec2::new(0., 1.), Vec2::new(1., 0.))),
+                Score::default(),
+            ))
+            .id();
+        let actor = world
+            .spawn(Stimuli::default().with_max_age(10.))
+            .add_children(&[max, stack, curved])
+            .id();
+
+        let score = |world: &mut World, entity| {
+            world.trigger(RunScoring::entity(entity));
+            world.flush();
+            world.get::<Score>(entity).unwrap().get()
+        };
+
+        // Calm until insulted.
+        assert_eq!(score(world, max), 0.);
+
+        world.trigger(Stimulate::new(actor, "insult", 0.4));
+        world.trigger(Stimulate::new(actor, "gunshot", 1.));
+        world.flush();
+        world.resource_mut::<Time>().advance_by(Duration::from_secs(5));
+        world.trigger(Stimulate::new(actor, "insult", 0.25));
+        world.flush();
+
+        assert_relative_eq!(score(world, max), 0.25);
+        assert_relative_eq!(score(world, stack), 0.2 + 0.25);
+        assert_relative_eq!(score(world, curved), 0.4 * 0.75);
+
+        // The first insult is forgotten once a new stimulus is received after the maximum age.
+        world.resource_mut::<Time>().advance_by(Duration::from_secs(6));
+        world.trigger(Stimulate::new(actor, "gunshot", 1.));
+        world.flush();
+        assert_eq!(world.get::<Stimuli>(actor).unwrap().of_kind("insult").count(), 1);
+        assert_relative_eq!(score(world, stack), 0.25 * 0.4);
+    }
+
+    #[test]
+    fn without_time() {
+        let mut app = App::new();
+        app.add_plugins(crate::ObservedUtilityPlugins::TurnBased);
+        let world = app.world_mut();
+
+        let startled = world.spawn((Stimulus::new("gunshot", 4.), Score::default())).id();
+        let actor = world.spawn(Stimuli::default()).add_child(startled).id();
+
+        world.trigger(Stimulate::new(actor, "gunshot", 0.8));
+        world.trigger(RunScoring::entity(startled));
+        world.flush();
+        assert_relative_eq!(world.get::<Score>(startled).unwrap().get(), 0.8);
+    }
+}
@@ -0,0 +1,368 @@
+//! Think rates let actors re-decide less often than every tick, so thousands of background actors stay cheap.
+//!
+//! An actor entity with a [`ThinkRate`] component is scored and picked every `interval` seconds,
+//! instead of by [`RunScoring::all`] and [`RunPicking::all`].
+//! Each actor's first think is offset by its phase, so actors with the same interval don't all think on the same tick.
+//! Use [`ThinkRate::staggered`] to spread actors evenly by their [`Entity`].
+//!
+//! Level of detail (LOD) picks the interval from an actor's [`ThinkImportance`], such as its closeness to the camera,
+//! which is written by your own systems.
+//! The [`ThinkTiers`] resource maps importance to intervals, and actors less important than all tiers are frozen:
+//! they keep their current action, without being scored, picked or requested a new action.
+//!
+//! Add the [`ThinkPlugin`] to run think rates and apply tiers.
+//!
+//! # Example
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_observed_utility::prelude::*;
+//!
+//! #[derive(Component)]
+//! struct Idle;
+//! #[derive(Component)]
+//! struct Player;
+//!
+//! /// Actors closer to the player are more important.
+//! fn importance_by_distance(
+//!     player: Single<&Transform, With<Player>>,
+//!     mut actors: Query<(&Transform, &mut ThinkImportance)>,
+//! ) {
+//!     for (transform, mut importance) in actors.iter_mut() {
+//!         importance.0 = -transform.translation.distance(player.translation);
+//!     }
+//! }
+//!
+//! # let mut app = App::new();
+//! # app.add_plugins(ObservedUtilityPlugins::RealTime);
+//! app.add_plugins(ThinkPlugin::default())
+//!     // Within 20 units think every tick, within 100 units every second, and freeze beyond that.
+//!     .insert_resource(ThinkTiers::default().with(-20., 0.).with(-100., 1.))
+//!     .add_systems(FixedPostUpdate, importance_by_distance.before(ThinkPlugin::apply_tiers));
+//!
+//! # let world = app.world_mut();
+//! let idle = world.register_component::<Idle>();
+//! let actor = world.spawn((Picker::new(idle), Highest, ThinkImportance::default())).id();
+//! world.entity_mut(actor).insert(ThinkRate::staggered(1., actor));
+//! ```
+//!
+//! [`RunScoring::all`]: crate::event::RunScoring::all
+//! [`RunPicking::all`]: crate::event::RunPicking::all
+
+use bevy::{
+    ecs::schedule::{InternedScheduleLabel, ScheduleLabel},
+    prelude::*,
+};
+
+use crate::{
+    RealtimeLifecyclePlugin,
+    ecs::{score_and_pick, tick_interval},
+    picking::SelfScheduled,
+};
+
+/// [`Plugin`] that applies [`ThinkTiers`] and runs [`ThinkRate`]s in the configured [`Schedule`].
+pub struct ThinkPlugin {
+    /// The [`ScheduleLabel`] to apply tiers and run think rates in, before the [`RealtimeLifecyclePlugin`]
+    /// requests actions, so that actors act on their latest pick in the same tick.
+    pub update_in: InternedScheduleLabel,
+}
+
+impl Default for ThinkPlugin {
+    fn default() -> Self {
+        Self {
+            update_in: FixedPostUpdate.intern(),
+        }
+    }
+}
+
+impl Plugin for ThinkPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ThinkTiers>();
+
+        app.add_systems(
+            self.update_in,
+            (Self::apply_tiers, Self::think)
+                .chain()
+                .before(RealtimeLifecyclePlugin::request_action_if_none_or_default),
+        );
+
+        app.register_type::<ThinkRate>()
+            .register_type::<ThinkImportance>()
+            .register_type::<ThinkTiers>();
+    }
+}
+
+impl ThinkPlugin {
+    /// [`System`] that sets the interval of each [`ThinkRate`] from its actor's [`ThinkImportance`],
+    /// freezing actors less important than all [`ThinkTiers`].
+    ///
+    /// Does nothing if there are no tiers.
+    pub fn apply_tiers(tiers: Res<ThinkTiers>, mut actors: Query<(&ThinkImportance, &mut ThinkRate)>) {
+        if tiers.is_empty() {
+            return;
+        }
+
+        for (importance, mut rate) in actors.iter_mut() {
+            let interval = tiers.interval_for(importance.0);
+            let frozen = interval.is_none();
+            if rate.frozen != frozen {
+                rate.frozen = frozen;
+            }
+            if let Some(interval) = interval
+                && rate.interval != interval
+            {
+                rate.set_interval(interval);
+            }
+        }
+    }
+
+    /// [`System`] that ticks all [`ThinkRate`]s by the [`Time`] delta,
+    /// then scores and picks the actors that are due to think.
+    pub fn think(mut commands: Commands, time: Res<Time>, mut actors: Query<(Entity, &mut ThinkRate)>) {
+        for (entity, mut rate) in actors.iter_mut() {
+            if rate.tick(time.delta_secs()) {
+                commands.queue(score_and_pick(entity, false));
+            }
+        }
+    }
+}
+
+/// [`Component`] for actor entities that think, i.e. are scored and picked, every `interval` seconds.
+///
+/// See the [module docs](crate::think) for more information.
+#[derive(Component, Reflect)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[reflect(Component, PartialEq, Debug)]
+#[require(SelfScheduled)]
+pub struct ThinkRate {
+    /// The number of seconds between thinks, or `0` to think every tick.
+    interval: f32,
+    /// The number of seconds since the last think.
+    elapsed: f32,
+    /// Whether the actor is frozen on its current action.
+    frozen: bool,
+}
+
+impl ThinkRate {
+    /// Creates a new [`ThinkRate`] that thinks every `interval` seconds, starting immediately.
+    #[must_use]
+    pub fn new(interval: f32) -> Self {
+        let interval = interval.max(0.);
+        Self {
+            interval,
+            elapsed: interval,
+            frozen: false,
+        }
+    }
+
+    /// Creates a new [`ThinkRate`] that thinks every `interval` seconds,
+    /// with a phase spread evenly over the interval by the [`Entity`] of the actor.
+    #[must_use]
+    pub fn staggered(interval: f32, entity: Entity) -> Self {
+        /// The fractional part of the golden ratio, which spreads consecutive indices evenly.
+        const GOLDEN_RATIO_FRACT: f64 = 0.618_033_988_749_894_8;
+
+        #[expect(clippy::cast_possible_truncation)]
+        let phase = (f64::from(entity.index_u32()) * GOLDEN_RATIO_FRACT).fract() as f32;
+        Self::new(interval).with_phase(phase)
+    }
+
+    /// Delays the first think by a fraction of the interval, from `0` to `1`.
+    #[must_use]
+    pub fn with_phase(mut self, phase: f32) -> Self {
+        self.elapsed = self.interval * (1. - phase.clamp(0., 1.));
+        self
+    }
+
+    /// Returns the number of seconds between thinks.
+    #[must_use]
+    pub fn interval(&self) -> f32 {
+        self.interval
+    }
+
+    /// Sets the number of seconds between thinks, keeping the progress towards the next think.
+    pub fn set_interval(&mut self, interval: f32) {
+        let interval = interval.max(0.);
+        self.elapsed = if self.interval > 0. {
+            self.elapsed / self.interval * interval
+        } else {
+            interval
+        };
+        self.interval = interval;
+    }
+
+    /// Returns `true` if the actor is frozen on its current action.
+    #[must_use]
+    pub fn is_frozen(&self) -> bool {
+        self.frozen
+    }
+
+    /// Sets whether the actor is frozen on its current action.
+    pub fn set_frozen(&mut self, frozen: bool) {
+        self.frozen = frozen;
+    }
+
+    /// Advances the think rate by the given number of seconds, returning `true` if the actor is due to think.
+    ///
+    /// The time past the interval counts towards the next think, so the actor keeps its cadence and phase.
+    /// Frozen actors are never due.
+    pub fn tick(&mut self, seconds: f32) -> bool {
+        if self.frozen {
+            return false;
+        }
+        tick_interval(&mut self.elapsed, self.interval, seconds)
+    }
+}
+
+/// [`Component`] for the importance of an actor entity with a [`ThinkRate`], used to pick its [`ThinkTiers`] tier.
+///
+/// Higher is more important. Write it from your own systems, such as from the distance to the camera.
+#[derive(Component, Reflect)]
+#[derive(Clone, Copy, PartialEq, PartialOrd, Debug, Default)]
+#[reflect(Component, PartialEq, Debug, Default)]
+pub struct ThinkImportance(pub f32);
+
+/// [`Resource`] for the level of detail tiers that pick the interval of [`ThinkRate`]s from [`ThinkImportance`].
+///
+/// See the [module docs](crate::think) for more information.
+#[derive(Resource, Reflect)]
+#[derive(Clone, PartialEq, Debug, Default)]
+#[reflect(Resource, PartialEq, Debug, Default)]
+pub struct ThinkTiers {
+    /// The minimum importance and interval of each tier, by descending importance.
+    tiers: Vec<(f32, f32)>,
+}
+
+impl ThinkTiers {
+    /// Adds a tier for actors at least as important as `min_importance`, thinking every `interval` seconds.
+    #[must_use]
+    pub fn with(mut self, min_importance: f32, interval: f32) -> Self {
+        self.insert(min_importance, interval);
+        self
+    }
+
+    /// Inserts a tier for actors at least as important as `min_importance`, thinking every `interval` seconds.
+    pub fn insert(&mut self, min_importance: f32, interval: f32) {
+        let index = self.tiers.partition_point(|&(min, _)| min > min_importance);
+        self.tiers.insert(index, (min_importance, interval.max(0.)));
+    }
+
+    /// Returns an iterator over the minimum importance and interval of each tier, by descending importance.
+    pub fn iter(&self) -> impl Iterator<Item = (f32, f32)> {
+        self.tiers.iter().copied()
+    }
+
+    /// Returns `true` if there are no tiers.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.tiers.is_empty()
+    }
+
+    /// Returns the interval of the most important tier the importance reaches,
+    /// or [`None`] if it's less important than all tiers.
+    #[must_use]
+    pub fn interval_for(&self, importance: f32) -> Option<f32> {
+        self.tiers
+            .iter()
+            .find(|&&(min, _)| importance >= min)
+            .map(|&(_, interval)| interval)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::prelude::*;
+
+    use crate::{
+        acting::CurrentAction,
+        picking::{FirstToScore, Picker},
+        scoring::{FixedScore, Score},
+        think::{ThinkImportance, ThinkPlugin, ThinkRate, ThinkTiers},
+    };
+
+    #[derive(Component)]
+    struct Idle;
+
+    #[derive(Component)]
+    struct Wander;
+
+    #[test]
+    fn tick_keeps_phase() {
+        let mut rate = ThinkRate::new(1.).with_phase(0.5);
+        assert!(!rate.tick(0.25));
+        assert!(rate.tick(0.5));
+        // The quarter second past the interval counts towards the next think.
+        assert!(!rate.tick(0.5));
+        assert!(rate.tick(0.25));
+    }
+
+    #[test]
+    fn think_rates_and_tiers() {
+        let mut app = App::new();
+        app.add_plugins(crate::ObservedUtilityPlugins::TurnBased);
+        app.init_resource::<Time>();
+        app.insert_resource(ThinkTiers::default().with(0., 1.).with(10., 0.));
+        let world = app.world_mut();
+
+        let idle = world.register_component::<Idle>();
+        let wander = world.register_component::<Wander>();
+
+        let spawn = |world: &mut World, rate: ThinkRate, importance: f32| {
+            let scorer = world.spawn((FixedScore::new(0.8), Score::default())).id();
+            world
+                .spawn((
+                    Picker::new(idle).with(scorer, wander),
+                    FirstToScore::new(0.5),
+                    CurrentAction(idle),
+                    rate,
+                    ThinkImportance(importance),
+                ))
+                .add_child(scorer)
+                .id()
+        };
+        let near = spawn(world, ThinkRate::new(5.), 20.);
+        let mid = spawn(world, ThinkRate::new(5.).with_phase(0.5), 5.);
+        let far = spawn(world, ThinkRate::new(5.), -5.);
+
+        let run = |world: &mut World, millis: u64| {
+            world.resource_mut::<Time>().advance_by(Duration::from_millis(millis));
+            world.run_system_cached(ThinkPlugin::apply_tiers).unwrap();
+            world.run_system_cached(ThinkPlugin::think).unwrap();
+            world.flush();
+        };
+        let picked = |world: &World, actor: Entity| world.get::<Picker>(actor).unwrap().picked;
+
+        // The mid actor's first think is delayed by half its interval.
+        run(world, 0);
+        assert_eq!(picked(world, near), wander);
+        assert_eq!(picked(world, mid), idle);
+        assert_eq!(picked(world, far), idle);
+        assert!(world.get::<ThinkRate>(far).unwrap().is_frozen());
+
+        run(world, 500);
+        assert_eq!(picked(world, mid), wander);
+        assert_eq!(picked(world, far), idle);
+
+        // Once more important, the far actor unfreezes.
+        world.entity_mut(far).insert(ThinkImportance(15.));
+        run(world, 0);
+        assert_eq!(picked(world, far), wander);
+    }
+
+    #[test]
+    fn staggered_phases() {
+        let phases: Vec<f32> = (0..4)
+            .map(|index| {
+                let rate = ThinkRate::staggered(1., Entity::from_raw_u32(index).unwrap());
+                rate.interval() - rate.elapsed
+            })
+            .collect();
+        for (i, a) in phases.iter().enumerate() {
+            for b in &phases[i + 1..] {
+                assert!((a - b).abs() > 0.1);
+            }
+        }
+    }
+}
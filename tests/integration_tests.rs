@@ -202,7 +202,7 @@ fn test_action_cancellation() {
             let action = trigger.event().action;
             let action1_id = world.component_id::<Action1>().unwrap();
             if action == action1_id {
-                commands.entity(actor).insert(Action1::default());
+                commands.entity(actor).insert(Action1);
             }
         },
     );
@@ -212,7 +212,7 @@ fn test_action_cancellation() {
             let action = trigger.event().action;
             let action2_id = world.component_id::<Action2>().unwrap();
             if action == action2_id {
-                commands.entity(actor).insert(Action2::default());
+                commands.entity(actor).insert(Action2);
             }
         },
     );
@@ -568,20 +568,20 @@ fn test_realtime_lifecycle() {
         |trigger: On<OnActionInitiated>, mut commands: Commands, world: &World| {
             let actor = trigger.event().entity;
             let action = trigger.event().action;
-            if let Some(drink_action_id) = world.component_id::<DrinkAction>() {
-                if action == drink_action_id {
-                    commands.entity(actor).insert(DrinkAction::default());
-                }
+            if let Some(drink_action_id) = world.component_id::<DrinkAction>()
+                && action == drink_action_id
+            {
+                commands.entity(actor).insert(DrinkAction);
             }
         },
     );
     app.add_observer(|trigger: On<OnActionEnded>, mut commands: Commands, world: &World| {
         let actor = trigger.event().entity;
         let action = trigger.event().action;
-        if let Some(drink_action_id) = world.component_id::<DrinkAction>() {
-            if action == drink_action_id {
-                commands.entity(actor).remove::<DrinkAction>();
-            }
+        if let Some(drink_action_id) = world.component_id::<DrinkAction>()
+            && action == drink_action_id
+        {
+            commands.entity(actor).remove::<DrinkAction>();
         }
     });
 
@@ -697,6 +697,163 @@ fn test_multiple_actors() {
     assert!(world.get::<Action2>(actor2).is_none());
 }
 
+/// Test that the best point of a picked tactical choice is exposed to the initiated action
+#[test]
+fn test_tactical_target_on_action_initiated() {
+    let mut app = App::new();
+    app.add_plugins(ObservedUtilityPlugins::TurnBased);
+
+    let world = app.world_mut();
+
+    let my_action = world.register_component::<MyAction>();
+    let idle_action = world.register_component::<IdleAction>();
+
+    let mut commands = world.commands();
+    commands.spawn((TacticalThreat, Transform::from_xyz(-10., 0., 0.)));
+    let cover = commands
+        .spawn((
+            Score::default(),
+            TacticalPoints::new(RingGenerator::new(2., 4), WeightedProduct)
+                .with(DistanceToNearestThreat, LinearEvaluator::from_range(0., 20.)),
+        ))
+        .id();
+    let actor = commands
+        .spawn((
+            Transform::default(),
+            Picker::new(idle_action).with(cover, my_action),
+            Highest,
+            CurrentAction(idle_action),
+        ))
+        .add_child(cover)
+        .id();
+    world.flush();
+
+    world.commands().trigger(RunScoring::entity(cover));
+    world.commands().trigger(RunPicking::entity(actor));
+    world.commands().trigger(RequestAction::picked(actor));
+    world.flush();
+
+    assert_eq!(Some(cover), world.get::<Picker>(actor).unwrap().picked_entity);
+    let target = world
+        .get::<TacticalTarget>(actor)
+        .expect("TacticalTarget should be inserted");
+    assert_relative_eq!(2., target.0.x, epsilon = 0.0001);
+}
+
+/// Test that interrupting an actor rescores, re-picks and preempts its current action immediately
+#[test]
+fn test_interrupt_repicks_immediately() {
+    #[derive(Resource, Default)]
+    struct Cancelled(Vec<ComponentId>);
+
+    for plugins in [ObservedUtilityPlugins::TurnBased, ObservedUtilityPlugins::RealTime] {
+        let mut app = App::new();
+        app.add_plugins(plugins);
+        app.init_resource::<Cancelled>();
+        app.add_observer(|trigger: On<OnActionEnded>, mut cancelled: ResMut<Cancelled>| {
+            if trigger.event().reason == ActionEndReason::Cancelled {
+                cancelled.0.push(trigger.event().action);
+            }
+        });
+
+        let world = app.world_mut();
+
+        let attack = world.register_component::<Action1>();
+        let flee = world.register_component::<Action2>();
+        let idle_action = world.register_component::<IdleAction>();
+
+        let attack_score = world.spawn((Score::default(), FixedScore::new(0.6))).id();
+        let flee_score = world.spawn((Score::default(), FixedScore::new(0.1))).id();
+        let actor = world
+            .spawn((
+                Picker::new(idle_action)
+                    .with(attack_score, attack)
+                    .with(flee_score, flee),
+                Highest,
+                CurrentAction(attack),
+            ))
+            .add_children(&[attack_score, flee_score])
+            .id();
+
+        // Nothing changed, so the current action keeps running.
+        world.trigger(Interrupt { entity: actor });
+        world.flush();
+        assert_eq!(attack, world.get::<CurrentAction>(actor).unwrap().0);
+        assert!(world.resource::<Cancelled>().0.is_empty());
+
+        // Taking damage makes fleeing more appealing.
+        world.entity_mut(flee_score).insert(FixedScore::new(0.9));
+        world.trigger(Interrupt { entity: actor });
+        world.flush();
+
+        assert_relative_eq!(0.9, world.get::<Score>(flee_score).unwrap().get());
+        assert_eq!(flee, world.get::<Picker>(actor).unwrap().picked);
+        assert_eq!(flee, world.get::<CurrentAction>(actor).unwrap().0);
+        assert_eq!(vec![attack], world.resource::<Cancelled>().0);
+    }
+}
+
+/// Test that a rejected action falls back to the next-ranked choice, down to the default action
+#[test]
+fn test_rejected_action_falls_back_to_next_ranked() {
+    #[derive(Resource, Default)]
+    struct Blocked(Vec<ComponentId>);
+
+    let mut app = App::new();
+    app.add_plugins(ObservedUtilityPlugins::TurnBased);
+    app.init_resource::<Blocked>();
+    app.add_observer(
+        |trigger: On<OnActionInitiated>, mut commands: Commands, blocked: Res<Blocked>| {
+            let OnActionInitiated { entity, action } = *trigger.event();
+            if blocked.0.contains(&action) {
+                commands.trigger(RejectAction { entity, action });
+            }
+        },
+    );
+
+    let world = app.world_mut();
+
+    let attack = world.register_component::<Action1>();
+    let flee = world.register_component::<Action2>();
+    let idle_action = world.register_component::<IdleAction>();
+
+    let attack_score = world.spawn((Score::default(), FixedScore::new(0.9))).id();
+    let flee_score = world.spawn((Score::default(), FixedScore::new(0.5))).id();
+    let actor = world
+        .spawn((
+            Picker::new(idle_action)
+                .with(attack_score, attack)
+                .with(flee_score, flee),
+            Highest,
+        ))
+        .add_children(&[attack_score, flee_score])
+        .id();
+
+    // The path to the target is blocked, so the actor flees instead.
+    world.resource_mut::<Blocked>().0.push(attack);
+    world.trigger(RunScoring::all());
+    world.trigger(RunPicking::entity(actor));
+    world.flush();
+    world.trigger(RequestAction::picked(actor));
+    world.flush();
+
+    let picker = world.get::<Picker>(actor).unwrap();
+    assert_eq!(flee, picker.picked);
+    assert_eq!(Some(flee_score), picker.picked_entity);
+    assert_eq!(flee, world.get::<CurrentAction>(actor).unwrap().0);
+
+    // With every choice rejected, the actor falls back to its default action.
+    world.resource_mut::<Blocked>().0.push(flee);
+    world.entity_mut(actor).remove::<CurrentAction>();
+    world.trigger(RunPicking::entity(actor));
+    world.flush();
+    world.trigger(RequestAction::picked(actor));
+    world.flush();
+
+    assert_eq!(idle_action, world.get::<Picker>(actor).unwrap().picked);
+    assert_eq!(idle_action, world.get::<CurrentAction>(actor).unwrap().0);
+}
+
 // Helper components for tests
 #[derive(Component)]
 struct MyAction;